@@ -1,5 +1,5 @@
-use criterion::{criterion_group, criterion_main, Criterion};
-use sort::Sorter;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use sort::{Distribution, Sorter};
 
 fn benchmark_sorts(c: &mut Criterion) {
     let length = 10_000;
@@ -14,12 +14,56 @@ fn benchmark_sorts(c: &mut Criterion) {
 
     //bench_sorter_method!(bogo_sort);
     bench_sorter_method!(bubble_sort);
+    bench_sorter_method!(comb_sort);
+    bench_sorter_method!(gnome_sort);
     bench_sorter_method!(insertion_sort);
+    bench_sorter_method!(adaptive_insertion_sort);
+    bench_sorter_method!(counting_sort);
     bench_sorter_method!(merge_sort);
+    bench_sorter_method!(quick_sort);
+    bench_sorter_method!(heap_sort);
     bench_sorter_method!(stalin_sort);
     bench_sorter_method!(std_sort);
     bench_sorter_method!(std_sort_unstable);
 }
 
-criterion_group!(benches, benchmark_sorts);
+/// Compare each algorithm on lists of different shapes.
+///
+/// Each distribution gets its own group, so Criterion's report for that group is a table of how
+/// every algorithm performed on it, which shows which sort to use when.
+fn benchmark_sorts_by_distribution(c: &mut Criterion) {
+    let length = 10_000;
+
+    for distribution in Distribution::ALL {
+        let mut group = c.benchmark_group(distribution.name());
+        let sorter = Sorter::with_distribution(length, distribution);
+
+        macro_rules! bench_sorter_method {
+            ( $meth:ident ) => {
+                group.bench_with_input(
+                    BenchmarkId::new(stringify!($meth), length),
+                    &sorter,
+                    |b, sorter| b.iter(|| sorter.$meth()),
+                );
+            };
+        }
+
+        bench_sorter_method!(bubble_sort);
+        bench_sorter_method!(comb_sort);
+        bench_sorter_method!(gnome_sort);
+        bench_sorter_method!(insertion_sort);
+        bench_sorter_method!(adaptive_insertion_sort);
+        bench_sorter_method!(counting_sort);
+        bench_sorter_method!(merge_sort);
+        bench_sorter_method!(quick_sort);
+        bench_sorter_method!(heap_sort);
+        bench_sorter_method!(stalin_sort);
+        bench_sorter_method!(std_sort);
+        bench_sorter_method!(std_sort_unstable);
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, benchmark_sorts, benchmark_sorts_by_distribution);
 criterion_main!(benches);
@@ -0,0 +1,373 @@
+//! The core sorting algorithms, which sort a slice in place.
+//!
+//! These don't need `std`, only `alloc` for the buffer in [`merge_sort`], so they can be used on
+//! their own in `no_std` code.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Sort the slice with a bubble sort.
+pub fn bubble_sort<T: Ord>(list: &mut [T]) {
+    let mut iterations = list.len().saturating_sub(1);
+
+    for _ in 0..list.len().saturating_sub(1) {
+        for i in 0..iterations {
+            if list[i] > list[i + 1] {
+                list.swap(i, i + 1);
+            }
+        }
+        iterations -= 1;
+    }
+}
+
+/// Sort the slice with an insertion sort.
+pub fn insertion_sort<T: Ord>(list: &mut [T]) {
+    insertion_sort_by(list, T::cmp);
+}
+
+/// Sort the slice with an insertion sort, using the given comparison function, which can be used
+/// to count the comparisons.
+///
+/// Each element is compared with the ones before it, from the nearest one backwards, and swapped
+/// past every one which is bigger than it.
+pub fn insertion_sort_by<T>(list: &mut [T], mut compare: impl FnMut(&T, &T) -> Ordering) {
+    for j in 1..list.len() {
+        let mut i = j;
+        while i > 0 && compare(&list[i - 1], &list[i]) == Ordering::Greater {
+            list.swap(i - 1, i);
+            i -= 1;
+        }
+    }
+}
+
+/// Sort the slice with a merge sort. Equal elements keep their order, so this sort is stable.
+pub fn merge_sort<T: Ord + Clone>(list: &mut [T]) {
+    merge_sort_by(list, T::cmp);
+}
+
+/// Sort the slice with a merge sort, comparing the keys which the given function extracts from
+/// each element, like [`slice::sort_by_key`]. See [`merge_sort`].
+pub fn merge_sort_by_key<T: Clone, K: Ord>(list: &mut [T], mut key: impl FnMut(&T) -> K) {
+    merge_sort_by(list, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sort the slice with a merge sort, using the given comparison function. See [`merge_sort`].
+pub fn merge_sort_by<T: Clone>(list: &mut [T], mut compare: impl FnMut(&T, &T) -> Ordering) {
+    merge_sort_recursive(list, &mut compare);
+}
+
+/// Recursively merge sort the slice. The comparison function is passed by reference so that it
+/// doesn't need to be copied for every recursive call.
+fn merge_sort_recursive<T: Clone>(list: &mut [T], compare: &mut impl FnMut(&T, &T) -> Ordering) {
+    if list.len() < 2 {
+        return;
+    }
+
+    let mid = list.len() / 2;
+    let (left, right) = list.split_at_mut(mid);
+
+    // Sort the left and right halves individually
+    merge_sort_recursive(left, compare);
+    merge_sort_recursive(right, compare);
+
+    // Now we create a vector to store the newly merged list and scan through each half, adding
+    // the smaller element each iteration, or the left one if they're equal
+    let mut left_index = 0;
+    let mut right_index = mid;
+    let mut vec = Vec::with_capacity(list.len());
+
+    while left_index < mid && right_index < list.len() {
+        if compare(&list[left_index], &list[right_index]) != Ordering::Greater {
+            vec.push(list[left_index].clone());
+            left_index += 1;
+        } else {
+            vec.push(list[right_index].clone());
+            right_index += 1;
+        }
+    }
+
+    // One of these slices will be empty, but the other will contain the unmerged, sorted elements
+    vec.extend_from_slice(&list[left_index..mid]);
+    vec.extend_from_slice(&list[right_index..]);
+
+    // Then we just put the elements back into the list slice
+    list.clone_from_slice(&vec);
+}
+
+/// Sort the slice with a quicksort.
+///
+/// The middle element is used as the pivot, so sorted and reversed lists don't need quadratic
+/// time. We only recurse into the smaller side of each partition and loop on the bigger one, so
+/// the recursion is never more than `log2(n)` calls deep.
+pub fn quick_sort<T: Ord>(list: &mut [T]) {
+    quick_sort_by(list, T::cmp);
+}
+
+/// Sort the slice with a quicksort, comparing the keys which the given function extracts from
+/// each element, like [`slice::sort_by_key`]. See [`quick_sort`].
+pub fn quick_sort_by_key<T, K: Ord>(list: &mut [T], mut key: impl FnMut(&T) -> K) {
+    quick_sort_by(list, |a, b| key(a).cmp(&key(b)));
+}
+
+/// Sort the slice with a quicksort, using the given comparison function. See [`quick_sort`].
+pub fn quick_sort_by<T>(list: &mut [T], mut compare: impl FnMut(&T, &T) -> Ordering) {
+    quick_sort_recursive(list, &mut compare);
+}
+
+/// Quicksort the slice, only recursing into the smaller side of each partition. The comparison
+/// function is passed by reference so that it doesn't need to be copied for every recursive call.
+fn quick_sort_recursive<T>(mut list: &mut [T], compare: &mut impl FnMut(&T, &T) -> Ordering) {
+    while list.len() > 1 {
+        let pivot = partition(list, compare);
+        let (left, right) = core::mem::take(&mut list).split_at_mut(pivot);
+        // The pivot is the first element of `right`, and it's already in place
+        let right = &mut right[1..];
+
+        if left.len() < right.len() {
+            quick_sort_recursive(left, compare);
+            list = right;
+        } else {
+            quick_sort_recursive(right, compare);
+            list = left;
+        }
+    }
+}
+
+/// Partition the slice around its middle element, so that everything before that element is
+/// smaller than it, and everything after it is at least as big. Return the new index of that
+/// element.
+fn partition<T>(list: &mut [T], compare: &mut impl FnMut(&T, &T) -> Ordering) -> usize {
+    let last = list.len() - 1;
+    list.swap(list.len() / 2, last);
+
+    let mut store = 0;
+    for i in 0..last {
+        if compare(&list[i], &list[last]) == Ordering::Less {
+            list.swap(i, store);
+            store += 1;
+        }
+    }
+
+    list.swap(store, last);
+    store
+}
+
+/// Sort the slice with a heap sort.
+///
+/// We first rearrange the slice into a max heap, where every element is at least as big as its
+/// children, and then repeatedly swap the biggest element at the root to the end of the heap and
+/// shrink the heap by one.
+pub fn heap_sort<T: Ord>(list: &mut [T]) {
+    for root in (0..list.len() / 2).rev() {
+        sift_down(list, root);
+    }
+
+    for end in (1..list.len()).rev() {
+        list.swap(0, end);
+        sift_down(&mut list[..end], 0);
+    }
+}
+
+/// Move the element at `root` down the heap in the slice until it's at least as big as both its
+/// children.
+fn sift_down<T: Ord>(heap: &mut [T], mut root: usize) {
+    loop {
+        let mut biggest = root;
+        for child in [2 * root + 1, 2 * root + 2] {
+            if child < heap.len() && heap[child] > heap[biggest] {
+                biggest = child;
+            }
+        }
+
+        if biggest == root {
+            return;
+        }
+        heap.swap(root, biggest);
+        root = biggest;
+    }
+}
+
+/// Check whether the given sort is stable, by sorting a list of `(key, original_index)` pairs with
+/// lots of duplicate keys, and checking that equal keys are still in their original order.
+///
+/// The sort must only compare the keys, like with [`merge_sort_by_key`], since comparing the whole
+/// pairs would put equal keys in order of their index anyway. This can only show that a sort is
+/// unstable, since a stable result for one list doesn't mean it's stable for every list.
+pub fn is_stable_sort(sort: impl Fn(&[(u32, usize)]) -> Vec<(u32, usize)>) -> bool {
+    let list: Vec<(u32, usize)> = (0..100).map(|idx| ((idx as u32 * 7) % 5, idx)).collect();
+    let sorted = sort(&list);
+
+    sorted.len() == list.len()
+        && sorted
+            .windows(2)
+            .all(|pair| match pair[0].0.cmp(&pair[1].0) {
+                Ordering::Less => true,
+                Ordering::Equal => pair[0].1 < pair[1].1,
+                Ordering::Greater => false,
+            })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+
+    /// A sort from this module, for lists of `i32`.
+    type Sort = fn(&mut [i32]);
+
+    /// Every sort in this module, with its name.
+    const SORTS: [(Sort, &str); 5] = [
+        (bubble_sort, "bubble_sort"),
+        (insertion_sort, "insertion_sort"),
+        (merge_sort, "merge_sort"),
+        (quick_sort, "quick_sort"),
+        (heap_sort, "heap_sort"),
+    ];
+
+    #[test]
+    fn sorts_edge_cases() {
+        for list in [
+            vec![],
+            vec![1],
+            vec![2, 1],
+            vec![3, -1, 2, -1, 0],
+            vec![5; 10],
+            (0..100).collect(),
+            (0..100).rev().collect(),
+        ] {
+            let mut expected = list.clone();
+            expected.sort();
+
+            for (sort, name) in SORTS {
+                let mut output = list.clone();
+                sort(&mut output);
+                assert_eq!(output, expected, "{name} on {list:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn sorts_other_types() {
+        let words = ["pear", "apple", "fig", "banana", "apple"];
+        let mut expected = words;
+        expected.sort();
+
+        for sort in [
+            bubble_sort,
+            insertion_sort,
+            merge_sort,
+            quick_sort,
+            heap_sort,
+        ] {
+            let mut output = words;
+            sort(&mut output);
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn merge_sort_is_stable() {
+        /// A pair which is only ordered by its first element.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        struct Keyed(u32, char);
+
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut list = vec![Keyed(2, 'a'), Keyed(1, 'b'), Keyed(2, 'c'), Keyed(1, 'd')];
+        merge_sort(&mut list);
+        assert_eq!(
+            list.iter().map(|keyed| keyed.1).collect::<Vec<_>>(),
+            vec!['b', 'd', 'a', 'c']
+        );
+    }
+
+    #[test]
+    fn is_stable_sort_classifies_sorts() {
+        /// A pair which is only ordered by its key, for the sorts which don't take a key function.
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        struct Keyed((u32, usize));
+
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0 .0.cmp(&other.0 .0)
+            }
+        }
+
+        fn by_key(sort: fn(&mut [Keyed])) -> impl Fn(&[(u32, usize)]) -> Vec<(u32, usize)> {
+            move |list| {
+                let mut keyed: Vec<Keyed> = list.iter().copied().map(Keyed).collect();
+                sort(&mut keyed);
+                keyed.into_iter().map(|keyed| keyed.0).collect()
+            }
+        }
+
+        assert!(is_stable_sort(|list| {
+            let mut list = list.to_vec();
+            merge_sort_by_key(&mut list, |&(key, _)| key);
+            list
+        }));
+        assert!(is_stable_sort(by_key(insertion_sort)));
+        assert!(is_stable_sort(by_key(bubble_sort)));
+
+        assert!(!is_stable_sort(|list| {
+            let mut list = list.to_vec();
+            quick_sort_by_key(&mut list, |&(key, _)| key);
+            list
+        }));
+        assert!(!is_stable_sort(by_key(heap_sort)));
+
+        // Sorting the whole pairs gets the indices in order, even with an unstable sort
+        assert!(is_stable_sort(|list| {
+            let mut list = list.to_vec();
+            heap_sort(&mut list);
+            list
+        }));
+        assert!(!is_stable_sort(|list| list.iter().rev().copied().collect()));
+    }
+
+    #[test]
+    fn sort_by_key() {
+        let list = vec![(1, 'c'), (2, 'a'), (3, 'd'), (4, 'b'), (5, 'a')];
+        let expected = vec![(2, 'a'), (5, 'a'), (4, 'b'), (1, 'c'), (3, 'd')];
+
+        let mut output = list.clone();
+        merge_sort_by_key(&mut output, |&(_, letter)| letter);
+        assert_eq!(output, expected);
+
+        // Quicksort isn't stable, so the order of the two `'a'`s could be either way round
+        let mut output = list;
+        quick_sort_by_key(&mut output, |&(_, letter)| letter);
+        assert_eq!(
+            output.iter().map(|&(_, letter)| letter).collect::<Vec<_>>(),
+            vec!['a', 'a', 'b', 'c', 'd']
+        );
+        assert_eq!(output[2..], expected[2..]);
+    }
+
+    #[test]
+    fn insertion_sort_by_counts_comparisons() {
+        let mut comparisons = 0;
+        let mut list: Vec<u32> = (0..100).collect();
+        insertion_sort_by(&mut list, |a, b| {
+            comparisons += 1;
+            a.cmp(b)
+        });
+        assert_eq!(comparisons, 99);
+    }
+}
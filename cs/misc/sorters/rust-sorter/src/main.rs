@@ -1,44 +1,193 @@
 use num_format::{Locale, ToFormattedString};
-use sort::{Sorter, SorterMethod};
+use sort::{Mismatch, PracticalityCheck, SortResult, Sorter, SorterMethod};
+use std::collections::BTreeMap;
+use std::io::{self, Read};
 use std::sync::{mpsc, Arc};
-use std::{env, thread};
+use std::{env, fs, process, thread};
 
-/// Create a list of tuples of `Sorter` methods with their associated names.
+/// Create a list of tuples of `Sorter` methods with their associated names and the checks for
+/// whether they can be used on a given list.
 ///
-/// These tuples are intended to be used for arguments to `sort::time_sort()`.
+/// A method can be followed by `if` and the name of a `Sorter` method to use as its
+/// [`PracticalityCheck`]. Otherwise, the method can be used on any list. These tuples are intended
+/// to be filtered with `sort::practical_sorts()` and then used for arguments to `sort::time_sort()`.
 macro_rules! sorter_methods {
-    ( $( $x:ident ),*, ) => {
+    ( $( $x:ident $( if $check:ident )? ),*, ) => {
         {
-            let mut temp_vec: Vec<(SorterMethod, &str)> = Vec::new();
-            $(
-                temp_vec.push((Sorter::$x as SorterMethod, stringify!($x)));
-            )*
+            let temp_vec: Vec<(SorterMethod, &str, PracticalityCheck)> = vec![
+                $( (
+                    Sorter::$x as SorterMethod,
+                    stringify!($x),
+                    practicality_check!($( $check )?),
+                ), )*
+            ];
             temp_vec
         }
     };
 }
 
+/// Get the [`PracticalityCheck`] for [`sorter_methods`], which is the given `Sorter` method, or
+/// `sort::always_practical` if there isn't one.
+macro_rules! practicality_check {
+    () => {
+        sort::always_practical as PracticalityCheck
+    };
+    ( $check:ident ) => {
+        Sorter::$check as PracticalityCheck
+    };
+}
+
+/// Print the error message and exit with a failure code.
+fn exit_with_error(message: impl std::fmt::Display) -> ! {
+    eprintln!("ERROR: {message}");
+    process::exit(1);
+}
+
+/// Read a list of integers from the given file, or from stdin if the file is `-`.
+fn read_input_list(file: &str) -> Vec<u32> {
+    let input = if file == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map(|_| input)
+    } else {
+        fs::read_to_string(file)
+    }
+    .unwrap_or_else(|e| exit_with_error(format!("Unable to read {file}: {e}")));
+
+    match sort::parse_int_list(&input) {
+        Ok(list) if list.is_empty() => exit_with_error("The input list is empty"),
+        Ok(list) => list,
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// Sorts which are known to drop elements, so they're flagged rather than treated as failures in
+/// `--compare` mode.
+const LOSSY_SORTS: [&str; 1] = ["stalin_sort"];
+
+/// Check that every sort gives the same output as `Sorter::std_sort`, exiting with a failure code
+/// if any of them disagree, apart from the ones in [`LOSSY_SORTS`].
+fn compare(sorter: &Sorter, sorts: &[(SorterMethod, &str)]) {
+    let mut failed = false;
+
+    for Mismatch { name, index } in sort::compare_sorts(sorter, sorts) {
+        if LOSSY_SORTS.contains(&name) {
+            println!("{name} disagrees at index {index}, but it's known to lose elements");
+        } else {
+            eprintln!("ERROR: {name} disagrees with std_sort at index {index}");
+            failed = true;
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+    println!(
+        "All sorts that aren't known to be lossy agree on {} items",
+        sorter.len().to_formatted_string(&Locale::en)
+    );
+}
+
 /// Run the various sorts and time them.
+///
+/// The first argument is the length of the list, and `--json` can be passed anywhere to print a
+/// JSON object mapping each sort to its time and whether it sorted the list correctly. Instead of
+/// a random list, `--input <file>` sorts the integers in the file, or stdin if the file is `-`.
+///
+/// With `--compare`, the sorts aren't timed, but their outputs are checked against each other
+/// instead. The random list can be made reproducible with `--seed <n>`.
 fn main() {
+    let mut json = false;
+    let mut compare_mode = false;
+    let mut input_file = None;
+    let mut seed = None;
+    let mut length_arg = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--compare" => compare_mode = true,
+            "--seed" => match args.next().map(|seed| seed.parse()) {
+                Some(Ok(n)) => seed = Some(n),
+                _ => exit_with_error("--seed needs a non-negative integer"),
+            },
+            "--input" => match args.next() {
+                Some(file) => input_file = Some(file),
+                None => exit_with_error("--input needs a file name, or - for stdin"),
+            },
+            _ => length_arg = Some(arg),
+        }
+    }
+
     let default_length: u32 = 1000;
-    let length: u32 = match env::args().collect::<Vec<String>>().get(1) {
+    let length = match length_arg {
         Some(num) => num.parse().unwrap_or(default_length),
         None => default_length,
     };
+    let sorter = match (input_file, seed) {
+        (Some(file), _) => Sorter::from_vec(read_input_list(&file)),
+        (None, Some(seed)) => Sorter::new_seeded(length, seed),
+        (None, None) => Sorter::new(length),
+    };
+    let length = sorter.len();
 
     let sorts = sorter_methods![
         //bogo_sort,
         bubble_sort,
+        comb_sort,
+        gnome_sort,
         insertion_sort,
+        adaptive_insertion_sort,
+        counting_sort if counting_sort_is_practical,
         merge_sort,
         threaded_merge_sort,
+        quick_sort,
+        heap_sort,
         stalin_sort,
         std_sort,
         std_sort_unstable,
     ];
 
-    let sorter = Arc::new(Sorter::new(length));
+    // Some sorts can't handle every list, like a counting sort, which would need too much memory
+    // for a list with a very wide range of values
+    let (sorts, skipped) = sort::practical_sorts(&sorter, &sorts);
+    for name in skipped {
+        eprintln!("WARNING: skipping {name}, since it isn't practical for this list");
+    }
+
+    if compare_mode {
+        compare(&sorter, &sorts);
+        return;
+    }
+
+    let sorter = Arc::new(sorter);
     let mut handles = Vec::new();
+
+    if json {
+        let (tx, rx) = mpsc::channel();
+
+        for (method, name) in sorts {
+            let sorter = Arc::clone(&sorter);
+            let tx_new = tx.clone();
+            handles.push(thread::spawn(move || {
+                tx_new
+                    .send((name, sort::time_sort_checked(&sorter, method)))
+                    .unwrap();
+            }));
+        }
+        drop(tx);
+
+        let results: BTreeMap<&str, SortResult> = rx.into_iter().collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        println!("{}", serde_json::to_string_pretty(&results).unwrap());
+        return;
+    }
+
     let (tx, rx) = mpsc::channel();
 
     println!(
@@ -57,11 +206,11 @@ fn main() {
     }
     drop(tx);
 
-    for (name, time) in rx {
-        println!("{name} took {time:?}");
-    }
-
+    // The sorts finish in any order, so we wait for all of them before printing the table
+    let times: Vec<_> = rx.into_iter().collect();
     for handle in handles {
         handle.join().unwrap();
     }
+
+    println!("{}", sort::summary_table(&times));
 }
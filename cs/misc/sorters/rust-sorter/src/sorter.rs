@@ -0,0 +1,1111 @@
+use crate::algorithms;
+use num_format::{Locale, ToFormattedString};
+use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::{self, Duration};
+use tabled::{builder::Builder, Style};
+
+/// The different shapes of list that a `Sorter` can be created with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribution {
+    /// Every element is distinct and the list is shuffled.
+    Random,
+
+    /// The list is sorted apart from a few elements that have been swapped.
+    NearlySorted,
+
+    /// The list is sorted in descending order.
+    Reversed,
+
+    /// The list is shuffled but only contains a few distinct values.
+    ManyDuplicates,
+}
+
+impl Distribution {
+    /// Every distribution, to make it easy to iterate over them.
+    pub const ALL: [Self; 4] = [
+        Self::Random,
+        Self::NearlySorted,
+        Self::Reversed,
+        Self::ManyDuplicates,
+    ];
+
+    /// A short name for the distribution, for use in benchmark reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::NearlySorted => "nearly_sorted",
+            Self::Reversed => "reversed",
+            Self::ManyDuplicates => "many_duplicates",
+        }
+    }
+}
+
+/// Statistics about a single run of a sorting algorithm.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SortStats {
+    /// The number of times two elements were compared.
+    pub comparisons: u64,
+}
+
+impl SortStats {
+    /// Compare two elements, counting the comparison.
+    fn compare(&mut self, a: u32, b: u32) -> Ordering {
+        self.comparisons += 1;
+        a.cmp(&b)
+    }
+}
+
+#[derive(Clone)]
+pub struct Sorter {
+    list: Vec<u32>,
+}
+
+impl Sorter {
+    #![allow(dead_code)]
+
+    /// Create a new list with `length` elements and shuffle it.
+    pub fn new(length: u32) -> Self {
+        let mut list: Vec<u32> = (0..length).collect();
+        list.shuffle(&mut thread_rng());
+        Self { list }
+    }
+
+    /// Create a new list with `length` elements and shuffle it with the given seed, so that the
+    /// same seed always gives the same list.
+    pub fn new_seeded(length: u32, seed: u64) -> Self {
+        let mut list: Vec<u32> = (0..length).collect();
+        list.shuffle(&mut StdRng::seed_from_u64(seed));
+        Self { list }
+    }
+
+    /// Create a sorter for the given list, rather than a random one.
+    pub fn from_vec(list: Vec<u32>) -> Self {
+        Self { list }
+    }
+
+    /// Return the number of elements in the list.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Check if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Create a new list with `length` elements in the shape of the given distribution.
+    pub fn with_distribution(length: u32, distribution: Distribution) -> Self {
+        let mut rng = thread_rng();
+
+        let list: Vec<u32> = match distribution {
+            Distribution::Random => return Self::new(length),
+            Distribution::NearlySorted => {
+                let mut list: Vec<u32> = (0..length).collect();
+
+                // Swap about 1% of the elements, but always at least one pair
+                if length > 1 {
+                    for _ in 0..(length / 100).max(1) {
+                        let i = rng.gen_range(0..length as usize);
+                        let j = rng.gen_range(0..length as usize);
+                        list.swap(i, j);
+                    }
+                }
+                list
+            }
+            Distribution::Reversed => (0..length).rev().collect(),
+            Distribution::ManyDuplicates => {
+                let mut list: Vec<u32> = (0..length).map(|n| n % 10).collect();
+                list.shuffle(&mut rng);
+                list
+            }
+        };
+
+        Self { list }
+    }
+
+    /// Sort the list with the given method, so that methods can be chosen at runtime, like from
+    /// a list of [`SorterMethod`]s.
+    pub fn sort_with(&self, method: SorterMethod) -> Vec<u32> {
+        method(self)
+    }
+
+    /// Perform a bogo sort on the list.
+    ///
+    /// WARNING: Bogo sort shuffles the whole list until it's sorted.
+    /// Anything more than a few elements will take a long time.
+    pub fn bogo_sort(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        let mut rng = thread_rng();
+
+        while !is_sorted(&list) {
+            list.shuffle(&mut rng);
+        }
+        list
+    }
+
+    /// Perform a bubble sort on the list.
+    pub fn bubble_sort(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        algorithms::bubble_sort(&mut list);
+        list
+    }
+
+    /// Perform a comb sort on the list.
+    ///
+    /// This is like bubble sort, but it compares elements which are a gap apart, and the gap
+    /// shrinks by a factor of 1.3 each pass until it's 1, when it's just a bubble sort. Small
+    /// elements near the end of the list can move a long way in one swap, rather than one place
+    /// per pass like in bubble sort.
+    pub fn comb_sort(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        let mut gap = list.len();
+        let mut sorted = false;
+
+        while !sorted {
+            gap = (gap * 10 / 13).max(1);
+
+            // We can only be done once a pass with a gap of 1 doesn't swap anything
+            sorted = gap == 1;
+            for i in 0..list.len().saturating_sub(gap) {
+                if list[i] > list[i + gap] {
+                    list.swap(i, i + gap);
+                    sorted = false;
+                }
+            }
+        }
+
+        list
+    }
+
+    /// Perform a gnome sort on the list.
+    ///
+    /// The gnome steps forward while the two elements it's standing between are in order, and when
+    /// they're not, it swaps them and steps back. This is like insertion sort, but it moves each
+    /// element into place by swapping it, and then has to walk forward again.
+    pub fn gnome_sort(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        let mut i = 0;
+
+        while i < list.len() {
+            if i == 0 || list[i - 1] <= list[i] {
+                i += 1;
+            } else {
+                list.swap(i - 1, i);
+                i -= 1;
+            }
+        }
+
+        list
+    }
+
+    /// Perform an insertion sort on the list.
+    pub fn insertion_sort(&self) -> Vec<u32> {
+        self.insertion_sort_with_stats().0
+    }
+
+    /// Perform an insertion sort on the list, counting the comparisons.
+    pub fn insertion_sort_with_stats(&self) -> (Vec<u32>, SortStats) {
+        let mut list = self.list.clone();
+        let mut stats = SortStats::default();
+        algorithms::insertion_sort_by(&mut list, |&a, &b| stats.compare(a, b));
+        (list, stats)
+    }
+
+    /// Perform an adaptive insertion sort on the list.
+    ///
+    /// This finds the longest prefix of the list that's already sorted, and then inserts each
+    /// remaining element into it. To find where an element goes, we search backwards from the end
+    /// of the sorted part, doubling the step each time, and then binary search in the last step.
+    /// An element that only moves a short distance only needs a few comparisons, so this is close
+    /// to linear on nearly sorted lists.
+    pub fn adaptive_insertion_sort(&self) -> Vec<u32> {
+        self.adaptive_insertion_sort_with_stats().0
+    }
+
+    /// Perform an adaptive insertion sort on the list, counting the comparisons.
+    pub fn adaptive_insertion_sort_with_stats(&self) -> (Vec<u32>, SortStats) {
+        let mut list = self.list.clone();
+        let mut stats = SortStats::default();
+
+        let mut sorted_len = 1.min(list.len());
+        while sorted_len < list.len()
+            && stats.compare(list[sorted_len - 1], list[sorted_len]) != Ordering::Greater
+        {
+            sorted_len += 1;
+        }
+
+        for j in sorted_len..list.len() {
+            let next_item = list[j];
+
+            // Gallop backwards to find a range which contains the first element bigger than this
+            // one, which is where this one goes
+            let mut low = 0;
+            let mut high = j;
+            let mut step = 1;
+            while step <= j {
+                if stats.compare(list[j - step], next_item) != Ordering::Greater {
+                    low = j - step + 1;
+                    break;
+                }
+                high = j - step;
+                step *= 2;
+            }
+
+            let index = low
+                + list[low..high]
+                    .partition_point(|&item| stats.compare(item, next_item) != Ordering::Greater);
+            list.copy_within(index..j, index + 1);
+            list[index] = next_item;
+        }
+
+        (list, stats)
+    }
+
+    /// Perform a counting sort on the list.
+    ///
+    /// This counts how many times each value appears and then writes out each value that many
+    /// times, so it never compares elements. It takes O(n + k) time and O(k) memory, where `k` is
+    /// the range of values in the list, so it's only efficient when the range of values is
+    /// comparable to the length of the list, like with [`Self::new`].
+    ///
+    /// This method will panic if the range is too wide to count, so callers should check
+    /// [`Self::counting_sort_is_practical`] first, or use [`Self::try_counting_sort`] instead. The
+    /// binary passes the check to [`practical_sorts`] so that it never runs a counting sort that
+    /// would panic.
+    pub fn counting_sort(&self) -> Vec<u32> {
+        self.try_counting_sort()
+            .unwrap_or_else(|e| panic!("Unable to do a counting sort: {e}"))
+    }
+
+    /// Perform a counting sort like [`Self::counting_sort`], unless the range of values is much
+    /// wider than the list, in which case we'd need too much memory for the counts. See
+    /// [`Self::counting_sort_is_practical`].
+    pub fn try_counting_sort(&self) -> Result<Vec<u32>, CountingRangeError> {
+        let (Some(&min), Some(&max)) = (self.list.iter().min(), self.list.iter().max()) else {
+            return Ok(Vec::new());
+        };
+        if !self.counting_sort_is_practical() {
+            return Err(CountingRangeError {
+                min,
+                max,
+                length: self.list.len(),
+            });
+        }
+
+        // Offset everything by the minimum so that the counts only cover the range of values
+        let mut counts = vec![0usize; (max - min) as usize + 1];
+        for &item in &self.list {
+            counts[(item - min) as usize] += 1;
+        }
+
+        let mut list = Vec::with_capacity(self.list.len());
+        for (offset, &count) in counts.iter().enumerate() {
+            list.extend(std::iter::repeat_n(min + offset as u32, count));
+        }
+        Ok(list)
+    }
+
+    /// Check if the range of values in the list is narrow enough for a counting sort. We allow up
+    /// to [`MAX_COUNTS_PER_ITEM`] counts for each item, or [`MIN_COUNTS_ALLOWED`] in total, so
+    /// that short lists of small numbers are fine.
+    pub fn counting_sort_is_practical(&self) -> bool {
+        let (Some(&min), Some(&max)) = (self.list.iter().min(), self.list.iter().max()) else {
+            return true;
+        };
+        let range = (max - min) as usize + 1;
+
+        range <= MIN_COUNTS_ALLOWED || range / MAX_COUNTS_PER_ITEM <= self.list.len()
+    }
+
+    /// Perform a merge sort on the list.
+    pub fn merge_sort(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        algorithms::merge_sort(&mut list);
+        list
+    }
+
+    /// Perform a merge sort on the list, ordering the elements by the key which the given function
+    /// extracts from each one, like [`slice::sort_by_key`].
+    pub fn merge_sort_by_key<K: Ord>(&self, key: impl Fn(&u32) -> K) -> Vec<u32> {
+        let mut list = self.list.clone();
+        algorithms::merge_sort_by_key(&mut list, key);
+        list
+    }
+
+    /// Sort the list into descending order with a merge sort. This is stable, so equal elements
+    /// would keep their order, unlike reversing the result of a stable ascending sort.
+    pub fn sort_descending_stable(&self) -> Vec<u32> {
+        self.merge_sort_by_key(|&n| std::cmp::Reverse(n))
+    }
+
+    /// Perform a multi-threaded merge sort on the list.
+    ///
+    /// See [`Self::merge_sort`].
+    pub fn threaded_merge_sort(&self) -> Vec<u32> {
+        const THRESHOLD: usize = 100_000;
+
+        fn recursive_merge_sort(list: &mut [u32]) {
+            if list.len() < 2 {
+                return;
+            }
+
+            let mid = list.len() / 2;
+            let (left, right) = list.split_at_mut(mid);
+
+            // It's expensive to always spawn new threads, so only do it if the length is above a
+            // certain THRESHOLD.
+            if left.len() > THRESHOLD || right.len() > THRESHOLD {
+                use std::thread;
+
+                thread::scope(|s| {
+                    s.spawn(|| recursive_merge_sort(left));
+                    s.spawn(|| recursive_merge_sort(right));
+                });
+            } else {
+                recursive_merge_sort(left);
+                recursive_merge_sort(right);
+            }
+
+            // Now we create a vector to store the newly merged list and scan through each half,
+            // adding the smaller number each iteration
+            let mut left_index = 0;
+            let mut right_index = mid;
+            let mut vec = Vec::with_capacity(list.len());
+
+            while left_index < mid && right_index < list.len() {
+                if list[left_index] < list[right_index] {
+                    vec.push(list[left_index]);
+                    left_index += 1;
+                } else {
+                    vec.push(list[right_index]);
+                    right_index += 1;
+                }
+            }
+
+            // One of these slices will be empty, but the other will contain the unmerged, sorted
+            // elements
+            for elem in &list[left_index..mid] {
+                vec.push(*elem);
+            }
+            for elem in &list[right_index..] {
+                vec.push(*elem);
+            }
+
+            // Then we just put the elements back into the list slice
+            list.copy_from_slice(&vec[..list.len()]);
+        }
+
+        if self.list.len() <= 2 * THRESHOLD {
+            eprintln!(
+                "WARNING: threaded_merge_sort is only an advantage over merge_sort for \
+                lists with more than {} items",
+                (2 * THRESHOLD).to_formatted_string(&Locale::en)
+            );
+        }
+
+        let mut list = self.list.clone();
+        recursive_merge_sort(&mut list[..]);
+        list
+    }
+
+    /// Perform a quicksort on the list.
+    pub fn quick_sort(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        algorithms::quick_sort(&mut list);
+        list
+    }
+
+    /// Perform a quicksort on the list, ordering the elements by the key which the given function
+    /// extracts from each one, like [`slice::sort_by_key`].
+    pub fn quick_sort_by_key<K: Ord>(&self, key: impl Fn(&u32) -> K) -> Vec<u32> {
+        let mut list = self.list.clone();
+        algorithms::quick_sort_by_key(&mut list, key);
+        list
+    }
+
+    /// Perform a heap sort on the list.
+    pub fn heap_sort(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        algorithms::heap_sort(&mut list);
+        list
+    }
+
+    /// Perform a Stalin sort on the list.
+    ///
+    /// This works by removing all elements that aren't in order.
+    pub fn stalin_sort(&self) -> Vec<u32> {
+        let list = self.list.clone();
+        let mut highest: u32 = 0;
+        let mut new_list: Vec<u32> = Vec::new();
+
+        // Just take the elements that we want to keep
+        for item in list {
+            if item > highest {
+                highest = item;
+                new_list.push(item);
+            }
+        }
+        new_list
+    }
+
+    /// Sort the list with the standard library `Vec::sort` method.
+    pub fn std_sort(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        list.sort();
+        list
+    }
+
+    /// Sort the list with the standard library `Vec::sort_unstable` method.
+    pub fn std_sort_unstable(&self) -> Vec<u32> {
+        let mut list = self.list.clone();
+        list.sort_unstable();
+        list
+    }
+}
+
+/// Create a sorter from any iterator of values, like a range or a generator, without collecting
+/// into a `Vec` first.
+impl FromIterator<u32> for Sorter {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        Self {
+            list: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Check if the given list is sorted in ascending order.
+fn is_sorted(list: &[u32]) -> bool {
+    list.windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+/// The most counts per item in the list that [`Sorter::try_counting_sort`] will allow.
+pub const MAX_COUNTS_PER_ITEM: usize = 16;
+
+/// The number of counts that [`Sorter::try_counting_sort`] will always allow, however short the
+/// list is.
+pub const MIN_COUNTS_ALLOWED: usize = 1 << 16;
+
+/// An error from [`Sorter::try_counting_sort`] when the range of values is too wide to count.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CountingRangeError {
+    /// The smallest value in the list.
+    pub min: u32,
+
+    /// The largest value in the list.
+    pub max: u32,
+
+    /// The length of the list.
+    pub length: usize,
+}
+
+impl fmt::Display for CountingRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The values from {} to {} are too spread out to count for a list of {} items",
+            self.min, self.max, self.length
+        )
+    }
+}
+
+impl std::error::Error for CountingRangeError {}
+
+/// An error from parsing a list of integers with [`parse_int_list`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseListError {
+    /// The line number of the bad item, starting from 1.
+    pub line: usize,
+
+    /// The item that couldn't be parsed.
+    pub item: String,
+}
+
+impl fmt::Display for ParseListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Line {}: {:?} is not a non-negative integer",
+            self.line, self.item
+        )
+    }
+}
+
+impl std::error::Error for ParseListError {}
+
+/// Parse a list of integers separated by commas and/or whitespace, including newlines.
+pub fn parse_int_list(input: &str) -> Result<Vec<u32>, ParseListError> {
+    let mut list = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        for item in line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|item| !item.is_empty())
+        {
+            list.push(item.parse().map_err(|_| ParseListError {
+                line: idx + 1,
+                item: item.to_string(),
+            })?);
+        }
+    }
+
+    Ok(list)
+}
+
+pub type SorterMethod = fn(&Sorter) -> Vec<u32>;
+
+/// Sort with the given `Sorter` method, returning the sorted list and how long the sort took.
+pub fn sort_and_time(sorter: &Sorter, method: SorterMethod) -> (Vec<u32>, Duration) {
+    let start = time::Instant::now();
+    let list = sorter.sort_with(method);
+    let end = time::Instant::now();
+
+    (list, end.duration_since(start))
+}
+
+/// Time the given `Sorter` method with the given sorter, discarding the sorted list. See
+/// [`sort_and_time`].
+pub fn time_sort(sorter: &Sorter, method: SorterMethod) -> Duration {
+    sort_and_time(sorter, method).1
+}
+
+/// The result of timing a `Sorter` method and checking its output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct SortResult {
+    /// How long the sort took, in nanoseconds.
+    pub nanos: u128,
+
+    /// Whether the output was the whole list in ascending order.
+    pub sorted: bool,
+}
+
+/// Time the given `Sorter` method like [`time_sort`], and check that it actually sorted the list.
+///
+/// Only the sort itself is timed, not the check.
+pub fn time_sort_checked(sorter: &Sorter, method: SorterMethod) -> SortResult {
+    let (list, duration) = sort_and_time(sorter, method);
+
+    let mut expected = sorter.list.clone();
+    expected.sort_unstable();
+
+    SortResult {
+        nanos: duration.as_nanos(),
+        sorted: list == expected,
+    }
+}
+
+/// A check for whether a `Sorter` method can be used on the list in the given sorter, like
+/// [`Sorter::counting_sort_is_practical`].
+pub type PracticalityCheck = fn(&Sorter) -> bool;
+
+/// A check for sorts which can be used on any list.
+pub fn always_practical(_sorter: &Sorter) -> bool {
+    true
+}
+
+/// Split the sorts into the ones which can be used on the list in the given sorter, and the names
+/// of the ones which can't, according to the [`PracticalityCheck`] that goes with each sort.
+pub fn practical_sorts<'n>(
+    sorter: &Sorter,
+    sorts: &[(SorterMethod, &'n str, PracticalityCheck)],
+) -> (Vec<(SorterMethod, &'n str)>, Vec<&'n str>) {
+    let mut practical = Vec::new();
+    let mut skipped = Vec::new();
+
+    for &(method, name, is_practical) in sorts {
+        if is_practical(sorter) {
+            practical.push((method, name));
+        } else {
+            skipped.push(name);
+        }
+    }
+
+    (practical, skipped)
+}
+
+/// A sort whose output didn't match the reference sort in [`compare_sorts`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mismatch<'n> {
+    /// The name of the sort.
+    pub name: &'n str,
+
+    /// The first index where the output differed. If one output is a prefix of the other, then
+    /// this is the length of the shorter one.
+    pub index: usize,
+}
+
+/// Run every given sort on the same list and compare its output to `Sorter::std_sort`, returning
+/// the sorts which disagreed, in the order they were given.
+pub fn compare_sorts<'n>(sorter: &Sorter, sorts: &[(SorterMethod, &'n str)]) -> Vec<Mismatch<'n>> {
+    let expected = sorter.std_sort();
+
+    sorts
+        .iter()
+        .filter_map(|&(method, name)| {
+            let list = sorter.sort_with(method);
+            let index = expected
+                .iter()
+                .zip(&list)
+                .position(|(a, b)| a != b)
+                .or_else(|| {
+                    (list.len() != expected.len()).then(|| list.len().min(expected.len()))
+                })?;
+
+            Some(Mismatch { name, index })
+        })
+        .collect()
+}
+
+/// Make a table of the given sort times, sorted from fastest to slowest, with how many times
+/// slower each sort was than the fastest one, and a total at the bottom.
+pub fn summary_table(times: &[(&str, Duration)]) -> String {
+    let times: Vec<(&str, Duration)> = {
+        let mut times = times.to_vec();
+        times.sort_by_key(|&(name, time)| (time, name));
+        times
+    };
+    let fastest = times.first().map_or(1, |(_, time)| time.as_nanos().max(1));
+
+    let mut builder = Builder::default();
+    builder.set_columns(["Sort", "Time", "Relative"]);
+    for (name, time) in &times {
+        builder.add_record([
+            name.to_string(),
+            format!("{time:?}"),
+            format!("{:.2}x", time.as_nanos() as f64 / fastest as f64),
+        ]);
+    }
+    let total: Duration = times.iter().map(|&(_, time)| time).sum();
+    builder.add_record(["total".to_string(), format!("{total:?}"), String::new()]);
+
+    builder.build().with(Style::modern()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{collection::vec, prelude::*};
+
+    /// Test the given `Sorter` method with the given list length.
+    macro_rules! test_sorter_method {
+        ( $meth:ident, $x:literal ) => {{
+            assert!(is_sorted(&Sorter::new($x).$meth()));
+        }};
+    }
+
+    /// Run the given body `x` number of times to ensure good tests.
+    macro_rules! test_multiple {
+        ( $x:literal, $body:expr ) => {{
+            for _ in 0..$x {
+                $body;
+            }
+        }};
+    }
+
+    #[test]
+    fn sorter_new() {
+        assert_eq!(Sorter::new(10).list.len(), 10);
+        assert_eq!(Sorter::new(100).list.len(), 100);
+        assert_eq!(Sorter::new(1000).list.len(), 1000);
+        assert_eq!(Sorter::new(10_000).list.len(), 10_000);
+        assert_eq!(Sorter::new(100_000).list.len(), 100_000);
+    }
+
+    #[test]
+    fn sorter_with_distribution() {
+        for distribution in Distribution::ALL {
+            let list = Sorter::with_distribution(1000, distribution).list;
+            assert_eq!(list.len(), 1000, "{distribution:?}");
+
+            let mut sorted = list.clone();
+            sorted.sort();
+
+            match distribution {
+                Distribution::Random => assert_eq!(sorted, (0..1000).collect::<Vec<_>>()),
+                Distribution::NearlySorted => {
+                    assert_eq!(sorted, (0..1000).collect::<Vec<_>>());
+                    let out_of_place = list.iter().enumerate().filter(|&(i, &n)| i as u32 != n);
+                    assert!(out_of_place.count() <= 20);
+                }
+                Distribution::Reversed => {
+                    assert_eq!(list, (0..1000).rev().collect::<Vec<_>>());
+                }
+                Distribution::ManyDuplicates => {
+                    sorted.dedup();
+                    assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_iter() {
+        let sorter = Sorter::from_iter((0..100).rev());
+        assert_eq!(sorter.len(), 100);
+        assert_eq!(sorter.merge_sort(), (0..100).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn sort_with() {
+        let sorter = Sorter::from_vec(vec![3, 1, 2, 1]);
+        assert_eq!(sorter.sort_with(Sorter::merge_sort), vec![1, 1, 2, 3]);
+        assert_eq!(sorter.sort_with(Sorter::stalin_sort), vec![3]);
+    }
+
+    #[test]
+    fn sort_and_time_test() {
+        let sorter = Sorter::new(1000);
+        let (list, _) = sort_and_time(&sorter, Sorter::merge_sort);
+        assert_eq!(list.len(), 1000);
+        assert!(list.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn time_sort_checked_test() {
+        let sorter = Sorter::new(1000);
+        assert!(time_sort_checked(&sorter, Sorter::merge_sort).sorted);
+        assert!(!time_sort_checked(&sorter, Sorter::stalin_sort).sorted);
+    }
+
+    #[test]
+    fn sort_result_json() {
+        let results = std::collections::BTreeMap::from([
+            (
+                "merge_sort",
+                SortResult {
+                    nanos: 1500,
+                    sorted: true,
+                },
+            ),
+            (
+                "stalin_sort",
+                SortResult {
+                    nanos: 20,
+                    sorted: false,
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            serde_json::to_string(&results).unwrap(),
+            r#"{"merge_sort":{"nanos":1500,"sorted":true},"stalin_sort":{"nanos":20,"sorted":false}}"#
+        );
+    }
+
+    #[test]
+    fn new_seeded() {
+        assert_eq!(
+            Sorter::new_seeded(100, 42).list,
+            Sorter::new_seeded(100, 42).list
+        );
+        assert_eq!(
+            Sorter::new_seeded(100, 42).std_sort(),
+            (0..100).collect::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn compare_sorts_test() {
+        let sorts: [(SorterMethod, &str); 3] = [
+            (Sorter::merge_sort, "merge_sort"),
+            (Sorter::stalin_sort, "stalin_sort"),
+            (
+                |sorter| sorter.std_sort().into_iter().rev().collect(),
+                "reversed",
+            ),
+        ];
+
+        assert_eq!(
+            compare_sorts(&Sorter::from_vec(vec![1, 3, 2, 4]), &sorts),
+            vec![
+                Mismatch {
+                    name: "stalin_sort",
+                    index: 1
+                },
+                Mismatch {
+                    name: "reversed",
+                    index: 0
+                },
+            ]
+        );
+        assert_eq!(
+            compare_sorts(&Sorter::from_vec(vec![1, 2, 3]), &sorts[..2]),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn summary_table_test() {
+        let table = summary_table(&[
+            ("merge_sort", Duration::from_micros(30)),
+            ("bubble_sort", Duration::from_millis(3)),
+            ("std_sort", Duration::from_micros(20)),
+        ]);
+        let rows: Vec<Vec<&str>> = table
+            .lines()
+            .filter(|line| line.starts_with('│'))
+            .map(|line| {
+                line.split('│')
+                    .map(str::trim)
+                    .filter(|cell| !cell.is_empty())
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec!["Sort", "Time", "Relative"],
+                vec!["std_sort", "20µs", "1.00x"],
+                vec!["merge_sort", "30µs", "1.50x"],
+                vec!["bubble_sort", "3ms", "150.00x"],
+                vec!["total", "3.05ms"],
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_int_list_test() {
+        assert_eq!(
+            parse_int_list("3, 1,2\n  5\t4,,\r\n\n 10 ,9").unwrap(),
+            vec![3, 1, 2, 5, 4, 10, 9]
+        );
+        assert_eq!(parse_int_list("").unwrap(), Vec::<u32>::new());
+        assert_eq!(
+            parse_int_list("1, 2\n3, x4, 5"),
+            Err(ParseListError {
+                line: 2,
+                item: "x4".to_string()
+            })
+        );
+        assert_eq!(
+            parse_int_list("1\n\n-2").unwrap_err().to_string(),
+            "Line 3: \"-2\" is not a non-negative integer"
+        );
+    }
+
+    #[test]
+    fn bogo_sort() {
+        test_multiple!(100, test_sorter_method!(bogo_sort, 5));
+    }
+
+    #[test]
+    fn bubble_sort() {
+        test_multiple!(10, test_sorter_method!(bubble_sort, 1000));
+    }
+
+    #[test]
+    fn comb_sort() {
+        test_multiple!(100, test_sorter_method!(comb_sort, 1000));
+    }
+
+    #[test]
+    fn gnome_sort() {
+        test_multiple!(10, test_sorter_method!(gnome_sort, 1000));
+    }
+
+    #[test]
+    fn insertion_sort() {
+        test_multiple!(100, test_sorter_method!(insertion_sort, 1000));
+    }
+
+    #[test]
+    fn adaptive_insertion_sort() {
+        test_multiple!(100, test_sorter_method!(adaptive_insertion_sort, 1000));
+
+        for distribution in Distribution::ALL {
+            let sorter = Sorter::with_distribution(1000, distribution);
+            assert_eq!(
+                sorter.adaptive_insertion_sort(),
+                sorter.std_sort(),
+                "{distribution:?}"
+            );
+        }
+        for list in [vec![], vec![1], vec![2, 1], vec![1, 1, 0, 3, 2, 2]] {
+            let sorter = Sorter::from_vec(list);
+            assert_eq!(sorter.adaptive_insertion_sort(), sorter.std_sort());
+        }
+    }
+
+    #[test]
+    fn adaptive_insertion_sort_comparisons() {
+        let sorter = Sorter::from_vec((0..1000).collect());
+        let (_, stats) = sorter.adaptive_insertion_sort_with_stats();
+        assert_eq!(stats.comparisons, 999);
+
+        test_multiple!(10, {
+            let sorter = Sorter::with_distribution(10_000, Distribution::NearlySorted);
+            let (_, plain) = sorter.insertion_sort_with_stats();
+            let (_, adaptive) = sorter.adaptive_insertion_sort_with_stats();
+            assert!(
+                adaptive.comparisons < plain.comparisons,
+                "{adaptive:?} should have fewer comparisons than {plain:?}"
+            );
+        });
+    }
+
+    #[test]
+    fn counting_sort() {
+        test_multiple!(100, test_sorter_method!(counting_sort, 10_000));
+
+        for distribution in Distribution::ALL {
+            let sorter = Sorter::with_distribution(1000, distribution);
+            assert_eq!(
+                sorter.counting_sort(),
+                sorter.std_sort(),
+                "{distribution:?}"
+            );
+        }
+        for list in [vec![], vec![7], vec![2, 1], vec![5, 0, 5, 3, 3, 100]] {
+            let sorter = Sorter::from_vec(list);
+            assert_eq!(sorter.counting_sort(), sorter.std_sort());
+        }
+    }
+
+    #[test]
+    fn counting_sort_wide_range() {
+        // The counts are offset by the minimum, so big values close together are fine
+        let sorter = Sorter::from_vec(vec![u32::MAX, u32::MAX - 2, u32::MAX - 1, u32::MAX]);
+        assert!(sorter.counting_sort_is_practical());
+        assert_eq!(sorter.counting_sort(), sorter.std_sort());
+
+        // This used to try to allocate 4 billion counts and abort
+        let sorter = Sorter::from_vec(vec![u32::MAX, 3, 1]);
+        assert!(!sorter.counting_sort_is_practical());
+        assert_eq!(
+            sorter.try_counting_sort(),
+            Err(CountingRangeError {
+                min: 1,
+                max: u32::MAX,
+                length: 3
+            })
+        );
+
+        let sorts: [(SorterMethod, &str, PracticalityCheck); 2] = [
+            (Sorter::merge_sort, "merge_sort", always_practical),
+            (
+                Sorter::counting_sort,
+                "counting_sort",
+                Sorter::counting_sort_is_practical,
+            ),
+        ];
+        let (practical, skipped) = practical_sorts(&sorter, &sorts);
+        let names: Vec<&str> = practical.into_iter().map(|(_, name)| name).collect();
+        assert_eq!(names, vec!["merge_sort"]);
+        assert_eq!(skipped, vec!["counting_sort"]);
+    }
+
+    #[test]
+    fn merge_sort() {
+        test_multiple!(100, test_sorter_method!(merge_sort, 10_000));
+    }
+
+    #[test]
+    fn quick_sort() {
+        test_multiple!(100, test_sorter_method!(quick_sort, 10_000));
+    }
+
+    #[test]
+    fn sort_by_key() {
+        let sorter = Sorter::from_vec(vec![13, 21, 5, 32, 40]);
+        let expected = vec![40, 21, 32, 13, 5];
+
+        assert_eq!(sorter.merge_sort_by_key(|&n| n % 10), expected);
+        assert_eq!(sorter.quick_sort_by_key(|&n| n % 10), expected);
+        assert_eq!(
+            sorter.merge_sort_by_key(|&n| std::cmp::Reverse(n)),
+            vec![40, 32, 21, 13, 5]
+        );
+        assert_eq!(sorter.sort_descending_stable(), vec![40, 32, 21, 13, 5]);
+    }
+
+    #[test]
+    fn heap_sort() {
+        test_multiple!(100, test_sorter_method!(heap_sort, 10_000));
+    }
+
+    #[test]
+    fn stalin_sort() {
+        test_multiple!(100, test_sorter_method!(stalin_sort, 10_000));
+    }
+
+    #[test]
+    fn std_sort() {
+        test_multiple!(100, test_sorter_method!(std_sort, 10_000));
+    }
+
+    #[test]
+    fn std_sort_unstable() {
+        test_multiple!(100, test_sorter_method!(std_sort_unstable, 10_000));
+    }
+
+    /// Generate lists which are likely to find edge cases: any values, lots of duplicates, or
+    /// every element the same. Each kind can be empty or have a single element.
+    fn arbitrary_list() -> impl Strategy<Value = Vec<u32>> {
+        prop_oneof![
+            vec(any::<u32>(), 0..500),
+            vec(0..10u32, 0..500),
+            (any::<u32>(), 0..500usize).prop_map(|(n, len)| vec![n; len]),
+        ]
+    }
+
+    /// Sort the list with the standard library, to compare other sorts against.
+    fn sorted(mut list: Vec<u32>) -> Vec<u32> {
+        list.sort_unstable();
+        list
+    }
+
+    proptest! {
+        // The quadratic sorts are slow in debug builds, so we don't run as many cases
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn sorts_give_sorted_permutation(list in arbitrary_list()) {
+            let sorter = Sorter::from_vec(list.clone());
+            let expected = sorted(list);
+
+            for (method, name) in [
+                (Sorter::bubble_sort as SorterMethod, "bubble_sort"),
+                (Sorter::comb_sort, "comb_sort"),
+                (Sorter::gnome_sort, "gnome_sort"),
+                (Sorter::insertion_sort, "insertion_sort"),
+                (Sorter::adaptive_insertion_sort, "adaptive_insertion_sort"),
+                (Sorter::merge_sort, "merge_sort"),
+                (Sorter::threaded_merge_sort, "threaded_merge_sort"),
+                (Sorter::quick_sort, "quick_sort"),
+                (Sorter::heap_sort, "heap_sort"),
+                (Sorter::std_sort, "std_sort"),
+                (Sorter::std_sort_unstable, "std_sort_unstable"),
+            ] {
+                prop_assert_eq!(sorter.sort_with(method), expected.clone(), "{}", name);
+            }
+        }
+
+        // Counting sort needs memory for every value up to the biggest one, so the values have to
+        // be small
+        #[test]
+        fn counting_sort_gives_sorted_permutation(list in vec(0..10_000u32, 0..500)) {
+            prop_assert_eq!(Sorter::from_vec(list.clone()).counting_sort(), sorted(list));
+        }
+
+        // Bogo sort is only fast enough for tiny lists
+        #[test]
+        fn bogo_sort_gives_sorted_permutation(list in vec(any::<u32>(), 0..5)) {
+            prop_assert_eq!(Sorter::from_vec(list.clone()).bogo_sort(), sorted(list));
+        }
+
+        // Stalin sort deliberately loses elements, including zeros and repeated values, but what's
+        // left should be sorted and in the same order as the input
+        #[test]
+        fn stalin_sort_gives_sorted_subsequence(list in arbitrary_list()) {
+            let output = Sorter::from_vec(list.clone()).stalin_sort();
+
+            prop_assert!(is_sorted(&output), "{:?}", output);
+            let mut input = list.iter();
+            prop_assert!(output.iter().all(|item| input.any(|x| x == item)));
+        }
+    }
+}
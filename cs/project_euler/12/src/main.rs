@@ -1,28 +1,51 @@
+/// Count the divisors of `n` directly, to check the faster counts against.
+#[cfg(test)]
 fn count_divisors(n: usize) -> usize {
-    if n == 1 {
-        1
+    numtheory::divisors(n as u64).len()
+}
+
+/// Count the divisors of `n` from its prime factorisation, which is the product of one more than
+/// each exponent.
+fn count_divisors_from_factors(n: usize) -> usize {
+    numtheory::prime_factors(n as u64)
+        .into_iter()
+        .map(|(_, exponent)| exponent as usize + 1)
+        .product()
+}
+
+/// Count the divisors of the `n`th triangle number without computing it.
+///
+/// `T_n = n(n + 1) / 2`, and `n` and `n + 1` are coprime, so exactly one of them is even. Halving
+/// that one gives two coprime factors of `T_n`, and the divisor count is multiplicative, so we can
+/// factor the two smaller numbers instead of the big triangle number.
+fn count_triangle_divisors(n: usize) -> usize {
+    let (a, b) = if n.is_multiple_of(2) {
+        (n / 2, n + 1)
     } else {
-        divisors::get_divisors(n).len() + 2
-    }
+        (n, n.div_ceil(2))
+    };
+
+    count_divisors_from_factors(a) * count_divisors_from_factors(b)
 }
 
-fn get_nth_triangle_number(n: usize) -> usize {
-    (n * (n + 1)) / 2
+/// Get the `n`th triangle number, or `None` if it would overflow.
+fn get_nth_triangle_number(n: usize) -> Option<usize> {
+    // Halve whichever of n and n + 1 is even first, so that only the result can overflow
+    let next = n.checked_add(1)?;
+    if n.is_multiple_of(2) {
+        (n / 2).checked_mul(next)
+    } else {
+        n.checked_mul(next / 2)
+    }
 }
 
 fn main() {
+    let n = (1usize..)
+        .find(|&n| count_triangle_divisors(n) > 500)
+        .unwrap();
     println!(
         "{}",
-        (1usize..)
-            .find_map(|n| {
-                let triangle = get_nth_triangle_number(n);
-                if count_divisors(triangle) > 500 {
-                    Some(triangle)
-                } else {
-                    None
-                }
-            })
-            .unwrap()
+        get_nth_triangle_number(n).expect("The triangle number should fit in a usize")
     );
 }
 
@@ -39,10 +62,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn count_triangle_divisors_test() {
+        for n in 1..5000 {
+            let triangle = get_nth_triangle_number(n).unwrap();
+            assert_eq!(
+                count_triangle_divisors(n),
+                count_divisors(triangle),
+                "n = {n}"
+            );
+        }
+    }
+
     #[test]
     fn get_nth_triangle_number_test() {
         for i in 1..1000 {
-            assert_eq!(get_nth_triangle_number(i), (1..=i).sum::<usize>());
+            assert_eq!(get_nth_triangle_number(i), Some((1..=i).sum::<usize>()));
         }
+
+        assert_eq!(get_nth_triangle_number(usize::MAX), None);
+        assert_eq!(get_nth_triangle_number(1 << (usize::BITS / 2 + 1)), None);
     }
 }
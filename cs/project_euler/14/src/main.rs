@@ -1,21 +1,40 @@
-fn get_chain_length(value: u64) -> u64 {
+/// Get the length of the Collatz chain starting at `value`, or `None` if any value in the chain
+/// is too big for a `u64`.
+fn get_chain_length(value: u64) -> Option<u64> {
     if value == 1 {
-        return 1;
+        return Some(1);
     }
 
-    1 + get_chain_length(match value % 2 {
+    let next = match value % 2 {
         0 => value / 2,
-        _ => 3 * value + 1,
-    })
+        _ => value.checked_mul(3)?.checked_add(1)?,
+    };
+    get_chain_length(next).map(|length| length + 1)
 }
 
 fn main() {
     println!(
         "{:?}",
         (1..1000000)
-            .into_iter()
-            .map(|v| (v, get_chain_length(v)))
+            .filter_map(|v| get_chain_length(v).map(|length| (v, length)))
             .max_by(|x, y| x.1.cmp(&y.1))
             .unwrap()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_chain_length_test() {
+        assert_eq!(get_chain_length(1), Some(1));
+        assert_eq!(get_chain_length(13), Some(10));
+        assert_eq!(get_chain_length(27), Some(112));
+        assert_eq!(get_chain_length(837799), Some(525));
+
+        // 3n + 1 overflows straight away for these
+        assert_eq!(get_chain_length(u64::MAX), None);
+        assert_eq!(get_chain_length(u64::MAX / 3 + 2), None);
+    }
+}
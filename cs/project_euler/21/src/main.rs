@@ -1,5 +1,9 @@
 fn get_proper_divisors(n: u16) -> Vec<u16> {
-    (1..=(n / 2)).filter(|x| n % x == 0).collect()
+    numtheory::divisors(n.into())
+        .into_iter()
+        .filter(|&d| d != u64::from(n))
+        .map(|d| u16::try_from(d).expect("Divisors of a u16 should fit in a u16"))
+        .collect()
 }
 
 fn is_amicable(n: u16) -> bool {
@@ -0,0 +1,194 @@
+//! Number theory utilities shared between the Project Euler problems.
+
+/// The bases to use for Miller-Rabin. Testing against all of these is enough to make it
+/// deterministic for every `u64`.
+const MILLER_RABIN_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Compute `(a * b) % m` without overflowing.
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+/// Compute `base.pow(exp) % m` by repeated squaring, without overflowing.
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    base %= m;
+
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp /= 2;
+    }
+
+    result
+}
+
+/// Check if `n` is prime with the deterministic Miller-Rabin test.
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+
+    for &p in &MILLER_RABIN_BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // Write n - 1 as d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    MILLER_RABIN_BASES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+
+        false
+    })
+}
+
+/// Get the prime factorisation of `n` as pairs of `(prime, exponent)`, sorted by the primes.
+/// Both 0 and 1 have no prime factors.
+pub fn prime_factors(mut n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    if n == 0 {
+        return factors;
+    }
+
+    // Trial division is slow for big primes, so we stop as soon as what's left is prime
+    let mut p = 2;
+    let mut rest_is_prime = is_prime(n);
+    while !rest_is_prime && p <= n / p {
+        let mut exponent = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((p, exponent));
+            rest_is_prime = is_prime(n);
+        }
+
+        p += if p == 2 { 1 } else { 2 };
+    }
+
+    // Whatever's left has no factors below its square root, so it's prime
+    if n > 1 {
+        factors.push((n, 1));
+    }
+
+    factors
+}
+
+/// Get every divisor of `n`, including 1 and `n` itself, in ascending order. 0 has no divisors
+/// here, since every number divides it.
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut divisors = vec![1];
+    for (p, exponent) in prime_factors(n) {
+        let current = divisors.clone();
+        let mut power = 1;
+        for _ in 0..exponent {
+            power *= p;
+            divisors.extend(current.iter().map(|d| d * power));
+        }
+    }
+
+    divisors.sort_unstable();
+    divisors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_prime_test() {
+        let small_primes: Vec<u64> = (0..100).filter(|&n| is_prime(n)).collect();
+        assert_eq!(
+            small_primes,
+            vec![
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97
+            ]
+        );
+
+        // The 10,001st prime, from problem 7
+        assert!(is_prime(104_743));
+
+        // Strong pseudoprimes to lots of bases
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(3_825_123_056_546_413_051));
+
+        // Big primes near the limits
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(4_294_967_291));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn prime_factors_test() {
+        assert_eq!(prime_factors(0), vec![]);
+        assert_eq!(prime_factors(1), vec![]);
+        assert_eq!(prime_factors(2), vec![(2, 1)]);
+        assert_eq!(prime_factors(28), vec![(2, 2), (7, 1)]);
+        assert_eq!(prime_factors(360), vec![(2, 3), (3, 2), (5, 1)]);
+
+        // From problem 3
+        assert_eq!(
+            prime_factors(600_851_475_143),
+            vec![(71, 1), (839, 1), (1471, 1), (6857, 1)]
+        );
+
+        assert_eq!(prime_factors(1_000_000_007), vec![(1_000_000_007, 1)]);
+        assert_eq!(prime_factors(1 << 63), vec![(2, 63)]);
+        assert_eq!(
+            prime_factors(2 * 65_537 * 4_294_967_291),
+            vec![(2, 1), (65_537, 1), (4_294_967_291, 1)]
+        );
+        assert_eq!(
+            prime_factors(u64::MAX),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (6_700_417, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn divisors_test() {
+        assert_eq!(divisors(0), vec![]);
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(13), vec![1, 13]);
+        assert_eq!(divisors(28), vec![1, 2, 4, 7, 14, 28]);
+        assert_eq!(
+            divisors(220),
+            vec![1, 2, 4, 5, 10, 11, 20, 22, 44, 55, 110, 220]
+        );
+
+        for n in 1..500 {
+            let expected: Vec<u64> = (1..=n).filter(|d| n % d == 0).collect();
+            assert_eq!(divisors(n), expected, "n = {n}");
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use simplex::{
+    lin_prog::{comparison::Comparison, system::LinProgSystem, ObjectiveSense},
+    simplex::solve_with_simplex_tableaux,
+    Frac,
+};
+
+/// Generate a random system with the given number of constraints and the same number of variables.
+///
+/// Every constraint is a `≤` with positive coefficients and a positive constant, so the origin is
+/// always feasible and every variable is bounded above, which means every system has an optimal
+/// solution. The generator is seeded, so the systems are the same in every run.
+fn random_system(constraints: usize) -> LinProgSystem {
+    let mut rng = StdRng::seed_from_u64(constraints as u64);
+    let mut random_fracs = |len: usize, max: u64| {
+        (0..len)
+            .map(|_| Frac::from(rng.gen_range(1..=max)))
+            .collect()
+    };
+
+    let c: Vec<Frac> = random_fracs(constraints, 9);
+    let a: Vec<Vec<Frac>> = (0..constraints)
+        .map(|_| random_fracs(constraints, 9))
+        .collect();
+    let b: Vec<Frac> = random_fracs(constraints, 100);
+
+    LinProgSystem::from_matrix(
+        &c,
+        &a,
+        &b,
+        &vec![Comparison::LessThanOrEqual; constraints],
+        ObjectiveSense::Maximise,
+    )
+    .expect("The generated matrix should have consistent dimensions")
+}
+
+/// Solve random systems of increasing size, to catch performance regressions in the exact
+/// fraction arithmetic.
+fn benchmark_solver(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve_with_simplex_tableaux");
+
+    for constraints in [3, 6, 10, 15] {
+        let system = random_system(constraints);
+        solve_with_simplex_tableaux(&system)
+            .expect("Every generated system should have an optimal solution");
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(constraints),
+            &system,
+            |b, system| b.iter(|| solve_with_simplex_tableaux(system)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_solver);
+criterion_main!(benches);
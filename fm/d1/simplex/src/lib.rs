@@ -0,0 +1,14 @@
+//! This crate exists to help solve linear programming problems, and it's mainly used through its
+//! CLI app. The library is exposed so that the solver can be benchmarked.
+//!
+//! Throughout the crate, `LinProg` is used as an abbreviation for "linear programming".
+
+#![cfg_attr(debug_assertions, allow(unused_variables, dead_code))]
+
+pub mod lin_prog;
+pub mod simplex;
+
+use fraction::GenericFraction;
+
+/// The exact fraction type used for every number in the crate.
+pub type Frac = GenericFraction<u64>;
@@ -9,7 +9,7 @@ pub enum Comparison {
     /// Less than (<).
     LessThan,
 
-    /// Less than or equal (≤ or <=).
+    /// Less than or equal (≤, ≦, ⩽, or <=).
     LessThanOrEqual,
 
     /// Equal (=).
@@ -18,7 +18,7 @@ pub enum Comparison {
     /// Greater than (>).
     GreaterThan,
 
-    /// Greater than or equal (≥ or >=).
+    /// Greater than or equal (≥, ≧, ⩾, or >=).
     GreaterThanOrEqual,
 }
 
@@ -40,11 +40,14 @@ impl fmt::Display for Comparison {
 
 impl Comparison {
     /// Parse a comparison operator with nom.
+    ///
+    /// The Unicode variants `≦`, `≧`, `⩽`, and `⩾` are accepted as `≤` and `≥`, since some fonts
+    /// and locales produce them.
     pub fn nom_parse(input: &str) -> IResult<&str, Self> {
         alt((
             tag("=").map(|_| Self::Equal),
-            tag("≤").map(|_| Self::LessThanOrEqual),
-            tag("≥").map(|_| Self::GreaterThanOrEqual),
+            alt((tag("≤"), tag("≦"), tag("⩽"))).map(|_| Self::LessThanOrEqual),
+            alt((tag("≥"), tag("≧"), tag("⩾"))).map(|_| Self::GreaterThanOrEqual),
             tag("<=").map(|_| Self::LessThanOrEqual),
             tag("<").map(|_| Self::LessThan),
             tag(">=").map(|_| Self::GreaterThanOrEqual),
@@ -52,6 +55,30 @@ impl Comparison {
         ))(input)
     }
 
+    /// The comparison operator written with just ASCII characters, like `<=` instead of `≤`, for
+    /// terminals which don't render Unicode well. [`Display`](fmt::Display) uses Unicode.
+    pub fn to_ascii(self) -> &'static str {
+        match self {
+            Self::LessThan => "<",
+            Self::LessThanOrEqual => "<=",
+            Self::Equal => "=",
+            Self::GreaterThan => ">",
+            Self::GreaterThanOrEqual => ">=",
+        }
+    }
+
+    /// Flip the direction of the comparison operator, as needed when multiplying both sides of an
+    /// inequality by a negative number. Equality stays the same.
+    pub fn flip(self) -> Self {
+        match self {
+            Self::LessThan => Self::GreaterThan,
+            Self::LessThanOrEqual => Self::GreaterThanOrEqual,
+            Self::Equal => Self::Equal,
+            Self::GreaterThan => Self::LessThan,
+            Self::GreaterThanOrEqual => Self::LessThanOrEqual,
+        }
+    }
+
     /// Compare the given values with the comparison operator.
     pub fn compare<T: PartialOrd>(&self, lhs: &T, rhs: &T) -> bool {
         match self {
@@ -92,4 +119,57 @@ mod tests {
             Ok(("< 10", Comparison::Equal))
         );
     }
+
+    #[test]
+    fn comparison_flip_test() {
+        assert_eq!(Comparison::LessThan.flip(), Comparison::GreaterThan);
+        assert_eq!(
+            Comparison::LessThanOrEqual.flip(),
+            Comparison::GreaterThanOrEqual
+        );
+        assert_eq!(Comparison::Equal.flip(), Comparison::Equal);
+        assert_eq!(Comparison::GreaterThan.flip(), Comparison::LessThan);
+        assert_eq!(
+            Comparison::GreaterThanOrEqual.flip(),
+            Comparison::LessThanOrEqual
+        );
+    }
+
+    #[test]
+    fn comparison_to_ascii_test() {
+        for (comparison, unicode, ascii) in [
+            (Comparison::LessThan, "<", "<"),
+            (Comparison::LessThanOrEqual, "≤", "<="),
+            (Comparison::Equal, "=", "="),
+            (Comparison::GreaterThan, ">", ">"),
+            (Comparison::GreaterThanOrEqual, "≥", ">="),
+        ] {
+            assert_eq!(comparison.to_string(), unicode);
+            assert_eq!(comparison.to_ascii(), ascii);
+
+            // Both spellings parse back to the same comparison
+            assert_eq!(Comparison::nom_parse(unicode), Ok(("", comparison)));
+            assert_eq!(Comparison::nom_parse(ascii), Ok(("", comparison)));
+        }
+    }
+
+    #[test]
+    fn comparison_parse_unicode_variants_test() {
+        assert_eq!(
+            Comparison::nom_parse("≦ 5"),
+            Ok((" 5", Comparison::LessThanOrEqual))
+        );
+        assert_eq!(
+            Comparison::nom_parse("⩽ 5"),
+            Ok((" 5", Comparison::LessThanOrEqual))
+        );
+        assert_eq!(
+            Comparison::nom_parse("≧ 5"),
+            Ok((" 5", Comparison::GreaterThanOrEqual))
+        );
+        assert_eq!(
+            Comparison::nom_parse("⩾ 5"),
+            Ok((" 5", Comparison::GreaterThanOrEqual))
+        );
+    }
 }
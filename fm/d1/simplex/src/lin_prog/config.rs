@@ -1,39 +1,472 @@
 //! This module handles config for the [`LinProgSystem`].
 
-use color_eyre::Result;
+use super::Variables;
+use color_eyre::{Report, Result};
 use inquire::MultiSelect;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, num::ParseIntError, str::FromStr};
 use tracing::instrument;
 
+/// The rule used to choose the pivot column in each iteration of the simplex algorithm.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PivotRule {
+    /// Choose the most negative number in the objective row, breaking ties with the
+    /// [`ColumnTieBreak`], which chooses the first such column by default.
+    #[default]
+    MostNegative,
+
+    /// Choose the most negative number in the objective row, breaking ties by preferring original
+    /// variables over slack, surplus, and artificial variables, using the ordering of
+    /// [`VariableType`](crate::simplex::VariableType).
+    PreferOriginal,
+
+    /// Choose the most negative number in the objective row, like [`Self::MostNegative`], but
+    /// break ties in the ratio test lexicographically, which is equivalent to perturbing the
+    /// constants by symbolic epsilons. This prevents the algorithm from cycling on degenerate
+    /// problems.
+    Lexicographic,
+
+    /// Choose a random column with a negative number in the objective row. The choice is seeded
+    /// by [`Config::rng_seed`], so the same system and seed always pivot the same way.
+    Random,
+}
+
+/// Which column to choose when several columns have the same most negative number in the
+/// objective row, and the [`PivotRule`] doesn't prefer any of them.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColumnTieBreak {
+    /// Choose the tied column furthest to the left.
+    #[default]
+    LowestIndex,
+
+    /// Choose the tied column furthest to the right, like some textbooks do.
+    HighestIndex,
+}
+
+/// The method used to find an initial feasible solution when the system has `≥` constraints,
+/// which need artificial variables.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SimplexMethod {
+    /// Minimise the sum of the artificial variables in a first stage, and then optimise the actual
+    /// objective function in a second stage.
+    #[default]
+    TwoStage,
+
+    /// Give every artificial variable a huge penalty of `M` in the objective function, so that
+    /// optimising it forces them to 0. Since `M` is symbolic, the objective row has numbers of the
+    /// form `aM + b`.
+    BigM,
+}
+
+/// The syntax allowed for numbers when parsing expressions and constraints.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum NumberSyntax {
+    /// Plain decimals like `2.34`, and fractions like `1/3` for constants.
+    #[default]
+    Plain,
+
+    /// Plain numbers, and also scientific notation like `2.34e12` or `1.5e-3`. This is off by
+    /// default, since `2e-3` would otherwise be read as the term `2e` minus 3.
+    Scientific,
+}
+
+/// How to name the slack, surplus, and artificial variables when displaying tableaux and
+/// solutions, so that the output can match the conventions of a textbook. Original variables
+/// always keep their own names.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum VariableNaming {
+    /// Names like `sl#0`, `su#0`, and `ar#0`, which can't clash with the original variables.
+    #[default]
+    Symbolic,
+
+    /// Names like `s1`, `e1`, and `a1`, counting from 1. Surplus variables are called excess
+    /// variables here, so that they don't clash with the slack variables.
+    Numbered,
+
+    /// Names like `s₁`, `e₁`, and `a₁`, like [`Self::Numbered`] but with subscripts.
+    Subscripted,
+}
+
+/// Which variables in the system must have integer values in the solution.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum IntegerMode {
+    /// Every variable can have a fractional value.
+    #[default]
+    None,
+
+    /// Every variable must have an integer value.
+    All,
+
+    /// Only the named variables must have integer values. The rest can be fractional.
+    Some(HashSet<String>),
+}
+
+impl IntegerMode {
+    /// Does any variable need to have an integer value?
+    pub fn requires_integers(&self) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Some(vars) => !vars.is_empty(),
+        }
+    }
+
+    /// Does the given variable need to have an integer value?
+    pub fn is_integer(&self, var: &str) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Some(vars) => vars.contains(var),
+        }
+    }
+}
+
 /// A simple config struct to handle options for the [`LinProgSystem`].
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Config {
-    /// Does this system require integer solutions?
-    pub integer_solutions: bool,
+    /// Which variables need integer solutions?
+    pub integer_variables: IntegerMode,
+
+    /// Which variables must be non-positive rather than non-negative? Each of these is replaced by
+    /// its negation in the tableau, so its column holds `-x` rather than `x`, and the value is
+    /// negated again in the solution.
+    pub non_positive_variables: HashSet<String>,
+
+    /// Should every constraint be treated as an equality, whatever comparison it was written with?
+    /// This is for systems which are already in standard form, with their slack variables written
+    /// out as original variables, so no slack or surplus variables are added. Each constraint
+    /// gets an artificial variable instead.
+    pub standard_form: bool,
+
+    /// Should each constraint and the objective function be multiplied by the lowest common
+    /// multiple of its denominators before solving? This gives the initial tableau integer
+    /// coefficients, which keeps the fractions smaller. The solution is the same either way.
+    pub clear_denominators: bool,
+
+    /// Should we find and report an irreducible infeasible subsystem if there's no feasible
+    /// solution?
+    pub report_infeasible_subsystem: bool,
+
+    /// Should an objective function with no variables in it be an error? By default, we just warn
+    /// about it and solve for any feasible point, since every point is optimal.
+    pub require_nonempty_objective: bool,
+
+    /// Should a coefficient which isn't an integer be an error? This is for when every problem
+    /// should only have integer coefficients, so that a typo like `2.5x` for `25x` gets caught.
+    pub integer_coefficients_only: bool,
+
+    /// The method used to deal with artificial variables.
+    pub method: SimplexMethod,
+
+    /// The rule used to choose the pivot column.
+    pub pivot_rule: PivotRule,
+
+    /// The seed for choosing pivot columns with [`PivotRule::Random`]. Without a seed, each
+    /// tableau gets one from entropy.
+    pub rng_seed: Option<u64>,
+
+    /// How to break ties between pivot columns which the [`PivotRule`] doesn't separate.
+    pub column_tie_break: ColumnTieBreak,
+
+    /// The syntax allowed for numbers when parsing the objective function and constraints.
+    pub number_syntax: NumberSyntax,
+
+    /// Should the artificial columns stay in the tableau after the first stage of the two-stage
+    /// simplex, like in some textbooks? They're never chosen as the pivot column in the second
+    /// stage, so the solution is the same either way.
+    pub keep_artificial_columns: bool,
+
+    /// Should we use Bland's rule to prevent cycling? This takes precedence over the
+    /// [`PivotRule`].
+    pub anti_cycling: bool,
+
+    /// Should the solution values be displayed as continued fractions?
+    pub continued_fractions: bool,
+
+    /// Should the numbers in tableaux be right-aligned when displayed?
+    pub right_align_numbers: bool,
+
+    /// Should output use only ASCII characters, like `<=` instead of `≤` in constraints, and
+    /// `theta` and `*` instead of `θ` and `×` in tableaux, which are also drawn with ASCII?
+    pub ascii_output: bool,
+
+    /// How to name the slack, surplus, and artificial variables in the output.
+    pub variable_naming: VariableNaming,
+
+    /// The maximum number of iterations to do before giving up, including both stages of the
+    /// two-stage simplex.
+    pub max_iterations: usize,
+
+    /// The name of the objective function, like `C` in `minimise C = 3x + 2y`, which is shown
+    /// with its value in the solution. This is set when parsing the objective function.
+    pub objective_name: Option<String>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            integer_solutions: false,
+            integer_variables: IntegerMode::None,
+            non_positive_variables: HashSet::new(),
+            standard_form: false,
+            clear_denominators: false,
+            report_infeasible_subsystem: false,
+            require_nonempty_objective: false,
+            integer_coefficients_only: false,
+            method: SimplexMethod::default(),
+            pivot_rule: PivotRule::default(),
+            rng_seed: None,
+            column_tie_break: ColumnTieBreak::default(),
+            number_syntax: NumberSyntax::default(),
+            keep_artificial_columns: false,
+            anti_cycling: false,
+            continued_fractions: false,
+            right_align_numbers: false,
+            ascii_output: false,
+            variable_naming: VariableNaming::default(),
+            max_iterations: 1000,
+            objective_name: None,
         }
     }
 }
 
 impl Config {
-    /// Build the config from user input using `inquire`.
+    /// The options for [`Config::set_option`] which are flags, turned on just by naming them.
+    const FLAGS: [&'static str; 10] = [
+        "standard_form",
+        "clear_denominators",
+        "report_infeasible_subsystem",
+        "require_nonempty_objective",
+        "integer_coefficients_only",
+        "keep_artificial_columns",
+        "anti_cycling",
+        "continued_fractions",
+        "right_align_numbers",
+        "ascii_output",
+    ];
+
+    /// The options for [`Config::set_option`] which need a value.
+    const OPTIONS_WITH_VALUES: [&'static str; 9] = [
+        "method",
+        "pivot_rule",
+        "seed",
+        "tie_break",
+        "integer",
+        "non_positive",
+        "naming",
+        "numbers",
+        "max_iterations",
+    ];
+
+    /// Set one option from a line of a `config` section in the text format of
+    /// [`LinProgSystem::build_from_str`](super::system::LinProgSystem::build_from_str), which
+    /// lists the options. The line is either the name of a flag to turn on, like `ascii_output`,
+    /// or a name and a value separated by `=`, like `pivot_rule = random`. Variable names must be
+    /// in `variables`.
+    pub fn set_option(&mut self, line: &str, variables: &Variables) -> Result<()> {
+        let (name, value) = match line.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim())),
+            None => (line.trim(), None),
+        };
+        let variable_names = |names: &str| {
+            names
+                .split_whitespace()
+                .map(|var| match variables.0.contains(var) {
+                    true => Ok(var.to_string()),
+                    false => Err(Report::msg(format!("Unknown variable {var:?}"))),
+                })
+                .collect::<Result<HashSet<String>>>()
+        };
+        fn parse_number<T: FromStr<Err = ParseIntError>>(name: &str, value: &str) -> Result<T> {
+            value
+                .parse()
+                .map_err(|e| Report::msg(format!("Invalid {name} {value:?}: {e}")))
+        }
+
+        match (name, value) {
+            ("standard_form", None) => self.standard_form = true,
+            ("clear_denominators", None) => self.clear_denominators = true,
+            ("report_infeasible_subsystem", None) => self.report_infeasible_subsystem = true,
+            ("require_nonempty_objective", None) => self.require_nonempty_objective = true,
+            ("integer_coefficients_only", None) => self.integer_coefficients_only = true,
+            ("keep_artificial_columns", None) => self.keep_artificial_columns = true,
+            ("anti_cycling", None) => self.anti_cycling = true,
+            ("continued_fractions", None) => self.continued_fractions = true,
+            ("right_align_numbers", None) => self.right_align_numbers = true,
+            ("ascii_output", None) => self.ascii_output = true,
+            ("method", Some("two_stage")) => self.method = SimplexMethod::TwoStage,
+            ("method", Some("big_m")) => self.method = SimplexMethod::BigM,
+            ("pivot_rule", Some("most_negative")) => self.pivot_rule = PivotRule::MostNegative,
+            ("pivot_rule", Some("prefer_original")) => self.pivot_rule = PivotRule::PreferOriginal,
+            ("pivot_rule", Some("lexicographic")) => self.pivot_rule = PivotRule::Lexicographic,
+            ("pivot_rule", Some("random")) => self.pivot_rule = PivotRule::Random,
+            ("seed", Some(seed)) => self.rng_seed = Some(parse_number(name, seed)?),
+            ("tie_break", Some("first")) => self.column_tie_break = ColumnTieBreak::LowestIndex,
+            ("tie_break", Some("last")) => self.column_tie_break = ColumnTieBreak::HighestIndex,
+            ("integer", Some("all")) => self.integer_variables = IntegerMode::All,
+            ("integer", Some(names)) => {
+                self.integer_variables = IntegerMode::Some(variable_names(names)?)
+            }
+            ("non_positive", Some(names)) => self.non_positive_variables = variable_names(names)?,
+            ("naming", Some("symbolic")) => self.variable_naming = VariableNaming::Symbolic,
+            ("naming", Some("numbered")) => self.variable_naming = VariableNaming::Numbered,
+            ("naming", Some("subscripted")) => self.variable_naming = VariableNaming::Subscripted,
+            ("numbers", Some("plain")) => self.number_syntax = NumberSyntax::Plain,
+            ("numbers", Some("scientific")) => self.number_syntax = NumberSyntax::Scientific,
+            ("max_iterations", Some(max)) => self.max_iterations = parse_number(name, max)?,
+            (name, Some(value)) if Self::OPTIONS_WITH_VALUES.contains(&name) => {
+                return Err(Report::msg(format!("Invalid {name} {value:?}")))
+            }
+            (name, None) if Self::OPTIONS_WITH_VALUES.contains(&name) => {
+                return Err(Report::msg(format!("Expected a value for {name}")))
+            }
+            (name, Some(_)) if Self::FLAGS.contains(&name) => {
+                return Err(Report::msg(format!(
+                    "{name} is a flag, so it can't have a value"
+                )))
+            }
+            (name, _) => return Err(Report::msg(format!("Unknown config option {name:?}"))),
+        }
+
+        Ok(())
+    }
+
+    /// Build the config from user input using `inquire`. If the user wants integer solutions,
+    /// then we also ask which of the given variables need to be integers, and likewise for
+    /// non-positive variables.
     #[instrument]
-    pub fn build_from_user() -> Result<Self> {
+    pub fn build_from_user(variables: &Variables) -> Result<Self> {
         const INTEGER_SOLUTIONS: &str = "Require integer solutions";
+        const NON_POSITIVE_VARIABLES: &str = "Allow some variables to be non-positive";
+        const STANDARD_FORM: &str =
+            "Treat every constraint as an equality, for systems already in standard form";
+        const CLEAR_DENOMINATORS: &str =
+            "Scale the constraints and objective function to integer coefficients";
+        const REPORT_INFEASIBLE_SUBSYSTEM: &str = "Report a minimal infeasible subsystem";
+        const REQUIRE_NONEMPTY_OBJECTIVE: &str =
+            "Reject objective functions which don't reference any variables";
+        const INTEGER_COEFFICIENTS_ONLY: &str = "Reject coefficients which aren't integers";
+        const BIG_M: &str = "Use the Big-M method instead of two-stage simplex";
+        const KEEP_ARTIFICIAL_COLUMNS: &str =
+            "Keep the artificial columns in the second stage of two-stage simplex";
+        const PREFER_ORIGINAL: &str = "Prefer original variables when choosing between tied pivots";
+        const LEXICOGRAPHIC: &str =
+            "Break ties in the ratio test lexicographically to avoid cycling";
+        const RANDOM_PIVOT: &str = "Choose a random pivot column from the negative ones";
+        const LAST_TIED_COLUMN: &str = "Choose the last of any tied pivot columns, not the first";
+        const ANTI_CYCLING: &str = "Use Bland's rule to avoid cycling";
+        const CONTINUED_FRACTIONS: &str = "Show solution values as continued fractions";
+        const RIGHT_ALIGN_NUMBERS: &str = "Right-align the numbers in tableaux";
+        const SCIENTIFIC_NOTATION: &str = "Allow numbers in scientific notation, like 1.5e-3";
+        const ASCII_OUTPUT: &str = "Only use ASCII characters in the output";
+        const NUMBERED_NAMES: &str =
+            "Name slack, surplus, and artificial variables like s1, e1, a1";
+        const SUBSCRIPTED_NAMES: &str =
+            "Name slack, surplus, and artificial variables like s₁, e₁, a₁";
 
         let selected = MultiSelect::new(
             "Please enable or disable configurations:",
-            vec![INTEGER_SOLUTIONS],
+            vec![
+                INTEGER_SOLUTIONS,
+                NON_POSITIVE_VARIABLES,
+                STANDARD_FORM,
+                CLEAR_DENOMINATORS,
+                REPORT_INFEASIBLE_SUBSYSTEM,
+                REQUIRE_NONEMPTY_OBJECTIVE,
+                INTEGER_COEFFICIENTS_ONLY,
+                BIG_M,
+                KEEP_ARTIFICIAL_COLUMNS,
+                PREFER_ORIGINAL,
+                LEXICOGRAPHIC,
+                RANDOM_PIVOT,
+                LAST_TIED_COLUMN,
+                ANTI_CYCLING,
+                CONTINUED_FRACTIONS,
+                RIGHT_ALIGN_NUMBERS,
+                SCIENTIFIC_NOTATION,
+                ASCII_OUTPUT,
+                NUMBERED_NAMES,
+                SUBSCRIPTED_NAMES,
+            ],
         )
         .with_default(&[])
         .prompt()?;
 
+        let integer_variables = if selected.contains(&INTEGER_SOLUTIONS) {
+            let vars = variables.0.iter().sorted().collect_vec();
+            let all_indices = (0..vars.len()).collect_vec();
+            let integer_vars = MultiSelect::new("Which variables must be integers?", vars.clone())
+                .with_default(&all_indices)
+                .prompt()?;
+
+            if integer_vars.len() == vars.len() {
+                IntegerMode::All
+            } else if integer_vars.is_empty() {
+                IntegerMode::None
+            } else {
+                IntegerMode::Some(integer_vars.into_iter().cloned().collect())
+            }
+        } else {
+            IntegerMode::None
+        };
+
+        let non_positive_variables = if selected.contains(&NON_POSITIVE_VARIABLES) {
+            let vars = variables.0.iter().sorted().collect_vec();
+            MultiSelect::new("Which variables must be non-positive?", vars)
+                .prompt()?
+                .into_iter()
+                .cloned()
+                .collect()
+        } else {
+            HashSet::new()
+        };
+
         Ok(Self {
-            integer_solutions: selected.contains(&INTEGER_SOLUTIONS),
+            integer_variables,
+            non_positive_variables,
+            standard_form: selected.contains(&STANDARD_FORM),
+            clear_denominators: selected.contains(&CLEAR_DENOMINATORS),
+            report_infeasible_subsystem: selected.contains(&REPORT_INFEASIBLE_SUBSYSTEM),
+            require_nonempty_objective: selected.contains(&REQUIRE_NONEMPTY_OBJECTIVE),
+            integer_coefficients_only: selected.contains(&INTEGER_COEFFICIENTS_ONLY),
+            method: if selected.contains(&BIG_M) {
+                SimplexMethod::BigM
+            } else {
+                SimplexMethod::TwoStage
+            },
+            pivot_rule: if selected.contains(&RANDOM_PIVOT) {
+                PivotRule::Random
+            } else if selected.contains(&LEXICOGRAPHIC) {
+                PivotRule::Lexicographic
+            } else if selected.contains(&PREFER_ORIGINAL) {
+                PivotRule::PreferOriginal
+            } else {
+                PivotRule::MostNegative
+            },
+            rng_seed: None,
+            column_tie_break: if selected.contains(&LAST_TIED_COLUMN) {
+                ColumnTieBreak::HighestIndex
+            } else {
+                ColumnTieBreak::LowestIndex
+            },
+            number_syntax: if selected.contains(&SCIENTIFIC_NOTATION) {
+                NumberSyntax::Scientific
+            } else {
+                NumberSyntax::Plain
+            },
+            keep_artificial_columns: selected.contains(&KEEP_ARTIFICIAL_COLUMNS),
+            anti_cycling: selected.contains(&ANTI_CYCLING),
+            continued_fractions: selected.contains(&CONTINUED_FRACTIONS),
+            right_align_numbers: selected.contains(&RIGHT_ALIGN_NUMBERS),
+            ascii_output: selected.contains(&ASCII_OUTPUT),
+            variable_naming: if selected.contains(&SUBSCRIPTED_NAMES) {
+                VariableNaming::Subscripted
+            } else if selected.contains(&NUMBERED_NAMES) {
+                VariableNaming::Numbered
+            } else {
+                VariableNaming::Symbolic
+            },
+            ..Self::default()
         })
     }
 }
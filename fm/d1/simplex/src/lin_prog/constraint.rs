@@ -1,11 +1,14 @@
 //! This module handles constraints, which express how variable expressions relate to contants.
 
 use super::{
-    expression::simple_expression::parse::ExpressionCustomParseError, parse_float_no_e, Comparison,
-    Expression, Variables,
+    config::NumberSyntax,
+    expression::simple_expression::parse::{ExpressionCustomParseError, ParseResult},
+    parse_frac, Comparison, Expression, Variables,
 };
 use crate::Frac;
-use color_eyre::Result;
+use color_eyre::{Report, Result};
+use fraction::{Integer, One, Zero};
+use itertools::Itertools;
 use nom::character::complete::multispace0;
 use std::fmt;
 
@@ -33,40 +36,238 @@ impl<'v> fmt::Display for Constraint<'v> {
 }
 
 impl<'v> Constraint<'v> {
+    /// Format the constraint like [`Display`](fmt::Display), but with the comparison written in
+    /// ASCII, like `x + y <= 4`. See [`Comparison::to_ascii`].
+    pub fn to_ascii_string(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.var_expression,
+            self.comparison.to_ascii(),
+            self.constant
+        )
+    }
+
     /// Parse a constraint from the input using `nom`.
-    pub fn nom_parse<'i>(
+    ///
+    /// Both sides can have variable terms and constant terms, like `3x + 2 <= x + 10`. If they do,
+    /// then the variable terms are moved to the left and simplified, and the constants are moved to
+    /// the right, so that example becomes `2x <= 8`. A constraint which already has only variables
+    /// on the left and a constant on the right is left as it is.
+    pub fn nom_parse<'i>(input: &'i str, vars: &'v Variables) -> ParseResult<'i, Self> {
+        Self::nom_parse_with_syntax(input, vars, NumberSyntax::Plain)
+    }
+
+    /// Parse a constraint like [`nom_parse`](Self::nom_parse), allowing the given syntax for
+    /// numbers.
+    pub fn nom_parse_with_syntax<'i>(
         input: &'i str,
         vars: &'v Variables,
-    ) -> Result<(&'i str, Self), nom::Err<ExpressionCustomParseError<'i, nom::error::Error<&'i str>>>>
-    {
-        let (input, var_expression) = Expression::nom_parse(input, vars)?;
+        syntax: NumberSyntax,
+    ) -> ParseResult<'i, Self> {
+        let (input, (lhs_expression, lhs_constant)) = nom_parse_side(input, vars, syntax)?;
         let (input, _) = multispace0(input)?;
         let (input, comparison) = match Comparison::nom_parse(input) {
             Ok(x) => Ok(x),
             Err(e) => Err(nom::Err::Error(ExpressionCustomParseError::NomError(e))),
         }?;
-        let (input, _) = multispace0(input)?;
-        let (input, constant) = match parse_float_no_e(input) {
-            Ok(x) => Ok(x),
-            Err(e) => Err(nom::Err::Error(ExpressionCustomParseError::NomError(e))),
-        }?;
+        let (input, (rhs_expression, rhs_constant)) = nom_parse_side(input, vars, syntax)?;
+
+        let constraint = if rhs_expression.0.is_empty() && lhs_constant.is_zero() {
+            Constraint {
+                var_expression: lhs_expression,
+                comparison,
+                constant: rhs_constant,
+            }
+        } else {
+            let var_expression = Expression(
+                lhs_expression
+                    .0
+                    .into_iter()
+                    .chain(
+                        rhs_expression
+                            .0
+                            .into_iter()
+                            .map(|(coeff, var)| (-coeff, var)),
+                    )
+                    .collect(),
+            )
+            .simplify();
 
-        Ok((
-            input,
             Constraint {
                 var_expression,
                 comparison,
-                constant: constant.into(),
-            },
-        ))
+                constant: rhs_constant - lhs_constant,
+            }
+        };
+
+        Ok((input, constraint))
+    }
+
+    /// Parse all the constraints on a single line, which are separated by `;` or `,`.
+    ///
+    /// Every constraint must be parsed completely, and an error will say which constraint on the
+    /// line failed to parse.
+    pub fn parse_line(input: &str, vars: &'v Variables) -> Result<Vec<Self>> {
+        Self::parse_line_with_syntax(input, vars, NumberSyntax::Plain)
+    }
+
+    /// Parse all the constraints on a single line like [`parse_line`](Self::parse_line), allowing
+    /// the given syntax for numbers.
+    pub fn parse_line_with_syntax(
+        input: &str,
+        vars: &'v Variables,
+        syntax: NumberSyntax,
+    ) -> Result<Vec<Self>> {
+        input
+            .split([';', ','])
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .enumerate()
+            .map(
+                |(idx, segment)| match Self::nom_parse_with_syntax(segment, vars, syntax) {
+                    Ok((rest, constraint)) if rest.trim().is_empty() => Ok(constraint),
+                    Ok((rest, _)) => Err(Report::msg(format!(
+                        "Constraint {} ({segment:?}) has unexpected trailing input {rest:?}",
+                        idx + 1
+                    ))),
+                    Err(e) => Err(Report::msg(format!(
+                        "Constraint {} ({segment:?}) failed to parse: {e}",
+                        idx + 1
+                    ))),
+                },
+            )
+            .collect()
+    }
+
+    /// Parse a double-sided bound like `0 <= x <= 10` into the pair of constraints `x ≥ 0` and
+    /// `x ≤ 10`, allowing the given syntax for numbers. The bound can also be written the other
+    /// way round, like `10 >= x >= 0`, but both comparisons must point the same way, and the
+    /// middle must be a single variable.
+    pub fn parse_bound_with_syntax(
+        input: &str,
+        vars: &'v Variables,
+        syntax: NumberSyntax,
+    ) -> Result<[Self; 2]> {
+        let parse_error =
+            |e: &dyn fmt::Display| Report::msg(format!("Bound {input:?} failed to parse: {e}"));
+        let parse_comparison =
+            |input| multispace0(input).and_then(|(input, _)| Comparison::nom_parse(input));
+
+        let (rest, first) = parse_constant_term(input, syntax).map_err(|e| parse_error(&e))?;
+        let (rest, first_comparison) = parse_comparison(rest).map_err(|e| parse_error(&e))?;
+        let (rest, (expression, constant)) =
+            nom_parse_side(rest, vars, syntax).map_err(|e| parse_error(&e))?;
+        let (rest, second_comparison) = parse_comparison(rest).map_err(|e| parse_error(&e))?;
+        let (rest, second) = parse_constant_term(rest, syntax).map_err(|e| parse_error(&e))?;
+        if !rest.trim().is_empty() {
+            return Err(Report::msg(format!(
+                "Bound {input:?} has unexpected trailing input {rest:?}"
+            )));
+        }
+
+        let var = match expression.0.as_slice() {
+            &[(coeff, var)] if coeff == Frac::one() && constant.is_zero() => var,
+            _ => {
+                return Err(Report::msg(format!(
+                    "The middle of bound {input:?} must be a single variable"
+                )))
+            }
+        };
+        let (lower, upper) = match (first_comparison, second_comparison) {
+            (Comparison::LessThanOrEqual, Comparison::LessThanOrEqual) => (first, second),
+            (Comparison::GreaterThanOrEqual, Comparison::GreaterThanOrEqual) => (second, first),
+            _ => {
+                return Err(Report::msg(format!(
+                    "Bound {input:?} must use either ≤ twice or ≥ twice"
+                )))
+            }
+        };
+
+        Ok([
+            (Comparison::GreaterThanOrEqual, lower),
+            (Comparison::LessThanOrEqual, upper),
+        ]
+        .map(|(comparison, constant)| Constraint {
+            var_expression: Expression(vec![(Frac::one(), var)]),
+            comparison,
+            constant,
+        }))
+    }
+
+    /// Parse all the bounds on a single line, which are separated by `;` or `,` like in
+    /// [`parse_line`](Self::parse_line). Each bound gives two constraints. See
+    /// [`parse_bound_with_syntax`](Self::parse_bound_with_syntax).
+    pub fn parse_bounds_line_with_syntax(
+        input: &str,
+        vars: &'v Variables,
+        syntax: NumberSyntax,
+    ) -> Result<Vec<Self>> {
+        input
+            .split([';', ','])
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| Self::parse_bound_with_syntax(segment, vars, syntax))
+            .flatten_ok()
+            .collect()
+    }
+
+    /// Rebind the constraint to reference the same variables in a different [`Variables`]
+    /// instance. See [`Expression::rebind`].
+    pub fn rebind<'w>(&self, vars: &'w Variables) -> Constraint<'w> {
+        Constraint {
+            var_expression: self.var_expression.rebind(vars),
+            comparison: self.comparison,
+            constant: self.constant,
+        }
     }
 
-    /// Simplify the constraint.
+    /// Simplify the constraint, and normalise it so that the constant isn't negative.
     pub fn simplify(self) -> Self {
         Self {
             var_expression: self.var_expression.simplify(),
             ..self
         }
+        .normalise()
+    }
+
+    /// Make sure the constant isn't negative by multiplying both sides by -1 if needed, which
+    /// flips the comparison. The simplex tableau needs every row to start with a non-negative
+    /// value.
+    pub fn normalise(self) -> Self {
+        if self.constant >= Frac::zero() {
+            return self;
+        }
+
+        Self {
+            var_expression: Expression(
+                self.var_expression
+                    .0
+                    .into_iter()
+                    .map(|(coeff, var)| (-coeff, var))
+                    .collect(),
+            ),
+            comparison: self.comparison.flip(),
+            constant: -self.constant,
+        }
+    }
+
+    /// The lowest common multiple of the denominators of the coefficients and the constant. See
+    /// [`Expression::denominator_lcm`].
+    pub fn denominator_lcm(&self) -> u64 {
+        self.var_expression
+            .denominator_lcm()
+            .lcm(self.constant.denom().unwrap_or(&1))
+    }
+
+    /// Multiply both sides by [`Constraint::denominator_lcm`], so that every coefficient and the
+    /// constant are integers. This doesn't change which points satisfy the constraint.
+    pub fn clear_denominators(self) -> Self {
+        let scale = Frac::from(self.denominator_lcm());
+        Self {
+            var_expression: self.var_expression * scale,
+            constant: self.constant * scale,
+            ..self
+        }
     }
 
     /// Test to see if the constraint holds true for the given set of variables.
@@ -76,9 +277,140 @@ impl<'v> Constraint<'v> {
     }
 }
 
+/// A list of constraints, which can be collected from an iterator of [`Constraint`]s and turned
+/// into the `Vec` that a [`LinProgSystem`](super::system::LinProgSystem) holds, like in the
+/// `constraints_builder` of a [`LinProgSystemBuilder`](super::system::LinProgSystemBuilder).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Constraints<'v>(pub Vec<Constraint<'v>>);
+
+impl<'v> Constraints<'v> {
+    /// Create an empty list of constraints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a constraint to the end of the list.
+    pub fn add(&mut self, constraint: Constraint<'v>) -> &mut Self {
+        self.0.push(constraint);
+        self
+    }
+
+    /// Iterate over the constraints in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Constraint<'v>> {
+        self.0.iter()
+    }
+
+    /// The number of constraints.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Are there no constraints?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Simplify and normalise every constraint. See [`Constraint::simplify`].
+    pub fn simplify_all(self) -> Self {
+        self.into_iter().map(Constraint::simplify).collect()
+    }
+}
+
+impl<'v> FromIterator<Constraint<'v>> for Constraints<'v> {
+    fn from_iter<I: IntoIterator<Item = Constraint<'v>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'v> IntoIterator for Constraints<'v> {
+    type Item = Constraint<'v>;
+    type IntoIter = std::vec::IntoIter<Constraint<'v>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'v> From<Constraints<'v>> for Vec<Constraint<'v>> {
+    fn from(value: Constraints<'v>) -> Self {
+        value.0
+    }
+}
+
+/// Parse one side of a constraint, which is a mix of variable terms and constant terms separated
+/// by `+` or `-`. This returns the variable terms as an expression, along with the sum of the
+/// constant terms.
+fn nom_parse_side<'i, 'v>(
+    input: &'i str,
+    vars: &'v Variables,
+    syntax: NumberSyntax,
+) -> ParseResult<'i, (Expression<'v>, Frac)> {
+    let mut terms = vec![];
+    let mut constant = Frac::zero();
+    let mut input = input;
+    let mut first_term = true;
+
+    loop {
+        let (rest, _) = multispace0(input)?;
+
+        // Every term after the first needs a + or - before it, but the - is part of the term
+        let rest = if first_term {
+            rest
+        } else if let Some(rest) = rest.strip_prefix('+') {
+            rest
+        } else if rest.starts_with('-') {
+            rest
+        } else {
+            break;
+        };
+
+        // Expressions stop before constant terms, so we alternate between them
+        match Expression::nom_parse_with_syntax(rest, vars, syntax) {
+            Ok((rest, expression)) => {
+                terms.extend(expression.0);
+                input = rest;
+            }
+            Err(nom::Err::Error(_)) => match parse_constant_term(rest, syntax) {
+                Ok((rest, num)) => {
+                    constant += num;
+                    input = rest;
+                }
+                Err(e) if first_term => {
+                    return Err(nom::Err::Error(ExpressionCustomParseError::NomError(e)))
+                }
+                Err(_) => break,
+            },
+            Err(e) => return Err(e),
+        }
+
+        first_term = false;
+    }
+
+    Ok((input, (Expression(terms), constant)))
+}
+
+/// Parse a constant term, which is a number with an optional `-` before it.
+fn parse_constant_term(input: &str, syntax: NumberSyntax) -> nom::IResult<&str, Frac> {
+    use nom::{
+        character::complete::{char, one_of},
+        combinator::{opt, peek},
+        sequence::terminated,
+    };
+
+    let (input, _) = multispace0(input)?;
+    let (input, negative) = opt(terminated(char('-'), multispace0))(input)?;
+
+    // [`parse_frac`] treats a missing number as 1, so make sure there's actually a number
+    peek(one_of("0123456789."))(input)?;
+    let (input, num) = parse_frac(input, syntax)?;
+
+    Ok((input, if negative.is_some() { -num } else { num }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lin_prog::{config::Config, system::LinProgSystemBuilder, ObjectiveFunction};
 
     #[test]
     fn constraint_parse_test() {
@@ -177,4 +509,399 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn constraint_parse_fraction_test() {
+        let variables = Variables::from(["x", "y"]);
+
+        assert_eq!(
+            Constraint::nom_parse("3x <= 1/3", &variables),
+            Ok((
+                "",
+                Constraint {
+                    var_expression: Expression(vec![(3.into(), "x")]),
+                    comparison: Comparison::LessThanOrEqual,
+                    constant: Frac::new(1u32, 3u32)
+                }
+            ))
+        );
+        assert_eq!(
+            Constraint::nom_parse("1/3 x + 2/7 y <= 5", &variables),
+            Ok((
+                "",
+                Constraint {
+                    var_expression: Expression(vec![
+                        (Frac::new(1u32, 3u32), "x"),
+                        (Frac::new(2u32, 7u32), "y")
+                    ]),
+                    comparison: Comparison::LessThanOrEqual,
+                    constant: 5.into()
+                }
+            ))
+        );
+        assert!(Constraint::parse_line("x + y <= 2/0", &variables).is_err());
+        assert!(Constraint::parse_line("3/0 x + y <= 2", &variables)
+            .is_err_and(|err| err.to_string().contains("InvalidDenominator(\"0\")")));
+        assert!(Constraint::parse_line("x / y <= 2", &variables).is_err());
+    }
+
+    #[test]
+    fn constraint_parse_scientific_notation_test() {
+        let variables = Variables::from(["x", "e"]);
+
+        assert_eq!(
+            Constraint::parse_line_with_syntax(
+                "1.5e-3x + 2e <= 2.5E2",
+                &variables,
+                NumberSyntax::Scientific
+            )
+            .unwrap(),
+            vec![Constraint {
+                var_expression: Expression(vec![(Frac::new(3u32, 2000u32), "x"), (2.into(), "e")]),
+                comparison: Comparison::LessThanOrEqual,
+                constant: 250.into()
+            }]
+        );
+
+        // Without scientific notation, the e is a variable
+        assert_eq!(
+            Constraint::parse_line("x + 2e-3 <= 5", &variables).unwrap(),
+            vec![Constraint {
+                var_expression: Expression(vec![(2.into(), "e"), (1.into(), "x")]),
+                comparison: Comparison::LessThanOrEqual,
+                constant: 8.into()
+            }]
+        );
+        assert_eq!(
+            Constraint::parse_line_with_syntax(
+                "x + 2e-3 <= 5",
+                &variables,
+                NumberSyntax::Scientific
+            )
+            .unwrap(),
+            vec![Constraint {
+                var_expression: Expression(vec![(1.into(), "x")]),
+                comparison: Comparison::LessThanOrEqual,
+                constant: Frac::new(4998u32, 1000u32)
+            }]
+        );
+    }
+
+    #[test]
+    fn constraint_parse_unicode_comparison_test() {
+        let variables = Variables::from(["x", "y"]);
+
+        for (input, comparison) in [
+            ("x + y ≦ 4", Comparison::LessThanOrEqual),
+            ("x + y ⩽ 4", Comparison::LessThanOrEqual),
+            ("x + y ≧ 4", Comparison::GreaterThanOrEqual),
+            ("x + y ⩾ 4", Comparison::GreaterThanOrEqual),
+        ] {
+            assert_eq!(
+                Constraint::nom_parse(input, &variables),
+                Ok((
+                    "",
+                    Constraint {
+                        var_expression: Expression(vec![(1.into(), "x"), (1.into(), "y")]),
+                        comparison,
+                        constant: 4.into()
+                    }
+                )),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn constraint_to_ascii_string_test() {
+        let variables = Variables::from(["x", "y"]);
+        let constraints = Constraint::parse_line("x + y ≤ 4; 2x >= 1; x ⩾ y - 3", &variables)
+            .unwrap()
+            .into_iter()
+            .map(|con| (con.to_string(), con.to_ascii_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            constraints,
+            vec![
+                ("1x + 1y ≤ 4".to_string(), "1x + 1y <= 4".to_string()),
+                ("2x ≥ 1".to_string(), "2x >= 1".to_string()),
+                ("1x + -1y ≥ -3".to_string(), "1x + -1y >= -3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn constraint_normalise_test() {
+        let variables = Variables::from(["x", "y"]);
+
+        assert_eq!(
+            Constraint::nom_parse("2x <= -4", &variables)
+                .unwrap()
+                .1
+                .simplify(),
+            Constraint {
+                var_expression: Expression(vec![(-Frac::new(2u32, 1u32), "x")]),
+                comparison: Comparison::GreaterThanOrEqual,
+                constant: 4.into()
+            }
+        );
+        assert_eq!(
+            Constraint::nom_parse("x - y >= -5", &variables)
+                .unwrap()
+                .1
+                .normalise(),
+            Constraint {
+                var_expression: Expression(vec![(-Frac::new(1u32, 1u32), "x"), (1.into(), "y")]),
+                comparison: Comparison::LessThanOrEqual,
+                constant: 5.into()
+            }
+        );
+        assert_eq!(
+            Constraint::nom_parse("x + y = -1", &variables)
+                .unwrap()
+                .1
+                .normalise()
+                .comparison,
+            Comparison::Equal
+        );
+    }
+
+    #[test]
+    fn constraints_from_iterator_test() {
+        let variables = Variables::from(["x", "y"]);
+        let mut constraints: Constraints = ["x + x <= 4", "x - y >= -5"]
+            .into_iter()
+            .map(|input| Constraint::nom_parse(input, &variables).unwrap().1)
+            .collect();
+        constraints.add(Constraint::nom_parse("y <= 3", &variables).unwrap().1);
+        assert_eq!(constraints.len(), 3);
+
+        let constraints = constraints.simplify_all();
+        assert_eq!(
+            constraints
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["2x ≤ 4", "-1x + 1y ≤ 5", "1y ≤ 3"]
+        );
+
+        let system = LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config: Config::default(),
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("x + y", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                ["x + x <= 4", "y <= 3"]
+                    .into_iter()
+                    .map(|input| Constraint::nom_parse(input, vars).unwrap().1)
+                    .collect::<Constraints>()
+                    .simplify_all()
+                    .into()
+            },
+        }
+        .build();
+        assert_eq!(system.with_constraints(|cons| cons.len()), 2);
+    }
+
+    #[test]
+    fn constraint_parse_line_test() {
+        let variables = Variables::from(["x", "y"]);
+
+        assert_eq!(
+            Constraint::parse_line("5x+7y<=70; 10x+3y<=60", &variables).unwrap(),
+            vec![
+                Constraint {
+                    var_expression: Expression(vec![(5.into(), "x"), (7.into(), "y")]),
+                    comparison: Comparison::LessThanOrEqual,
+                    constant: 70.into()
+                },
+                Constraint {
+                    var_expression: Expression(vec![(10.into(), "x"), (3.into(), "y")]),
+                    comparison: Comparison::LessThanOrEqual,
+                    constant: 60.into()
+                }
+            ]
+        );
+
+        let expected = vec![
+            Constraint {
+                var_expression: Expression(vec![(1.into(), "x"), (1.into(), "y")]),
+                comparison: Comparison::LessThanOrEqual,
+                constant: 10.into(),
+            },
+            Constraint {
+                var_expression: Expression(vec![(1.into(), "x")]),
+                comparison: Comparison::GreaterThanOrEqual,
+                constant: 2.into(),
+            },
+        ];
+        assert_eq!(
+            Constraint::parse_line("x + y <= 10; x >= 2", &variables).unwrap(),
+            expected
+        );
+        assert_eq!(
+            Constraint::parse_line("x + y <= 10, x >= 2", &variables).unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            Constraint::parse_line("x + y <= 4;", &variables).unwrap(),
+            vec![Constraint {
+                var_expression: Expression(vec![(1.into(), "x"), (1.into(), "y")]),
+                comparison: Comparison::LessThanOrEqual,
+                constant: 4.into()
+            }]
+        );
+
+        assert!(Constraint::parse_line("x <= 1; x + z <= 2", &variables)
+            .is_err_and(|err| err.to_string().starts_with("Constraint 2 ")));
+        assert!(Constraint::parse_line("x <= 1, x >= y >= 0", &variables)
+            .is_err_and(|err| err.to_string().starts_with("Constraint 2 ")));
+        assert!(Constraint::parse_line("x <= 1 2", &variables)
+            .is_err_and(|err| err.to_string().contains("trailing input")));
+    }
+
+    #[test]
+    fn constraint_parse_bound_test() {
+        let variables = Variables::from(["x", "y"]);
+        let parse =
+            |input| Constraint::parse_bound_with_syntax(input, &variables, NumberSyntax::Plain);
+        let bounds = [
+            Constraint {
+                var_expression: Expression(vec![(1.into(), "x")]),
+                comparison: Comparison::GreaterThanOrEqual,
+                constant: 0.into(),
+            },
+            Constraint {
+                var_expression: Expression(vec![(1.into(), "x")]),
+                comparison: Comparison::LessThanOrEqual,
+                constant: 10.into(),
+            },
+        ];
+
+        assert_eq!(parse("0 <= x <= 10").unwrap(), bounds);
+        assert_eq!(parse("0≤x≤10").unwrap(), bounds);
+        assert_eq!(parse("10 >= x >= 0").unwrap(), bounds);
+        assert_eq!(
+            parse("-1/2 <= y <= 3").unwrap().map(|con| con.constant),
+            [Frac::new_neg(1u32, 2u32), 3.into()]
+        );
+
+        for input in [
+            "0 <= x + y <= 10",
+            "0 <= 2x <= 10",
+            "0 <= x + 1 <= 10",
+            "0 <= 5 <= 10",
+        ] {
+            assert!(
+                parse(input).is_err_and(|err| err.to_string().contains("single variable")),
+                "{input:?} should be rejected"
+            );
+        }
+        assert!(parse("0 <= x >= 10").is_err_and(|err| err.to_string().contains("twice")));
+        assert!(parse("0 <= x <= 10 5").is_err_and(|err| err.to_string().contains("trailing")));
+        assert!(parse("0 <= z <= 10").is_err());
+
+        assert_eq!(
+            Constraint::parse_bounds_line_with_syntax(
+                "0 <= x <= 10; 1 <= y <= 2",
+                &variables,
+                NumberSyntax::Plain
+            )
+            .unwrap()
+            .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn constraint_parse_both_sides_test() {
+        let variables = Variables::from(["x", "y"]);
+
+        assert_eq!(
+            Constraint::nom_parse("3x + 2 <= x + 10", &variables),
+            Ok((
+                "",
+                Constraint {
+                    var_expression: Expression(vec![(2.into(), "x")]),
+                    comparison: Comparison::LessThanOrEqual,
+                    constant: 8.into()
+                }
+            ))
+        );
+        assert_eq!(
+            Constraint::nom_parse("2x + y <= x + 8", &variables),
+            Ok((
+                "",
+                Constraint {
+                    var_expression: Expression(vec![(1.into(), "x"), (1.into(), "y")]),
+                    comparison: Comparison::LessThanOrEqual,
+                    constant: 8.into()
+                }
+            ))
+        );
+        assert_eq!(
+            Constraint::nom_parse("x - 1/2 >= 2y - 3 + 1/4", &variables),
+            Ok((
+                "",
+                Constraint {
+                    var_expression: Expression(vec![
+                        (1.into(), "x"),
+                        (-Frac::new(2u32, 1u32), "y")
+                    ]),
+                    comparison: Comparison::GreaterThanOrEqual,
+                    constant: -Frac::new(9u32, 4u32)
+                }
+            ))
+        );
+        assert_eq!(
+            Constraint::nom_parse("x <= 1 y", &variables),
+            Ok((
+                "",
+                Constraint {
+                    var_expression: Expression(vec![
+                        (1.into(), "x"),
+                        (-Frac::new(1u32, 1u32), "y")
+                    ]),
+                    comparison: Comparison::LessThanOrEqual,
+                    constant: Frac::zero()
+                }
+            ))
+        );
+        assert!(Constraint::parse_line("x / y <= x + 2", &variables).is_err());
+        assert!(Constraint::parse_line("x + z <= x + 2", &variables).is_err());
+    }
+
+    #[test]
+    fn constraint_parse_cancelling_variables_test() {
+        let variables = Variables::from(["x", "y"]);
+
+        assert_eq!(
+            Constraint::nom_parse("x + y <= x + 3", &variables),
+            Ok((
+                "",
+                Constraint {
+                    var_expression: Expression(vec![(1.into(), "y")]),
+                    comparison: Comparison::LessThanOrEqual,
+                    constant: 3.into()
+                }
+            ))
+        );
+        assert_eq!(
+            Constraint::nom_parse("2x + 5 >= x + x + 1", &variables),
+            Ok((
+                "",
+                Constraint {
+                    var_expression: Expression(vec![]),
+                    comparison: Comparison::GreaterThanOrEqual,
+                    constant: -Frac::new(4u32, 1u32)
+                }
+            ))
+        );
+    }
 }
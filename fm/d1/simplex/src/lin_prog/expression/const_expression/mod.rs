@@ -59,9 +59,8 @@ impl<'v, T: ?Sized + Ord + Hash> ConstExpression<'v, T> {
             iter::once(VariableOrConst::Constant(constant))
                 .chain(variables)
                 // Filter out the constant if it's 0
-                .filter(|var_or_const| match var_or_const {
-                    VariableOrConst::Constant(x) if *x == Frac::zero() => false,
-                    _ => true,
+                .filter(|var_or_const| {
+                    !matches!(var_or_const, VariableOrConst::Constant(x) if *x == Frac::zero())
                 })
                 .collect(),
         )
@@ -74,13 +73,38 @@ impl<'v, T: ?Sized + Ord + Hash> ConstExpression<'v, T> {
             _ => None,
         })
     }
+
+    /// Return the total coefficient of the given variable in the expression, adding together any
+    /// duplicate terms. This is 0 if the variable doesn't appear.
+    pub fn coefficient_of(&self, var: &T) -> Frac {
+        self.0
+            .iter()
+            .filter_map(|var_or_const| match var_or_const {
+                VariableOrConst::Variable(num, v) if *v == var => Some(*num),
+                _ => None,
+            })
+            .fold(Frac::zero(), |acc, num| acc + num)
+    }
+
+    /// Return every variable which has a term in the expression, sorted and without duplicates.
+    pub fn variables(&self) -> Vec<&'v T> {
+        self.0
+            .iter()
+            .filter_map(|var_or_const| match var_or_const {
+                VariableOrConst::Variable(_, var) => Some(*var),
+                _ => None,
+            })
+            .sorted()
+            .dedup()
+            .collect()
+    }
 }
 
 impl<'v, T: ?Sized + Ord + Hash> Add for ConstExpression<'v, T> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0.into_iter().chain(rhs.0.into_iter()).collect()).simplify()
+        Self(self.0.into_iter().chain(rhs.0).collect()).simplify()
     }
 }
 
@@ -163,6 +187,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn coefficient_of_and_variables_test() {
+        let expression = ConstExpression(vec![
+            Constant(15.into()),
+            Variable(3.into(), "b"),
+            Variable(Frac::new(1u32, 2u32), "a"),
+            Constant(2.into()),
+            Variable(-Frac::new(5u32, 2u32), "b"),
+        ]);
+
+        assert_eq!(expression.coefficient_of("a"), Frac::new(1u32, 2u32));
+        assert_eq!(expression.coefficient_of("b"), Frac::new(1u32, 2u32));
+        assert_eq!(expression.coefficient_of("c"), Frac::zero());
+        assert_eq!(expression.variables(), vec!["a", "b"]);
+
+        assert_eq!(
+            ConstExpression::<str>(vec![Constant(1.into())]).variables(),
+            Vec::<&str>::new()
+        );
+    }
+
     #[test]
     fn simplify_test() {
         assert_eq!(
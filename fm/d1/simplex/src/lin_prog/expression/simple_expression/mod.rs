@@ -1,11 +1,16 @@
 //! This module handles expressions in terms of variables with no constant terms.
 
+use super::const_expression::{ConstExpression, VariableOrConst};
 use crate::{lin_prog::Variables, Frac};
 use color_eyre::Result;
-use fraction::Zero;
+use fraction::{Integer, Zero};
 use inquire::Text;
 use itertools::Itertools;
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Add, Mul},
+};
 
 pub(crate) mod parse;
 
@@ -56,6 +61,45 @@ impl<'v> Expression<'v> {
         )
     }
 
+    /// The lowest common multiple of the denominators of the coefficients, which is the smallest
+    /// number that we can multiply the expression by to make every coefficient an integer.
+    pub fn denominator_lcm(&self) -> u64 {
+        self.0
+            .iter()
+            .filter_map(|(coeff, _)| coeff.denom())
+            .fold(1, |acc, denom| acc.lcm(denom))
+    }
+
+    /// Convert this expression into a [`ConstExpression`] with the same variable terms and no
+    /// constant terms, so that a constant can be added to it.
+    pub fn to_const_expression(self) -> ConstExpression<'v, str> {
+        ConstExpression(
+            self.0
+                .into_iter()
+                .map(|(coeff, var)| VariableOrConst::Variable(coeff, var))
+                .collect(),
+        )
+    }
+
+    /// Rebind the expression to reference the same variables in a different [`Variables`]
+    /// instance.
+    ///
+    /// This method will panic if any of the variables are missing from `vars`.
+    pub fn rebind<'w>(&self, vars: &'w Variables) -> Expression<'w> {
+        Expression(
+            self.0
+                .iter()
+                .map(|&(coeff, var)| {
+                    let var = vars
+                        .0
+                        .get(var)
+                        .expect("Every variable should exist in the new set of variables");
+                    (coeff, var.as_str())
+                })
+                .collect(),
+        )
+    }
+
     /// Evaluate the expression for the given variables.
     pub(crate) fn evaluate(&self, vars: &[(&'v str, Frac)]) -> Frac {
         self
@@ -91,10 +135,50 @@ impl<'v> Expression<'v> {
     }
 }
 
+impl<'v> Add for Expression<'v> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.into_iter().chain(rhs.0).collect()).simplify()
+    }
+}
+
+/// Multiply every coefficient in the expression by a scalar.
+impl<'v> Mul<Frac> for Expression<'v> {
+    type Output = Self;
+
+    fn mul(self, rhs: Frac) -> Self::Output {
+        Self(
+            self.0
+                .into_iter()
+                .map(|(coeff, var)| (coeff * rhs, var))
+                .collect(),
+        )
+        .simplify()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn add_and_mul_test() {
+        assert_eq!(
+            Expression(vec![(2.into(), "a"), (3.into(), "b")])
+                + Expression(vec![(-Frac::new(2u32, 1u32), "a"), (1.into(), "c")]),
+            Expression(vec![(3.into(), "b"), (1.into(), "c")])
+        );
+        assert_eq!(
+            Expression(vec![(2.into(), "a"), (3.into(), "b")]) * Frac::new(1u32, 2u32),
+            Expression(vec![(1.into(), "a"), (Frac::new(3u32, 2u32), "b")])
+        );
+        assert_eq!(
+            Expression(vec![(2.into(), "a")]) * Frac::zero(),
+            Expression(vec![])
+        );
+    }
+
     #[test]
     fn simplify_test() {
         assert_eq!(
@@ -139,4 +223,30 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn to_const_expression_test() {
+        let expression = Expression(vec![
+            (2.into(), "a"),
+            (-Frac::new(3u32, 10u32), "b"),
+            (1.into(), "a"),
+        ]);
+        let const_expression = expression.to_const_expression();
+
+        assert_eq!(
+            const_expression.0,
+            vec![
+                VariableOrConst::Variable(2.into(), "a"),
+                VariableOrConst::Variable(-Frac::new(3u32, 10u32), "b"),
+                VariableOrConst::Variable(1.into(), "a"),
+            ]
+        );
+        assert_eq!(const_expression.constant(), None);
+        assert_eq!(const_expression.coefficient_of("a"), 3.into());
+
+        assert_eq!(
+            Expression(vec![]).to_const_expression(),
+            ConstExpression(vec![])
+        );
+    }
 }
@@ -2,7 +2,10 @@
 
 use super::Expression;
 use crate::{
-    lin_prog::{parse_float_no_e, validate_variable, Variables, _VARIABLE_REGEX_INTERNAL},
+    lin_prog::{
+        config::NumberSyntax, parse_float_with_syntax, validate_variable, Variables,
+        _VARIABLE_REGEX_INTERNAL,
+    },
     Frac,
 };
 use color_eyre::{Report, Result};
@@ -32,6 +35,10 @@ pub enum ExpressionCustomParseError<'s, E> {
     /// Bad punctuation in the expression, like "a*b".
     #[error("bad punctuation {0:?}")]
     BadPunctuation(String),
+
+    /// The denominator of a fractional coefficient is 0 or too big, like in "3/0 x".
+    #[error("invalid denominator {0:?}; it must be a non-zero whole number")]
+    InvalidDenominator(&'s str),
 }
 
 // Convert from a nom error to mine using `?`.
@@ -60,28 +67,56 @@ impl<'s, I> ParseError<I> for ExpressionCustomParseError<'s, nom::error::Error<I
     }
 }
 
+/// The result of one of the `nom` parsers for expressions, using [`ExpressionCustomParseError`].
+pub(crate) type ParseResult<'i, O> =
+    Result<(&'i str, O), nom::Err<ExpressionCustomParseError<'i, nom::error::Error<&'i str>>>>;
+
 impl<'v> Expression<'v> {
     /// Parse an expression from the input using `nom`.
-    pub(crate) fn nom_parse<'i>(
+    pub(crate) fn nom_parse<'i>(input: &'i str, vars: &'v Variables) -> ParseResult<'i, Self> {
+        Self::nom_parse_with_syntax(input, vars, NumberSyntax::Plain)
+    }
+
+    /// Parse an expression from the input using `nom`, allowing the given syntax for the
+    /// coefficients.
+    pub(crate) fn nom_parse_with_syntax<'i>(
         input: &'i str,
         vars: &'v Variables,
-    ) -> Result<(&'i str, Self), nom::Err<ExpressionCustomParseError<'i, nom::error::Error<&'i str>>>>
-    {
-        let regex_disallowed_chars = Regex::new(r"[^a-zA-Z0-9.\s_<>=≤≥+-]").unwrap();
+        syntax: NumberSyntax,
+    ) -> ParseResult<'i, Self> {
+        let regex_disallowed_chars = Regex::new(r"[^a-zA-Z0-9.\s_<>=≤≥≦≧⩽⩾+-]").unwrap();
+        let regex_fraction = Regex::new(r"\d\s*/\s*\d").unwrap();
 
-        if let Ok((_, punctuation)) =
-            re_find::<'i, nom::error::Error<&'i str>>(regex_disallowed_chars)(input)
+        // Only check up to the comparison, since the constant after it may be a fraction
+        let expression_part = match input.find(['<', '>', '=', '≤', '≥', '≦', '≧', '⩽', '⩾'])
         {
+            Some(idx) => &input[..idx],
+            None => input,
+        };
+
+        // Coefficients and constant terms can be fractions, so the slash in something like `1/3`
+        // is fine
+        let expression_part = regex_fraction.replace_all(expression_part, "");
+
+        if let Some(punctuation) = regex_disallowed_chars.find(&expression_part) {
             return Err(nom::Err::Failure(
-                ExpressionCustomParseError::BadPunctuation(punctuation.to_string()),
+                ExpressionCustomParseError::BadPunctuation(punctuation.as_str().to_string()),
             ));
         }
 
+        // An explicit `+` on the first term is fine, but a `+` on its own still needs to be
+        // caught as bad punctuation by the term parser
+        let input = match pair(multispace0::<&str, nom::error::Error<&str>>, char('+'))(input) {
+            Ok((rest, _))
+                if !rest.trim_start().is_empty() && !rest.trim_start().starts_with('+') =>
+            {
+                rest
+            }
+            _ => input,
+        };
+
         let (input, expressions) = custom_separated_list1(
-            |input| -> Result<
-                (&str, ()),
-                nom::Err<ExpressionCustomParseError<'i, nom::error::Error<&'i str>>>,
-            > {
+            |input| -> ParseResult<'i, ()> {
                 let (input, _) = multispace0(input)?;
                 let (input2, plus_minus) = alt((tag("+"), tag("-")))(input)?;
                 match plus_minus {
@@ -94,10 +129,7 @@ impl<'v> Expression<'v> {
                 }
             },
             // This closure parses a single term
-            move |input| -> Result<
-                (&'i str, (Frac, &'v str)),
-                nom::Err<ExpressionCustomParseError<'i, nom::error::Error<&'i str>>>,
-            > {
+            move |input| -> ParseResult<'i, (Frac, &'v str)> {
                 // If we've got any unconsumed punctuation at this point, then it's bad punctuation
                 if let Ok((_, punctuation)) =
                     char::<&'i str, nom::error::Error<&'i str>>('+')(input)
@@ -107,20 +139,21 @@ impl<'v> Expression<'v> {
                     ));
                 }
 
-                let (input, coeff) = match parse_float(input) {
+                let (input, coeff) = match parse_float(input, syntax) {
                     Ok((i, c)) => (i, c),
 
                     // No float found, so assume it's a 1
                     Err(nom::Err::Error(nom::error::Error {
                         input: _,
                         code: nom::error::ErrorKind::Float | nom::error::ErrorKind::Char,
-                    })) => (input, 1.),
+                    })) => (input, Frac::from(1)),
 
                     // In the case of a different error, just wrap and propagate
                     Err(e) => {
                         return Err(nom::Err::Failure(ExpressionCustomParseError::NomError(e)))
                     }
                 };
+                let (input, coeff) = parse_denominator(input, coeff)?;
 
                 // Find a variable
                 let (input, var) = re_find(
@@ -132,7 +165,7 @@ impl<'v> Expression<'v> {
 
                 // Make sure the variable is valid
                 match vars.0.get(var) {
-                    Some(v) => Ok((input, (coeff.into(), v))),
+                    Some(v) => Ok((input, (coeff, v))),
                     None => Err(nom::Err::Failure(
                         ExpressionCustomParseError::UndefinedVariable(var),
                     )),
@@ -166,7 +199,7 @@ impl<'v> Expression<'v> {
 }
 
 /// Parse a float as part of an expression, allowing for whitespace between `-` and the number.
-fn parse_float(input: &str) -> IResult<&str, f32> {
+fn parse_float(input: &str, syntax: NumberSyntax) -> IResult<&str, Frac> {
     let (input, _) = multispace0(input)?;
     let (input, negative) =
         match pair(tag::<&str, &str, nom::error::Error<&str>>("-"), multispace0)(input) {
@@ -174,8 +207,35 @@ fn parse_float(input: &str) -> IResult<&str, f32> {
             Err(_) => (input, false),
         };
     let (input, _) = multispace0(input)?;
-    let (input, num) = parse_float_no_e(input)?;
-    Ok((input, if negative { num * -1. } else { num }))
+    let (input, num) = parse_float_with_syntax(input, syntax)?;
+    Ok((input, if negative { -num } else { num }))
+}
+
+/// Parse the optional denominator of a coefficient like `1/3`, and divide the numerator by it.
+/// The denominator must be a non-zero whole number, so the result is exact.
+fn parse_denominator(input: &str, numerator: Frac) -> ParseResult<'_, Frac> {
+    use nom::{
+        character::complete::digit1,
+        sequence::{preceded, tuple},
+    };
+
+    match preceded(
+        tuple((
+            multispace0,
+            char::<&str, nom::error::Error<&str>>('/'),
+            multispace0,
+        )),
+        digit1,
+    )(input)
+    {
+        Ok((rest, denominator)) => match denominator.parse::<u64>() {
+            Ok(n) if n != 0 => Ok((rest, numerator / Frac::from(n))),
+            _ => Err(nom::Err::Failure(
+                ExpressionCustomParseError::InvalidDenominator(denominator),
+            )),
+        },
+        Err(_) => Ok((input, numerator)),
+    }
 }
 
 /// A custom version of [`nom::multi_separated_list1()`] which allows instances where the `sep`
@@ -287,6 +347,30 @@ mod tests {
             ))
         );
 
+        assert_eq!(
+            Expression::nom_parse("+3a - 2b", &variables),
+            Ok((
+                "",
+                Expression(vec![(3.into(), "a"), (-Frac::new(2u32, 1u32), "b")])
+            ))
+        );
+        assert_eq!(
+            Expression::nom_parse(" + a", &variables),
+            Ok(("", Expression(vec![(1.into(), "a")])))
+        );
+
+        assert_eq!(
+            Expression::nom_parse("1/3a + 2 / 7 b - 1/2c", &variables),
+            Ok((
+                "",
+                Expression(vec![
+                    (Frac::new(1u32, 3u32), "a"),
+                    (Frac::new(2u32, 7u32), "b"),
+                    (-Frac::new(1u32, 2u32), "c")
+                ])
+            ))
+        );
+
         assert!(
             matches!(
                 Expression::nom_parse("", &variables),
@@ -312,6 +396,15 @@ mod tests {
             ),
             "Double + symbol"
         );
+        assert!(
+            matches!(
+                Expression::nom_parse("++a", &variables),
+                Err(nom::Err::Failure(ExpressionCustomParseError::BadPunctuation(
+                    punc
+                ))) if punc == "+"
+            ),
+            "Double leading + symbol"
+        );
         assert!(
             matches!(
                 Expression::nom_parse("a*b", &variables),
@@ -366,5 +459,14 @@ mod tests {
             ),
             "Undefined variable z"
         );
+        assert!(
+            matches!(
+                Expression::nom_parse("3/0 a", &variables),
+                Err(nom::Err::Failure(
+                    ExpressionCustomParseError::InvalidDenominator("0")
+                ))
+            ),
+            "Dividing by 0 in a coefficient"
+        );
     }
 }
@@ -6,9 +6,11 @@ pub mod constraint;
 pub mod expression;
 pub mod system;
 
-use self::{comparison::Comparison, expression::Expression};
+use self::{comparison::Comparison, config::NumberSyntax, expression::Expression};
+use crate::Frac;
 use color_eyre::{Report, Result};
-use inquire::Select;
+use fraction::Zero;
+use inquire::{Select, Text};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::{collections::HashSet, fmt};
@@ -44,30 +46,73 @@ fn validate_variable(var: &str) -> Result<&str> {
     }
 }
 
+/// Split an optional name off the front of an objective expression like `C = 3x + 2y`, returning
+/// the name and the rest of the expression. The name must be a valid variable name, but it can't
+/// be one of the given variables.
+fn split_objective_name<'i>(
+    input: &'i str,
+    variables: &Variables,
+) -> Result<(Option<&'i str>, &'i str)> {
+    let Some((name, expression)) = input.split_once('=') else {
+        return Ok((None, input));
+    };
+
+    let name = validate_variable(name)
+        .map_err(|_| Report::msg(format!("Invalid objective function name {:?}", name.trim())))?;
+    if variables.0.contains(name) {
+        return Err(Report::msg(format!(
+            "Objective function name {name:?} is already a variable"
+        )));
+    }
+
+    Ok((Some(name), expression.trim()))
+}
+
+/// Whether to maximise or minimise an objective function.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ObjectiveSense {
+    /// Minimise the objective function.
+    Minimise,
+
+    /// Maximise the objective function.
+    Maximise,
+}
+
 /// The objective function for the [`LinProgSystem`].
+///
+/// The [`Frac`] is a constant offset added to the expression. It doesn't change where the optimum
+/// is, only the value of the objective function there.
 #[derive(Clone, Debug, PartialEq)]
 pub enum ObjectiveFunction<'v> {
     /// Minimise the expression.
-    Minimise(Expression<'v>),
+    Minimise(Expression<'v>, Frac),
 
     /// Maximise the expression.
-    Maximise(Expression<'v>),
+    Maximise(Expression<'v>, Frac),
 }
 
 impl<'v> fmt::Display for ObjectiveFunction<'v> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (word, expression) = match self {
-            ObjectiveFunction::Minimise(exp) => ("Minimise", exp),
-            ObjectiveFunction::Maximise(exp) => ("Maximise", exp),
+        let (word, expression, constant) = match self {
+            ObjectiveFunction::Minimise(exp, constant) => ("Minimise", exp, constant),
+            ObjectiveFunction::Maximise(exp, constant) => ("Maximise", exp, constant),
         };
-        write!(f, "{word} {expression}")
+        write!(f, "{word} {expression}")?;
+        if !constant.is_zero() {
+            write!(f, " + {constant}")?;
+        }
+        Ok(())
     }
 }
 
 impl<'v> ObjectiveFunction<'v> {
-    /// Build an objective function from user input using `inquire`.
+    /// Build an objective function from user input using `inquire`. The expression can start with
+    /// a name for the objective function, like `C = 3x + 2y`, which is returned alongside it.
     #[instrument]
-    pub fn build_from_user(variables: &'v Variables) -> Result<Self> {
+    pub fn build_from_user(
+        variables: &'v Variables,
+        syntax: NumberSyntax,
+    ) -> Result<(Option<String>, Self)> {
         let min_max = Select::new(
             "Please select a type of objective function:",
             vec!["Maximise", "Minimise"],
@@ -75,62 +120,296 @@ impl<'v> ObjectiveFunction<'v> {
         .prompt()
         .expect("inquire::Select should not fail");
 
-        let expression = Expression::build_from_user(
-            &format!("Please enter the expression to {}:", min_max.to_lowercase()),
-            variables,
-        )?;
+        let mut input = Text::new(&format!(
+            "Please enter the expression to {}:",
+            min_max.to_lowercase()
+        ))
+        .with_help_message("You can name the objective function like C = 3x + 2y")
+        .prompt()?;
+
+        let (name, (expression, constant)) = loop {
+            let parsed = split_objective_name(&input, variables).and_then(|(name, expression)| {
+                Ok((
+                    name.map(ToString::to_string),
+                    parse_expression_with_constant(expression, variables, syntax)?,
+                ))
+            });
+            match parsed {
+                Ok(parsed) => break parsed,
+                Err(e) => {
+                    input = Text::new("Please try again:")
+                        .with_initial_value(&input)
+                        .with_help_message(&format!("Error: {e}"))
+                        .prompt()?;
+                }
+            };
+        };
 
-        Ok(match min_max {
-            "Minimise" => Self::Minimise(expression),
-            "Maximise" => Self::Maximise(expression),
+        let objective_function = match min_max {
+            "Minimise" => Self::Minimise(expression, constant),
+            "Maximise" => Self::Maximise(expression, constant),
             _ => unreachable!("Selected text should only be 'Minimise' or 'Maximise'"),
-        })
+        };
+        Ok((name, objective_function))
+    }
+
+    /// Parse an objective function from a string like `maximise 3x + 2y + 50`. The first word can
+    /// be `maximise` or `minimise` (or spelled with a `z`), in any case, and the expression can end
+    /// with a constant term. The objective function can also be named, like `minimise C = 3x + 2y`,
+    /// but the name is ignored. See [`parse_named_with_syntax`](Self::parse_named_with_syntax).
+    pub fn parse(input: &str, variables: &'v Variables) -> Result<Self> {
+        Self::parse_with_syntax(input, variables, NumberSyntax::Plain)
+    }
+
+    /// Parse an objective function like [`parse`](Self::parse), allowing the given syntax for
+    /// numbers.
+    pub fn parse_with_syntax(
+        input: &str,
+        variables: &'v Variables,
+        syntax: NumberSyntax,
+    ) -> Result<Self> {
+        Self::parse_named_with_syntax(input, variables, syntax).map(|(_, obj_func)| obj_func)
+    }
+
+    /// Parse an objective function like [`parse_with_syntax`](Self::parse_with_syntax), also
+    /// returning its name if it has one, like `C` in `minimise C = 3x + 2y`. The name must be a
+    /// valid variable name, but it isn't added to the variables.
+    pub fn parse_named_with_syntax<'i>(
+        input: &'i str,
+        variables: &'v Variables,
+        syntax: NumberSyntax,
+    ) -> Result<(Option<&'i str>, Self)> {
+        let input = input.trim();
+        let (word, expression) = input.split_once(char::is_whitespace).ok_or_else(|| {
+            Report::msg(format!(
+                "Objective function {input:?} must be maximise or minimise followed by an expression"
+            ))
+        })?;
+        let (name, expression) = split_objective_name(expression.trim(), variables)?;
+        let (expression, constant) = parse_expression_with_constant(expression, variables, syntax)?;
+
+        match word.to_lowercase().as_str() {
+            "maximise" | "maximize" => Ok((name, Self::Maximise(expression, constant))),
+            "minimise" | "minimize" => Ok((name, Self::Minimise(expression, constant))),
+            _ => Err(Report::msg(format!(
+                "Expected maximise or minimise, not {word:?}"
+            ))),
+        }
+    }
+
+    /// Combine two objectives into the single objective `w1·f1 + w2·f2` with the given sense, so
+    /// that a problem with multiple criteria can be solved by optimising the weighted sum.
+    pub fn weighted_combination(
+        sense: ObjectiveSense,
+        (w1, f1): (Frac, Expression<'v>),
+        (w2, f2): (Frac, Expression<'v>),
+    ) -> Self {
+        let expression = f1 * w1 + f2 * w2;
+        match sense {
+            ObjectiveSense::Minimise => Self::Minimise(expression, Frac::zero()),
+            ObjectiveSense::Maximise => Self::Maximise(expression, Frac::zero()),
+        }
     }
 
     /// Simplify the objective function.
     pub fn simplify(self) -> Self {
         match self {
-            Self::Minimise(exp) => Self::Minimise(exp.simplify()),
-            Self::Maximise(exp) => Self::Maximise(exp.simplify()),
+            Self::Minimise(exp, constant) => Self::Minimise(exp.simplify(), constant),
+            Self::Maximise(exp, constant) => Self::Maximise(exp.simplify(), constant),
+        }
+    }
+
+    /// Rebind the objective function to reference the same variables in a different
+    /// [`Variables`] instance. See [`Expression::rebind`].
+    pub fn rebind<'w>(&self, variables: &'w Variables) -> ObjectiveFunction<'w> {
+        match self {
+            Self::Minimise(exp, constant) => {
+                ObjectiveFunction::Minimise(exp.rebind(variables), *constant)
+            }
+            Self::Maximise(exp, constant) => {
+                ObjectiveFunction::Maximise(exp.rebind(variables), *constant)
+            }
+        }
+    }
+
+    /// Return whether the objective function is maximised or minimised.
+    pub fn sense(&self) -> ObjectiveSense {
+        match self {
+            Self::Minimise(_, _) => ObjectiveSense::Minimise,
+            Self::Maximise(_, _) => ObjectiveSense::Maximise,
         }
     }
 
     /// Return a reference to the inner expression of the objective function.
     pub fn expression(&self) -> &Expression<'v> {
         match self {
-            Self::Minimise(exp) | Self::Maximise(exp) => exp,
+            Self::Minimise(exp, _) | Self::Maximise(exp, _) => exp,
+        }
+    }
+
+    /// Return the constant offset of the objective function.
+    pub fn constant(&self) -> Frac {
+        match self {
+            Self::Minimise(_, constant) | Self::Maximise(_, constant) => *constant,
         }
     }
 }
 
-/// Parse a float without the `2.34e12` type of syntax. This function is adapted from `nom`'s
-/// original float parsing system.
-fn parse_float_no_e(input: &str) -> nom::IResult<&str, f32> {
+/// Parse an expression which may end with a constant term, like `3x + 2y + 50`. If there's no
+/// constant term, then the constant is 0.
+fn parse_expression_with_constant<'v>(
+    input: &str,
+    variables: &'v Variables,
+    syntax: NumberSyntax,
+) -> Result<(Expression<'v>, Frac)> {
     use nom::{
-        branch::alt,
-        bytes::complete::tag,
-        character::complete::{char, digit1},
-        combinator::{map, opt, recognize},
-        sequence::{pair, tuple},
-        ParseTo,
+        character::complete::{multispace0, one_of},
+        combinator::eof,
+        sequence::{delimited, pair},
+    };
+
+    let (rest, expression) = Expression::nom_parse_with_syntax(input, variables, syntax)
+        .map_err(|e| Report::msg(e.to_string()))?;
+    if rest.trim().is_empty() {
+        return Ok((expression, Frac::zero()));
+    }
+
+    let (_, ((sign, constant), _)) = pair(
+        pair(delimited(multispace0, one_of("+-"), multispace0), |input| {
+            parse_frac(input, syntax)
+        }),
+        pair(multispace0, eof),
+    )(rest)
+    .map_err(|_| Report::msg(format!("Unable to parse {:?}", rest.trim())))?;
+
+    Ok((expression, if sign == '-' { -constant } else { constant }))
+}
+
+/// The parts of a decimal number: whether it's negative, the digits before the decimal point,
+/// and the digits after it.
+type DecimalParts<'i> = (bool, &'i str, &'i str);
+
+/// Parse the parts of a decimal number like `-2.34`. Either group of digits can be empty, so this
+/// also accepts an empty string or a lone sign.
+fn parse_decimal_parts(input: &str) -> nom::IResult<&str, DecimalParts<'_>> {
+    use nom::{
+        character::complete::{char, digit0, digit1, one_of},
+        combinator::opt,
+        sequence::preceded,
+    };
+
+    let (input, sign) = opt(one_of("+-"))(input)?;
+    let (input, int_digits) = digit0(input)?;
+
+    // A decimal point needs digits on at least one side of it
+    let (input, frac_digits) = if int_digits.is_empty() {
+        opt(preceded(char('.'), digit1))(input)?
+    } else {
+        opt(preceded(char('.'), digit0))(input)?
+    };
+
+    Ok((
+        input,
+        (sign == Some('-'), int_digits, frac_digits.unwrap_or("")),
+    ))
+}
+
+/// Build the exact fraction for the decimal number with the given parts, multiplied by
+/// `10^exponent`. This returns `None` if the number is too big to represent.
+fn decimal_to_frac(
+    (negative, int_digits, frac_digits): DecimalParts,
+    exponent: i32,
+) -> Option<Frac> {
+    let digits = format!("{int_digits}{frac_digits}");
+    let digits: u64 = if digits.is_empty() {
+        0
+    } else {
+        digits.parse().ok()?
+    };
+
+    // The number is `digits / 10^scale`
+    let scale = i32::try_from(frac_digits.len()).ok()? - exponent;
+    let magnitude = if scale >= 0 {
+        Frac::new(digits, 10u64.checked_pow(scale.unsigned_abs())?)
+    } else {
+        Frac::from(digits.checked_mul(10u64.checked_pow(scale.unsigned_abs())?)?)
     };
 
-    let (input, mut num) = recognize(tuple((
-        opt(alt((char('+'), char('-')))),
-        alt((
-            map(tuple((digit1, opt(pair(char('.'), opt(digit1))))), |_| ()),
-            map(tuple((char('.'), digit1)), |_| ()),
-            map(tag(""), |_| ()),
-        )),
-    )))(input)?;
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+/// Parse a number without the `2.34e12` type of syntax, exactly into a [`Frac`]. A missing
+/// number, like in the term `-x`, is read as 1.
+fn parse_float_no_e(input: &str) -> nom::IResult<&str, Frac> {
+    use nom::error::{Error, ErrorKind};
+
+    let (rest, parts @ (negative, int_digits, frac_digits)) = parse_decimal_parts(input)?;
+    if int_digits.is_empty() && frac_digits.is_empty() {
+        let one = Frac::from(1);
+        return Ok((rest, if negative { -one } else { one }));
+    }
 
-    if num == "-" {
-        num = "-1";
+    match decimal_to_frac(parts, 0) {
+        Some(num) => Ok((rest, num)),
+        None => Err(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge))),
     }
+}
+
+/// Parse a number which may use the `2.34e12` type of syntax, exactly into a [`Frac`]. The
+/// exponent is only parsed if there are digits before the `e` and after it, so a variable called
+/// `e` still works in terms like `2e` and `e2`.
+fn parse_float_with_e(input: &str) -> nom::IResult<&str, Frac> {
+    use nom::{
+        character::complete::{digit1, one_of},
+        combinator::{opt, recognize},
+        error::{Error, ErrorKind},
+        sequence::{pair, preceded},
+    };
+
+    let (rest, parts @ (_, int_digits, frac_digits)) = parse_decimal_parts(input)?;
+    if int_digits.is_empty() && frac_digits.is_empty() {
+        return parse_float_no_e(input);
+    }
+
+    let (rest, exponent) =
+        match preceded(one_of("eE"), recognize(pair(opt(one_of("+-")), digit1)))(rest) {
+            Ok((rest, exponent)) => (rest, exponent.parse().ok()),
+            Err(nom::Err::Error(_)) => (rest, Some(0)),
+            Err(e) => return Err(e),
+        };
 
-    match num.parse_to() {
-        Some(f) => Ok((input, f)),
-        None => Ok((input, 1.)),
+    match exponent.and_then(|exponent| decimal_to_frac(parts, exponent)) {
+        Some(num) => Ok((rest, num)),
+        None => Err(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge))),
+    }
+}
+
+/// Parse a number with [`parse_float_no_e`] or [`parse_float_with_e`], depending on the syntax.
+fn parse_float_with_syntax(input: &str, syntax: NumberSyntax) -> nom::IResult<&str, Frac> {
+    match syntax {
+        NumberSyntax::Plain => parse_float_no_e(input),
+        NumberSyntax::Scientific => parse_float_with_e(input),
+    }
+}
+
+/// Parse a number which may be written as an exact fraction like `1/3`. The numerator is parsed
+/// with [`parse_float_with_syntax`] and the optional denominator must be a non-zero integer.
+fn parse_frac(input: &str, syntax: NumberSyntax) -> nom::IResult<&str, Frac> {
+    use nom::{
+        character::complete::{char, digit1, multispace0},
+        error::{Error, ErrorKind},
+        sequence::{preceded, tuple},
+    };
+
+    let (input, numerator) = parse_float_with_syntax(input, syntax)?;
+
+    match preceded(tuple((multispace0, char('/'), multispace0)), digit1)(input) {
+        Ok((rest, denominator)) => match denominator.parse::<u64>() {
+            Ok(denominator) if denominator != 0 => Ok((rest, numerator / Frac::from(denominator))),
+            _ => Err(nom::Err::Failure(Error::new(input, ErrorKind::Verify))),
+        },
+        Err(nom::Err::Error(_)) => Ok((input, numerator)),
+        Err(e) => Err(e),
     }
 }
 
@@ -152,20 +431,189 @@ mod tests {
         assert!(validate_variable("@").is_err());
     }
 
+    #[test]
+    fn objective_function_parse_test() {
+        let variables = Variables::from(["x", "y"]);
+
+        let obj_func = ObjectiveFunction::parse("maximise 3x + 2y + 50", &variables).unwrap();
+        assert_eq!(
+            obj_func,
+            ObjectiveFunction::Maximise(
+                Expression(vec![(3.into(), "x"), (2.into(), "y")]),
+                50.into()
+            )
+        );
+        assert_eq!(obj_func.to_string(), "Maximise 3x + 2y + 50");
+
+        let obj_func = ObjectiveFunction::parse("Minimize x - y - 0.5", &variables).unwrap();
+        assert_eq!(
+            obj_func,
+            ObjectiveFunction::Minimise(
+                Expression(vec![(1.into(), "x"), (-Frac::from(1), "y")]),
+                -Frac::new(1u32, 2u32)
+            )
+        );
+        assert_eq!(obj_func.constant(), -Frac::new(1u32, 2u32));
+
+        let obj_func = ObjectiveFunction::parse("maximise 3x + 2y", &variables).unwrap();
+        assert_eq!(obj_func.constant(), Frac::zero());
+        assert_eq!(obj_func.to_string(), "Maximise 3x + 2y");
+
+        assert!(ObjectiveFunction::parse("maximise 3x + 50 + 2y", &variables).is_err());
+        assert!(ObjectiveFunction::parse("maximise 3x + 2y + 50 + 1", &variables).is_err());
+        assert!(ObjectiveFunction::parse("maximise 3x + 2z", &variables).is_err());
+    }
+
+    #[test]
+    fn objective_function_parse_named_test() {
+        let variables = Variables::from(["x", "y"]);
+        let parse = |input| {
+            ObjectiveFunction::parse_named_with_syntax(input, &variables, NumberSyntax::Plain)
+        };
+
+        let (name, obj_func) = parse("minimise cost = 3x + 2y").unwrap();
+        assert_eq!(name, Some("cost"));
+        assert_eq!(
+            obj_func,
+            ObjectiveFunction::Minimise(
+                Expression(vec![(3.into(), "x"), (2.into(), "y")]),
+                Frac::zero()
+            )
+        );
+
+        let (name, obj_func) = parse("Maximise  C=x - y + 5").unwrap();
+        assert_eq!(name, Some("C"));
+        assert_eq!(obj_func.to_string(), "Maximise 1x + -1y + 5");
+        assert!(!variables.0.contains("C"));
+
+        let (name, _) = parse("maximise 3x + 2y").unwrap();
+        assert_eq!(name, None);
+
+        assert_eq!(
+            ObjectiveFunction::parse("minimise C = 3x + 2y", &variables).unwrap(),
+            parse("minimise 3x + 2y").unwrap().1
+        );
+
+        assert!(parse("minimise 2C = 3x + 2y").is_err());
+        assert!(parse("minimise total cost = 3x + 2y").is_err());
+        assert!(parse("minimise x = 3x + 2y").is_err());
+        assert!(parse("minimise C = 3x + 2y = 4").is_err());
+        assert!(parse("minimise = 3x + 2y").is_err());
+    }
+
+    #[test]
+    fn objective_function_weighted_combination_test() {
+        let variables = Variables::from(["x", "y", "z"]);
+        let f1 = Expression::parse("3x + 2y", &variables).unwrap();
+        let f2 = Expression::parse("x - 4y + z", &variables).unwrap();
+
+        assert_eq!(
+            ObjectiveFunction::weighted_combination(
+                ObjectiveSense::Maximise,
+                (Frac::new(1u32, 2u32), f1.clone()),
+                (2.into(), f2.clone())
+            ),
+            ObjectiveFunction::Maximise(
+                Expression(vec![
+                    (Frac::new(7u32, 2u32), "x"),
+                    (-Frac::new(7u32, 1u32), "y"),
+                    (2.into(), "z")
+                ]),
+                Frac::zero()
+            )
+        );
+        assert_eq!(
+            ObjectiveFunction::weighted_combination(
+                ObjectiveSense::Minimise,
+                (1.into(), f1),
+                (Frac::zero(), f2)
+            ),
+            ObjectiveFunction::Minimise(
+                Expression(vec![(3.into(), "x"), (2.into(), "y")]),
+                Frac::zero()
+            )
+        );
+    }
+
     #[test]
     fn parse_float_no_e_test() {
-        assert_eq!(parse_float_no_e("1"), Ok(("", 1.)));
-        assert_eq!(parse_float_no_e("1.2 "), Ok((" ", 1.2)));
-        assert_eq!(parse_float_no_e(".3d"), Ok(("d", 0.3)));
-        assert_eq!(parse_float_no_e("-1"), Ok(("", -1.)));
-        assert_eq!(parse_float_no_e("-2.3-"), Ok(("-", -2.3)));
-        assert_eq!(parse_float_no_e("-.4"), Ok(("", -0.4)));
-        assert_eq!(parse_float_no_e("-0.4"), Ok(("", -0.4)));
+        assert_eq!(parse_float_no_e("1"), Ok(("", 1.into())));
+        assert_eq!(parse_float_no_e("1.2 "), Ok((" ", Frac::new(6u32, 5u32))));
+        assert_eq!(parse_float_no_e(".3d"), Ok(("d", Frac::new(3u32, 10u32))));
+        assert_eq!(parse_float_no_e("-1"), Ok(("", (-1).into())));
+        assert_eq!(
+            parse_float_no_e("-2.3-"),
+            Ok(("-", -Frac::new(23u32, 10u32)))
+        );
+        assert_eq!(parse_float_no_e("-.4"), Ok(("", -Frac::new(2u32, 5u32))));
+        assert_eq!(parse_float_no_e("-0.4"), Ok(("", -Frac::new(2u32, 5u32))));
         assert_eq!(
             parse_float_no_e("16 other stuff"),
-            Ok((" other stuff", 16.))
+            Ok((" other stuff", 16.into()))
+        );
+        assert_eq!(parse_float_no_e("-"), Ok(("", (-1).into())));
+        assert_eq!(parse_float_no_e("b"), Ok(("b", 1.into())));
+
+        // Decimals are exact, rather than the nearest binary fraction
+        assert_eq!(parse_float_no_e("0.1"), Ok(("", Frac::new(1u32, 10u32))));
+        assert_eq!(
+            parse_float_no_e("123456.789"),
+            Ok(("", Frac::new(123_456_789u32, 1000u32)))
+        );
+        assert!(matches!(
+            parse_float_no_e("123456789012345678901234567890"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn parse_float_with_e_test() {
+        assert_eq!(
+            parse_float_with_e("2.34e12"),
+            Ok(("", 2_340_000_000_000u64.into()))
+        );
+        assert_eq!(
+            parse_float_with_e("1.5e-3x"),
+            Ok(("x", Frac::new(3u32, 2000u32)))
+        );
+        assert_eq!(parse_float_with_e("-2E+2 "), Ok((" ", (-200).into())));
+        assert_eq!(parse_float_with_e(".5e1"), Ok(("", 5.into())));
+        assert_eq!(parse_float_with_e("1.2"), Ok(("", Frac::new(6u32, 5u32))));
+
+        // A variable called e still works when there isn't an exponent
+        assert_eq!(parse_float_with_e("2e"), Ok(("e", 2.into())));
+        assert_eq!(parse_float_with_e("2e + 3"), Ok(("e + 3", 2.into())));
+        assert_eq!(parse_float_with_e("2e-"), Ok(("e-", 2.into())));
+        assert_eq!(parse_float_with_e("e2"), Ok(("e2", 1.into())));
+        assert_eq!(parse_float_with_e("-e2"), Ok(("e2", (-1).into())));
+    }
+
+    #[test]
+    fn parse_frac_test() {
+        assert_eq!(
+            parse_frac("1/3", NumberSyntax::Plain),
+            Ok(("", Frac::new(1u32, 3u32)))
+        );
+        assert_eq!(
+            parse_frac("-2 / 6 ", NumberSyntax::Plain),
+            Ok((" ", -Frac::new(1u32, 3u32)))
+        );
+        assert_eq!(
+            parse_frac("1.5/2", NumberSyntax::Plain),
+            Ok(("", Frac::new(3u32, 4u32)))
+        );
+        assert_eq!(parse_frac("12", NumberSyntax::Plain), Ok(("", 12.into())));
+        assert_eq!(
+            parse_frac("12 /x", NumberSyntax::Plain),
+            Ok((" /x", 12.into()))
+        );
+        assert!(matches!(
+            parse_frac("3/0", NumberSyntax::Plain),
+            Err(nom::Err::Failure(_))
+        ));
+        assert_eq!(
+            parse_frac("1e3/4", NumberSyntax::Scientific),
+            Ok(("", 250.into()))
         );
-        assert_eq!(parse_float_no_e("-"), Ok(("", -1.)));
-        assert_eq!(parse_float_no_e("b"), Ok(("b", 1.)));
     }
 }
@@ -1,9 +1,12 @@
 //! This module handles linear programming systems. See [`LinProgSystem`].
 
 use super::{
-    config::Config, constraint::Constraint, validate_variable, ObjectiveFunction, Variables,
+    comparison::Comparison, config::Config, constraint::Constraint, expression::Expression,
+    validate_variable, ObjectiveFunction, ObjectiveSense, Variables,
 };
-use color_eyre::Result;
+use crate::Frac;
+use color_eyre::{Report, Result};
+use fraction::Zero;
 use inquire::{InquireError, Select, Text};
 use ouroboros::self_referencing;
 use std::{collections::HashSet, fmt};
@@ -41,9 +44,356 @@ impl fmt::Debug for LinProgSystem {
 }
 
 impl LinProgSystem {
+    /// Return the number of variables in the system.
+    pub fn num_variables(&self) -> usize {
+        self.borrow_variables().0.len()
+    }
+
+    /// Return the number of constraints in the system.
+    pub fn num_constraints(&self) -> usize {
+        self.with_constraints(|cons| cons.len())
+    }
+
+    /// Return whether the objective function of the system is maximised or minimised.
+    pub fn objective_sense(&self) -> ObjectiveSense {
+        self.with_objective_function(|obj_func| obj_func.sense())
+    }
+
+    /// Build a new system with the same variables as this one but with the given config.
+    ///
+    /// The objective function and constraints of the new system are produced by the given
+    /// closures, which get passed the new variables and this system's objective function and
+    /// constraints, rebound to the new variables.
+    pub fn rebuild<O, C>(&self, config: Config, objective_function: O, constraints: C) -> Self
+    where
+        O: for<'a> FnOnce(&'a Variables, ObjectiveFunction<'a>) -> ObjectiveFunction<'a>,
+        C: for<'a> FnOnce(&'a Variables, Vec<Constraint<'a>>) -> Vec<Constraint<'a>>,
+    {
+        LinProgSystemBuilder {
+            variables: self.borrow_variables().clone(),
+            config,
+            objective_function_builder: |variables: &Variables| {
+                let obj_func = self.with_objective_function(|obj_func| obj_func.rebind(variables));
+                objective_function(variables, obj_func)
+            },
+            constraints_builder: |variables: &Variables| {
+                let cons = self.with_constraints(|cons| {
+                    cons.iter().map(|con| con.rebind(variables)).collect()
+                });
+                constraints(variables, cons)
+            },
+        }
+        .build()
+    }
+
+    /// Build a copy of this system with the constant on the right of the constraint at the given
+    /// index replaced by `constant`.
+    ///
+    /// This method will panic if there's no constraint at `constraint_idx`.
+    pub fn with_constraint_constant(&self, constraint_idx: usize, constant: Frac) -> Self {
+        assert!(
+            constraint_idx < self.num_constraints(),
+            "Constraint index {constraint_idx} is out of range for {} constraints",
+            self.num_constraints()
+        );
+
+        self.rebuild(
+            self.borrow_config().clone(),
+            |_, obj_func| obj_func,
+            |_, mut cons| {
+                cons[constraint_idx].constant = constant;
+                cons
+            },
+        )
+    }
+
+    /// Build a copy of this system with the given constraint added after the existing ones.
+    pub fn with_extra_constraint(&self, constraint: &Constraint) -> Self {
+        self.rebuild(
+            self.borrow_config().clone(),
+            |_, obj_func| obj_func,
+            |variables, mut cons| {
+                cons.push(constraint.rebind(variables));
+                cons
+            },
+        )
+    }
+
+    /// Build a system from its variables and an objective function and constraints which have
+    /// already been built, with the default [`Config`]. Use [`rebuild`](Self::rebuild) to change
+    /// the config.
+    ///
+    /// The objective function and constraints can borrow their variables from anywhere, since
+    /// they get rebound to the variables of the new system, but every variable that they use
+    /// must be in `variables`.
+    ///
+    /// This isn't called `new` because `ouroboros` already generates a `new` constructor, which
+    /// takes closures like [`LinProgSystemBuilder`].
+    #[instrument(skip(objective_function, constraints))]
+    pub fn from_parts(
+        variables: Vec<&str>,
+        objective_function: ObjectiveFunction<'_>,
+        constraints: Vec<Constraint<'_>>,
+    ) -> Result<Self> {
+        let variables = Variables(
+            variables
+                .into_iter()
+                .map(|var| validate_variable(var).map(ToString::to_string))
+                .collect::<Result<HashSet<String>>>()?,
+        );
+
+        // Rebinding panics on unknown variables, so we check them first
+        if let Some(var) = objective_function
+            .expression()
+            .0
+            .iter()
+            .chain(constraints.iter().flat_map(|con| &con.var_expression.0))
+            .map(|&(_, var)| var)
+            .find(|&var| !variables.0.contains(var))
+        {
+            return Err(Report::msg(format!("Unknown variable {var:?}")));
+        }
+
+        Ok(LinProgSystemBuilder {
+            variables,
+            config: Config::default(),
+            objective_function_builder: |variables: &Variables| {
+                objective_function.rebind(variables)
+            },
+            constraints_builder: |variables: &Variables| {
+                constraints
+                    .iter()
+                    .map(|con| con.rebind(variables))
+                    .collect()
+            },
+        }
+        .build())
+    }
+
+    /// Build a system from a problem in matrix form, to optimise `c·x` subject to `A x ? b`, where
+    /// each `?` is the comparison for that row of `a`. There's no parsing involved, so this is the
+    /// most direct way to build a system from code. The system uses the default [`Config`].
+    ///
+    /// The variables are named `x0`, `x1`, and so on, one for each entry in `c`. Every row of `a`
+    /// must have the same length as `c`, and `a`, `b`, and `comparisons` must all have the same
+    /// length, otherwise we return an error.
+    ///
+    /// To build and solve the system in one go, use
+    /// [`solve_matrix`](crate::simplex::solve_matrix).
+    #[instrument]
+    pub fn from_matrix(
+        c: &[Frac],
+        a: &[Vec<Frac>],
+        b: &[Frac],
+        comparisons: &[Comparison],
+        sense: ObjectiveSense,
+    ) -> Result<Self> {
+        if c.is_empty() {
+            return Err(Report::msg(
+                "The objective function must have at least one variable",
+            ));
+        }
+        if b.len() != a.len() || comparisons.len() != a.len() {
+            return Err(Report::msg(format!(
+                "Expected one constant and one comparison for each of the {} rows of the matrix, \
+                but got {} constants and {} comparisons",
+                a.len(),
+                b.len(),
+                comparisons.len()
+            )));
+        }
+        if let Some((idx, row)) = a.iter().enumerate().find(|(_, row)| row.len() != c.len()) {
+            return Err(Report::msg(format!(
+                "Row {} of the matrix has {} entries, but there are {} variables",
+                idx + 1,
+                row.len(),
+                c.len()
+            )));
+        }
+
+        let names: Vec<String> = (0..c.len()).map(|idx| format!("x{idx}")).collect();
+        let expression = |coeffs: &[Frac]| {
+            Expression(
+                coeffs
+                    .iter()
+                    .zip(&names)
+                    .map(|(&coeff, name)| (coeff, name.as_str()))
+                    .collect(),
+            )
+        };
+
+        let objective_function = match sense {
+            ObjectiveSense::Minimise => ObjectiveFunction::Minimise(expression(c), Frac::zero()),
+            ObjectiveSense::Maximise => ObjectiveFunction::Maximise(expression(c), Frac::zero()),
+        };
+        let constraints = a
+            .iter()
+            .zip(b)
+            .zip(comparisons)
+            .map(|((row, &constant), &comparison)| Constraint {
+                var_expression: expression(row),
+                comparison,
+                constant,
+            })
+            .collect();
+
+        Self::from_parts(
+            names.iter().map(String::as_str).collect(),
+            objective_function,
+            constraints,
+        )
+    }
+
+    /// Build a system from text, so that problems can be solved non-interactively.
+    ///
+    /// The first line lists the variables, separated by spaces. The second line is `maximise` or
+    /// `minimise` followed by the objective function. Every line after that has one or more
+    /// constraints, separated by `;` or `,`. Blank lines are ignored, and errors say which line failed.
+    /// A line saying `bounds` starts a section where each line has double-sided bounds like
+    /// `0 <= x <= 10`, and a line saying `constraints` goes back to normal constraints.
+    /// The objective function can be named, like `minimise C = 3x + 2y`, and the name is stored
+    /// in the config.
+    ///
+    /// A line saying `config` starts a section where each line sets an option, which lets a file
+    /// choose anything that the interactive flow can. The section ends at a `bounds` or
+    /// `constraints` line, and it applies to the whole system, wherever it is. Each line is either
+    /// a flag to turn on, or a name and a value separated by `=`:
+    ///
+    /// - `method = two_stage` or `big_m`
+    /// - `pivot_rule = most_negative`, `prefer_original`, `lexicographic`, or `random`
+    /// - `seed = 42`, to seed the `random` pivot rule
+    /// - `tie_break = first` or `last`
+    /// - `integer = all`, or the names of the integer variables, separated by spaces
+    /// - `non_positive = ` the names of the non-positive variables, separated by spaces
+    /// - `naming = symbolic`, `numbered`, or `subscripted`
+    /// - `numbers = plain` or `scientific`
+    /// - `max_iterations = 1000`
+    /// - the flags `standard_form`, `clear_denominators`, `report_infeasible_subsystem`,
+    ///   `require_nonempty_objective`, `integer_coefficients_only`, `keep_artificial_columns`,
+    ///   `anti_cycling`, `continued_fractions`, `right_align_numbers`, and `ascii_output`
+    ///
+    /// Anything not set in a `config` section comes from the default [`Config`]. See
+    /// [`Config::set_option`].
+    pub fn build_from_str(input: &str) -> Result<Self> {
+        Self::build_from_str_with_config(input, Config::default())
+    }
+
+    /// Build a system from text like [`build_from_str`](Self::build_from_str), but starting from
+    /// the given config, which any `config` section in the text overrides. The numbers are parsed
+    /// with the [`NumberSyntax`](super::config::NumberSyntax) of the final config.
+    #[instrument(skip(input))]
+    pub fn build_from_str_with_config(input: &str, mut config: Config) -> Result<Self> {
+        let line_error = |line_num: usize, e: Report| Report::msg(format!("Line {line_num}: {e}"));
+
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let (variables_line_num, variables_line) = lines
+            .next()
+            .ok_or_else(|| Report::msg("Expected a line of variables"))?;
+        let variables = Variables(
+            variables_line
+                .split_whitespace()
+                .map(|var| validate_variable(var).map(ToString::to_string))
+                .collect::<Result<HashSet<String>>>()
+                .map_err(|e| line_error(variables_line_num, e))?,
+        );
+        debug!(?variables);
+
+        let (objective_function_line_num, objective_function_line) = lines
+            .next()
+            .ok_or_else(|| Report::msg("Expected a line with the objective function"))?;
+
+        // The config can change how everything else is parsed, so we take it out first
+        let mut in_config_section = false;
+        let mut constraint_lines: Vec<(usize, &str)> = Vec::new();
+        for (line_num, line) in lines {
+            match line.trim_end_matches(':').to_lowercase().as_str() {
+                "config" => {
+                    in_config_section = true;
+                    continue;
+                }
+                "bounds" | "constraints" => in_config_section = false,
+                _ => (),
+            }
+
+            if in_config_section {
+                config
+                    .set_option(line, &variables)
+                    .map_err(|e| line_error(line_num, e))?;
+            } else {
+                constraint_lines.push((line_num, line));
+            }
+        }
+        debug!(?config);
+        if constraint_lines.is_empty() {
+            return Err(Report::msg("Expected at least one constraint"));
+        }
+        let syntax = config.number_syntax;
+
+        let mut objective_name = None;
+        let mut system = LinProgSystemTryBuilder {
+            variables,
+            config,
+            objective_function_builder: |variables: &Variables| {
+                ObjectiveFunction::parse_named_with_syntax(
+                    objective_function_line,
+                    variables,
+                    syntax,
+                )
+                .map(|(name, objective_function)| {
+                    objective_name = name;
+                    objective_function.simplify()
+                })
+                .map_err(|e| line_error(objective_function_line_num, e))
+            },
+            constraints_builder: |variables: &Variables| {
+                let mut constraints = Vec::new();
+                let mut in_bounds_section = false;
+                for &(line_num, line) in &constraint_lines {
+                    match line.trim_end_matches(':').to_lowercase().as_str() {
+                        "bounds" => {
+                            in_bounds_section = true;
+                            continue;
+                        }
+                        "constraints" => {
+                            in_bounds_section = false;
+                            continue;
+                        }
+                        _ => (),
+                    }
+
+                    let cons = if in_bounds_section {
+                        Constraint::parse_bounds_line_with_syntax(line, variables, syntax)
+                    } else {
+                        Constraint::parse_line_with_syntax(line, variables, syntax)
+                    }
+                    .map_err(|e| line_error(line_num, e))?;
+                    constraints.extend(cons.into_iter().map(Constraint::simplify));
+                }
+                Ok(constraints)
+            },
+        }
+        .try_build()?;
+
+        if let Some(name) = objective_name {
+            system.with_config_mut(|config| config.objective_name = Some(name.to_string()));
+        }
+
+        debug!("{:#?}", system);
+        Ok(system)
+    }
+
     /// Build a system from an `inquire` prompt.
     #[instrument]
     pub fn build_from_user() -> Result<Self> {
+        const CONSTRAINT_HELP: &str =
+            "Variables and constants can be on either side; use <= for ≤ and >= for ≥; separate \
+            multiple constraints with ; or ,";
+
         let variables = Variables(
             Text::new("Please enter all your named variables, separated by spaces:")
                 .prompt()?
@@ -54,33 +404,48 @@ impl LinProgSystem {
         );
         debug!(?variables);
 
-        let config = Config::build_from_user()?;
+        let config = Config::build_from_user(&variables)?;
         debug!(?config);
+        let syntax = config.number_syntax;
 
-        let system = LinProgSystemBuilder {
+        let mut objective_name = None;
+        let mut system = LinProgSystemBuilder {
             variables,
             config,
             objective_function_builder: |variables: &Variables| {
-                let objective_function = ObjectiveFunction::build_from_user(variables)
-                    .expect("Building objective function from user should not fail")
-                    .simplify();
-                debug!(?objective_function);
+                let (name, objective_function) =
+                    ObjectiveFunction::build_from_user(variables, syntax)
+                        .expect("Building objective function from user should not fail");
+                let objective_function = objective_function.simplify();
+                debug!(?name, ?objective_function);
+                objective_name = name;
                 objective_function
             },
             constraints_builder: |variables: &Variables| {
                 let mut constraints = Vec::new();
+                // Each line entered so far, with the number of constraints on it, so that the last
+                // line can be edited
+                let mut lines: Vec<(String, usize)> = Vec::new();
+                // The line being edited and its old constraints, which are put back if the edit is
+                // cancelled
+                let mut editing: Option<(String, Vec<Constraint>)> = None;
 
                 loop {
-                    let mut input = match Text::new("Please enter a constraint inequality:")
-                        .with_help_message(
-                            "The constant must be on the RHS; use <= for ≤ and >= for ≥",
-                        )
-                        .prompt()
-                    {
+                    let prompt = Text::new("Please enter a constraint inequality:")
+                        .with_help_message(CONSTRAINT_HELP);
+                    let prompt = match &editing {
+                        Some((line, _)) => prompt.with_initial_value(line),
+                        None => prompt,
+                    };
+                    let mut input = match prompt.prompt() {
                         Ok(x) => x,
                         Err(
                             InquireError::OperationCanceled | InquireError::OperationInterrupted,
                         ) => {
+                            if let Some((line, old_constraints)) = editing.take() {
+                                lines.push((line, old_constraints.len()));
+                                constraints.extend(old_constraints);
+                            }
                             if constraints.is_empty() {
                                 println!("You must have at least one constraint inequality");
                                 continue;
@@ -92,17 +457,17 @@ impl LinProgSystem {
                     };
 
                     'input_loop: loop {
-                        match Constraint::nom_parse(&input, variables) {
-                            Ok((_, cons)) => {
-                                constraints.push(cons.simplify());
+                        match Constraint::parse_line_with_syntax(&input, variables, syntax) {
+                            Ok(cons) => {
+                                lines.push((input, cons.len()));
+                                constraints.extend(cons.into_iter().map(Constraint::simplify));
+                                editing = None;
                                 break 'input_loop;
                             }
                             Err(e) => {
                                 input = match Text::new("Please try again:")
                                     .with_initial_value(&input)
-                                    .with_help_message(
-                                        "The constant must be on the RHS; use <= for ≤ and >= for ≥",
-                                    )
+                                    .with_help_message(CONSTRAINT_HELP)
                                     .with_help_message(&format!("Error: {e}"))
                                     .prompt()
                                 {
@@ -122,16 +487,28 @@ impl LinProgSystem {
                             }
                         }
                     }
+                    if let Some((line, old_constraints)) = editing.take() {
+                        lines.push((line, old_constraints.len()));
+                        constraints.extend(old_constraints);
+                    }
 
                     match Select::new(
                         "Would you like to add another constraint?",
-                        vec!["Yes", "No"],
+                        vec!["Yes", "No", "Edit previous"],
                     )
                     .prompt()
                     .expect("inquire::Select should not fail")
                     {
                         "Yes" => continue,
                         "No" => break,
+                        "Edit previous" => {
+                            let (line, count) = lines
+                                .pop()
+                                .expect("We should only ask this after a line has been entered");
+                            let old_constraints = constraints.split_off(constraints.len() - count);
+                            editing = Some((line, old_constraints));
+                            continue;
+                        }
                         _ => unreachable!(
                             "inquire::Select should only yield the values given in the vec"
                         ),
@@ -144,7 +521,374 @@ impl LinProgSystem {
         }
         .build();
 
+        if objective_name.is_some() {
+            system.with_config_mut(|config| config.objective_name = objective_name);
+        }
+
         debug!("{:#?}", system);
         Ok(system)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lin_prog::config::{IntegerMode, NumberSyntax, PivotRule, SimplexMethod, VariableNaming},
+        simplex::solve_with_simplex_tableaux,
+    };
+
+    #[test]
+    fn num_variables_and_constraints_test() {
+        let system = LinProgSystemBuilder {
+            variables: Variables::from(["x", "y", "z"]),
+            config: Config::default(),
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("10x + 12y + 8z", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("2x + 2y <= 5", vars).unwrap().1,
+                    Constraint::nom_parse("5x + 3y + 4z <= 15", vars).unwrap().1,
+                ]
+            },
+        }
+        .build();
+
+        assert_eq!(system.num_variables(), 3);
+        assert_eq!(system.num_constraints(), 2);
+    }
+
+    #[test]
+    fn from_parts_test() {
+        let parse_vars = Variables::from(["x", "y"]);
+        let objective_function = ObjectiveFunction::parse("maximise 3x + 2y", &parse_vars).unwrap();
+        let constraints =
+            Constraint::parse_line("5x + 7y <= 70; 10x + 3y <= 60", &parse_vars).unwrap();
+
+        let system =
+            LinProgSystem::from_parts(vec!["x", "y"], objective_function, constraints).unwrap();
+
+        // The system doesn't borrow the variables that the pieces were parsed with
+        drop(parse_vars);
+
+        assert_eq!(system.borrow_variables(), &Variables::from(["x", "y"]));
+        assert_eq!(system.borrow_config(), &Config::default());
+        system.with_objective_function(|obj_func| {
+            assert_eq!(obj_func.to_string(), "Maximise 3x + 2y");
+        });
+        system.with_constraints(|cons| {
+            assert_eq!(
+                cons.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                vec!["5x + 7y ≤ 70", "10x + 3y ≤ 60"]
+            );
+        });
+    }
+
+    #[test]
+    fn objective_sense_test() {
+        let system = LinProgSystem::build_from_str("x y\nmaximise 3x + 2y\nx + y <= 4").unwrap();
+        assert_eq!(system.objective_sense(), ObjectiveSense::Maximise);
+
+        let system = LinProgSystem::build_from_str("x y\nminimise 3x + 2y\nx + y >= 4").unwrap();
+        assert_eq!(system.objective_sense(), ObjectiveSense::Minimise);
+    }
+
+    #[test]
+    fn from_matrix_test() {
+        let frac = |n: u64| Frac::new(n, 1u64);
+        let system = LinProgSystem::from_matrix(
+            &[frac(3), frac(2)],
+            &[vec![frac(5), frac(7)], vec![frac(10), frac(3)]],
+            &[frac(70), frac(60)],
+            &[Comparison::LessThanOrEqual, Comparison::LessThanOrEqual],
+            ObjectiveSense::Maximise,
+        )
+        .unwrap();
+
+        assert_eq!(system.borrow_variables(), &Variables::from(["x0", "x1"]));
+        system.with_objective_function(|obj_func| {
+            assert_eq!(obj_func.to_string(), "Maximise 3x0 + 2x1");
+        });
+        system.with_constraints(|cons| {
+            assert_eq!(
+                cons.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                vec!["5x0 + 7x1 ≤ 70", "10x0 + 3x1 ≤ 60"]
+            );
+        });
+
+        let solution = solve_with_simplex_tableaux(&system).unwrap();
+        assert_eq!(
+            solution.to_string(),
+            "\nObjFunc# = 26\nx0 = 42/11\nx1 = 80/11\nsl#0 = 0\nsl#1 = 0"
+        );
+    }
+
+    #[test]
+    fn from_matrix_error_test() {
+        let one = Frac::new(1u64, 1u64);
+        let from_matrix = |a: &[Vec<Frac>], b: &[Frac], comparisons: &[Comparison]| {
+            LinProgSystem::from_matrix(&[one, one], a, b, comparisons, ObjectiveSense::Minimise)
+                .map(|_| ())
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            from_matrix(
+                &[vec![one, one], vec![one]],
+                &[one, one],
+                &[Comparison::GreaterThanOrEqual; 2]
+            ),
+            "Row 2 of the matrix has 1 entries, but there are 2 variables"
+        );
+        assert_eq!(
+            from_matrix(&[vec![one, one]], &[], &[Comparison::GreaterThanOrEqual]),
+            "Expected one constant and one comparison for each of the 1 rows of the matrix, but \
+            got 0 constants and 1 comparisons"
+        );
+        assert_eq!(
+            from_matrix(&[vec![one, one]], &[one], &[]),
+            "Expected one constant and one comparison for each of the 1 rows of the matrix, but \
+            got 1 constants and 0 comparisons"
+        );
+        assert!(LinProgSystem::from_matrix(&[], &[], &[], &[], ObjectiveSense::Maximise).is_err());
+    }
+
+    #[test]
+    fn from_parts_error_test() {
+        let parse_vars = Variables::from(["x", "y"]);
+        let objective_function = ObjectiveFunction::parse("maximise 3x + 2y", &parse_vars).unwrap();
+        let constraints = Constraint::parse_line("x + y <= 4", &parse_vars).unwrap();
+
+        assert_eq!(
+            LinProgSystem::from_parts(vec!["x"], objective_function.clone(), constraints.clone())
+                .unwrap_err()
+                .to_string(),
+            "Unknown variable \"y\""
+        );
+        assert!(
+            LinProgSystem::from_parts(vec!["x", "y!"], objective_function, constraints).is_err()
+        );
+    }
+
+    #[test]
+    fn build_from_str_test() {
+        let system = LinProgSystem::build_from_str(
+            "x y\n\
+            Maximise 3x + 2y\n\
+            5x + 7y <= 70\n\
+            \n\
+            10x + 3y <= 60; x >= 1\n",
+        )
+        .unwrap();
+
+        assert_eq!(system.borrow_variables(), &Variables::from(["x", "y"]));
+        system.with_objective_function(|obj_func| {
+            assert_eq!(obj_func.to_string(), "Maximise 3x + 2y");
+        });
+        system.with_constraints(|cons| {
+            assert_eq!(
+                cons.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                vec!["5x + 7y ≤ 70", "10x + 3y ≤ 60", "1x ≥ 1"]
+            );
+        });
+    }
+
+    #[test]
+    fn build_from_str_named_objective_test() {
+        let system = LinProgSystem::build_from_str(
+            "x y\n\
+            minimise C = 3x + 2y\n\
+            x + y >= 4\n\
+            x + 3y >= 6\n",
+        )
+        .unwrap();
+
+        assert_eq!(system.borrow_variables(), &Variables::from(["x", "y"]));
+        assert_eq!(system.borrow_config().objective_name.as_deref(), Some("C"));
+        system.with_objective_function(|obj_func| {
+            assert_eq!(obj_func.to_string(), "Minimise 3x + 2y");
+        });
+
+        let solution = solve_with_simplex_tableaux(&system).unwrap();
+        assert!(solution.to_string().starts_with("\nC = 8\n"));
+
+        assert_eq!(
+            LinProgSystem::build_from_str("x y\nminimise x = 3x + 2y\nx + y >= 4")
+                .unwrap_err()
+                .to_string(),
+            "Line 2: Objective function name \"x\" is already a variable"
+        );
+    }
+
+    #[test]
+    fn build_from_str_bounds_section_test() {
+        let system = LinProgSystem::build_from_str(
+            "x y\n\
+            maximise x + y\n\
+            x + 2y <= 14\n\
+            bounds:\n\
+            0 <= x <= 10\n\
+            1 <= y <= 3\n\
+            constraints\n\
+            x - y <= 8\n",
+        )
+        .unwrap();
+
+        system.with_constraints(|cons| {
+            assert_eq!(
+                cons.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                [
+                    "1x + 2y ≤ 14",
+                    "1x ≥ 0",
+                    "1x ≤ 10",
+                    "1y ≥ 1",
+                    "1y ≤ 3",
+                    "1x + -1y ≤ 8"
+                ]
+            );
+        });
+
+        assert_eq!(
+            LinProgSystem::build_from_str("x y\nmaximise x + y\nbounds\n0 <= x + y <= 10")
+                .unwrap_err()
+                .to_string(),
+            "Line 4: The middle of bound \"0 <= x + y <= 10\" must be a single variable"
+        );
+    }
+
+    #[test]
+    fn build_from_str_with_config_test() {
+        let input = "x e\nmaximise 1.5e-3x + e\nx + 2e-3 <= 5";
+
+        let system = LinProgSystem::build_from_str(input).unwrap();
+        system.with_objective_function(|obj_func| {
+            assert_eq!(obj_func.to_string(), "Maximise 5/2e + -3x");
+        });
+
+        let config = Config {
+            number_syntax: NumberSyntax::Scientific,
+            ..Config::default()
+        };
+        let system = LinProgSystem::build_from_str_with_config(input, config.clone()).unwrap();
+        assert_eq!(system.borrow_config(), &config);
+        system.with_objective_function(|obj_func| {
+            assert_eq!(obj_func.to_string(), "Maximise 1e + 3/2000x");
+        });
+        system.with_constraints(|cons| {
+            assert_eq!(
+                cons.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                vec!["1x ≤ 2499/500"]
+            );
+        });
+    }
+
+    #[test]
+    fn build_from_str_config_section_test() {
+        let system = LinProgSystem::build_from_str(
+            "x y\n\
+            maximise 1.5e-3x + y\n\
+            x + y <= 4\n\
+            config:\n\
+            method = big_m\n\
+            pivot_rule = random\n\
+            seed = 42\n\
+            integer = x\n\
+            naming = numbered\n\
+            numbers = scientific\n\
+            ascii_output\n\
+            constraints\n\
+            x <= 3\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            system.borrow_config(),
+            &Config {
+                method: SimplexMethod::BigM,
+                pivot_rule: PivotRule::Random,
+                rng_seed: Some(42),
+                integer_variables: IntegerMode::Some(HashSet::from(["x".to_string()])),
+                variable_naming: VariableNaming::Numbered,
+                number_syntax: NumberSyntax::Scientific,
+                ascii_output: true,
+                ..Config::default()
+            }
+        );
+        system.with_constraints(|cons| {
+            assert_eq!(
+                cons.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                ["1x + 1y ≤ 4", "1x ≤ 3"]
+            );
+        });
+        assert!(solve_with_simplex_tableaux(&system)
+            .unwrap()
+            .to_string()
+            .starts_with("\nObjFunc# = 4\n"));
+
+        // The config section overrides the config that we start from
+        let system = LinProgSystem::build_from_str_with_config(
+            "x y\nmaximise x + y\nx + y <= 4\nconfig\ninteger = all",
+            Config {
+                integer_variables: IntegerMode::Some(HashSet::from(["y".to_string()])),
+                anti_cycling: true,
+                ..Config::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(system.borrow_config().integer_variables, IntegerMode::All);
+        assert!(system.borrow_config().anti_cycling);
+
+        let error = |config_line: &str| {
+            LinProgSystem::build_from_str(&format!(
+                "x y\nmaximise x + y\nx + y <= 4\nconfig\n{config_line}"
+            ))
+            .unwrap_err()
+            .to_string()
+        };
+        assert_eq!(
+            error("colour = red"),
+            "Line 5: Unknown config option \"colour\""
+        );
+        assert_eq!(
+            error("pivot_rule = biggest"),
+            "Line 5: Invalid pivot_rule \"biggest\""
+        );
+        assert_eq!(error("integer = x z"), "Line 5: Unknown variable \"z\"");
+        assert_eq!(error("method"), "Line 5: Expected a value for method");
+        assert_eq!(
+            error("anti_cycling = yes"),
+            "Line 5: anti_cycling is a flag, so it can't have a value"
+        );
+        assert!(error("seed = -1").starts_with("Line 5: Invalid seed \"-1\": "));
+        assert_eq!(
+            LinProgSystem::build_from_str("x y\nmaximise x + y\nconfig\nascii_output")
+                .unwrap_err()
+                .to_string(),
+            "Expected at least one constraint"
+        );
+    }
+
+    #[test]
+    fn build_from_str_error_test() {
+        let error = |input: &str| {
+            LinProgSystem::build_from_str(input)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(error("x y\nmaximise 3x + 2y\nx <= 4\nx + z <= 5").starts_with("Line 4: "));
+        assert!(error("x y\nmaximise 3x + 2y\n\nx <= 4\nx + y <= 5 <= 6").starts_with("Line 5: "));
+        assert!(error("x y\nmaximise 3x + 2w\nx <= 4").starts_with("Line 2: "));
+        assert!(error("x y\noptimise 3x + 2y\nx <= 4").starts_with("Line 2: "));
+        assert!(error("x y!\nmaximise 3x + 2y\nx <= 4").starts_with("Line 1: "));
+        assert_eq!(
+            error("x y\nmaximise 3x + 2y\n"),
+            "Expected at least one constraint"
+        );
+    }
+}
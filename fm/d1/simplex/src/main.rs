@@ -1,27 +1,24 @@
-//! This crate exists to help solve linear programming problems and is used as a CLI app.
-//!
-//! Throughout the crate, `LinProg` is used as an abbreviation for "linear programming".
+//! This is the CLI app to solve linear programming problems with the simplex algorithm.
 
 #![cfg_attr(debug_assertions, allow(unused_variables, dead_code))]
 
-mod lin_prog;
-mod simplex;
+use ::simplex::{
+    lin_prog::{config::PivotRule, system::LinProgSystem},
+    simplex,
+};
+use color_eyre::{Report, Result};
+use std::{env, fs, io};
+use tracing::{info, warn};
 
-use self::{lin_prog::system::LinProgSystem, simplex::solve_with_simplex_tableaux};
-use color_eyre::Result;
-use fraction::GenericFraction;
-use tracing::info;
-
-type Frac = GenericFraction<u32>;
-
-fn install_tracing() {
+/// Install the tracing subscriber, using `default_level` unless `RUST_LOG` is set.
+fn install_tracing(default_level: &str) {
     use tracing_error::ErrorLayer;
     use tracing_subscriber::prelude::*;
     use tracing_subscriber::{fmt, EnvFilter};
 
     let fmt_layer = fmt::layer().with_target(false);
     let filter_layer = EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new("info"))
+        .or_else(|_| EnvFilter::try_new(default_level))
         .unwrap();
 
     tracing_subscriber::registry()
@@ -31,13 +28,218 @@ fn install_tracing() {
         .init();
 }
 
+/// Solve the system, showing a progress bar with the current value of the objective function.
+#[cfg(feature = "progress")]
+fn solve(system: &LinProgSystem) -> Result<simplex::SolutionSet<'_>> {
+    use indicatif::{ProgressBar, ProgressStyle};
+
+    let bar = ProgressBar::new_spinner().with_style(
+        ProgressStyle::with_template("{spinner} Step {pos}: {msg}")
+            .expect("The progress bar template should be valid"),
+    );
+    let name = system
+        .borrow_config()
+        .objective_name
+        .as_deref()
+        .unwrap_or("ObjFunc#");
+    let solution = simplex::solve_with_simplex_tableaux_and_progress(system, |step, value| {
+        bar.set_position(step as u64);
+        bar.set_message(format!("{name} = {value}"));
+    });
+    bar.finish_and_clear();
+
+    solution
+}
+
+/// Solve the system without any progress reporting.
+#[cfg(not(feature = "progress"))]
+fn solve(system: &LinProgSystem) -> Result<simplex::SolutionSet<'_>> {
+    simplex::solve_with_simplex_tableaux(system)
+}
+
+/// How much of the working to show while solving the system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Output {
+    /// Log the working and the solution at the `info` level.
+    Log,
+
+    /// Print every tableau, from the initial one to the final one, and then the solution.
+    ShowSteps,
+
+    /// Print the initial tableau, and then explain each pivot in words before printing the tableau
+    /// it results in, and then the solution.
+    Explain,
+
+    /// Print just the final tableau and the solution.
+    FinalTableau,
+
+    /// Print just the solution.
+    Quiet,
+}
+
+impl Output {
+    /// The log level to use when `RUST_LOG` isn't set. Anything other than [`Output::Log`] prints
+    /// what it needs directly, so we don't want the intermediate tableaux logged as well.
+    fn default_log_level(self) -> &'static str {
+        match self {
+            Self::Log => "info",
+            Self::ShowSteps | Self::Explain | Self::FinalTableau | Self::Quiet => "warn",
+        }
+    }
+}
+
+/// The command line arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Args {
+    /// The path given with `--file`, if there is one. Without it, we build the system interactively.
+    file: Option<String>,
+
+    /// How much of the working to show.
+    output: Output,
+
+    /// The seed given with `--seed`, if there is one, for choosing random pivot columns. This
+    /// only has an effect if the system uses the random pivot rule, which a file can choose with
+    /// `pivot_rule = random` in its `config` section.
+    seed: Option<u64>,
+}
+
+/// Parse the command line arguments, not including the program name.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args> {
+    let mut args = args.into_iter();
+    let mut file = None;
+    let mut output = None;
+    let mut seed = None;
+
+    while let Some(arg) = args.next() {
+        let flag_output = match arg.as_str() {
+            "--file" => match args.next() {
+                Some(p) => {
+                    file = Some(p);
+                    continue;
+                }
+                None => return Err(Report::msg("Expected a path after --file")),
+            },
+            "--seed" => match args.next().map(|s| s.parse()) {
+                Some(Ok(s)) => {
+                    seed = Some(s);
+                    continue;
+                }
+                Some(Err(e)) => return Err(Report::msg(format!("Invalid seed: {e}"))),
+                None => return Err(Report::msg("Expected a number after --seed")),
+            },
+            "--show-steps" => Output::ShowSteps,
+            "--explain" => Output::Explain,
+            "--final-tableau" => Output::FinalTableau,
+            "--quiet" => Output::Quiet,
+            _ => return Err(Report::msg(format!("Unknown argument {arg:?}"))),
+        };
+
+        if output.is_some_and(|o| o != flag_output) {
+            return Err(Report::msg(
+                "Only one of --show-steps, --explain, --final-tableau, and --quiet can be given",
+            ));
+        }
+        output = Some(flag_output);
+    }
+
+    Ok(Args {
+        file,
+        output: output.unwrap_or(Output::Log),
+        seed,
+    })
+}
+
 fn main() -> Result<()> {
-    install_tracing();
     color_eyre::install()?;
+    let args = parse_args(env::args().skip(1))?;
+    install_tracing(args.output.default_log_level());
+
+    let mut system = match args.file {
+        Some(path) => LinProgSystem::build_from_str(&fs::read_to_string(path)?)?,
+        None => LinProgSystem::build_from_user()?,
+    };
+    if let Some(seed) = args.seed {
+        if system.borrow_config().pivot_rule != PivotRule::Random {
+            warn!(
+                seed,
+                "The seed is ignored, since it only affects the random pivot rule, which this \
+                system doesn't use"
+            );
+        }
+        system.with_config_mut(|config| config.rng_seed = Some(seed));
+    }
+    let solution = match args.output {
+        Output::Log | Output::Quiet => solve(&system)?,
+        Output::ShowSteps => {
+            simplex::solve_with_simplex_tableaux_verbose(&system, &mut io::stdout())?
+        }
+        Output::Explain => {
+            simplex::solve_with_simplex_tableaux_explained(&system, &mut io::stdout())?
+        }
+        Output::FinalTableau => {
+            let tableau = simplex::solve_to_final_tableau(&system)?;
+            println!("Final tableau:{tableau}\n");
+            simplex::solution_from_final_tableau(&system, tableau)?
+        }
+    };
 
-    let system = LinProgSystem::build_from_user()?;
-    let solution = solve_with_simplex_tableaux(&system)?;
-    info!(%solution, "Solution found!");
+    let continued_fractions = system.borrow_config().continued_fractions;
+    match (args.output, continued_fractions) {
+        (Output::Log, true) => {
+            let solution = simplex::continued_fraction::ContinuedFractions(&solution);
+            info!(%solution, "Solution found!");
+        }
+        (Output::Log, false) => info!(%solution, "Solution found!"),
+        (_, true) => println!(
+            "{}",
+            simplex::continued_fraction::ContinuedFractions(&solution)
+        ),
+        (_, false) => println!("{solution}"),
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parse_args_output_test() {
+        assert_eq!(args(&[]).unwrap().output, Output::Log);
+        assert_eq!(args(&["--show-steps"]).unwrap().output, Output::ShowSteps);
+        assert_eq!(
+            args(&["--final-tableau"]).unwrap().output,
+            Output::FinalTableau
+        );
+        assert_eq!(args(&["--explain"]).unwrap().output, Output::Explain);
+        assert_eq!(args(&["--quiet"]).unwrap().output, Output::Quiet);
+        assert_eq!(
+            args(&["--final-tableau", "--file", "lp.txt", "--final-tableau"]).unwrap(),
+            Args {
+                file: Some("lp.txt".to_string()),
+                output: Output::FinalTableau,
+                seed: None,
+            }
+        );
+        assert_eq!(args(&["--seed", "42"]).unwrap().seed, Some(42));
+
+        assert!(args(&["--final-tableau", "--quiet"]).is_err());
+        assert!(args(&["--show-steps", "--final-tableau"]).is_err());
+        assert!(args(&["--explain", "--show-steps"]).is_err());
+        assert!(args(&["--file"]).is_err());
+        assert!(args(&["--seed"]).is_err());
+        assert!(args(&["--seed", "-1"]).is_err());
+        assert!(args(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn default_log_level_test() {
+        assert_eq!(Output::Log.default_log_level(), "info");
+        assert_eq!(Output::FinalTableau.default_log_level(), "warn");
+    }
+}
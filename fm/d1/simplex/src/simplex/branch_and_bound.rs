@@ -0,0 +1,305 @@
+//! This module handles integer programming with branch and bound, where we keep splitting the
+//! feasible region around a variable with a fractional value until every variable that needs to be
+//! an integer is one.
+
+use super::{solve_with_simplex_tableaux, tableau::NoFeasibleSolution, SolutionSet, VariableType};
+use crate::{
+    lin_prog::{
+        comparison::Comparison,
+        config::{IntegerMode, VariableNaming},
+        constraint::Constraint,
+        expression::Expression,
+        system::LinProgSystem,
+        ObjectiveSense,
+    },
+    Frac,
+};
+use color_eyre::Result;
+use itertools::Itertools;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+use thiserror::Error;
+use tracing::{debug, error, instrument};
+
+/// There is no integer solution for the given [`LinProgSystem`], even though its continuous
+/// relaxation has a feasible solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub struct IntegerInfeasible;
+
+impl fmt::Display for IntegerInfeasible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "No integer solution for the given system, although its relaxation is feasible"
+        )
+    }
+}
+
+/// The best integer solution found so far. Every branch is a different system, so we own the
+/// variable names rather than borrowing them from the system that the solution came from.
+#[derive(Clone, Debug, PartialEq)]
+struct Incumbent {
+    /// The value of the objective function at this solution.
+    objective_function_value: Frac,
+
+    /// The values of the original variables.
+    variable_values: Vec<(String, Frac)>,
+}
+
+/// Find the optimal integer solution to the system with branch and bound, given the solution to
+/// its continuous relaxation. Only the variables required by the system's [`IntegerMode`] need
+/// integer values, and the rest can stay fractional.
+///
+/// We pick a variable which needs to be an integer but has a fractional value and branch on it
+/// by adding `x ≤ floor` in one branch and `x ≥ ceil` in the other, re-solving each branch and
+/// recursing. Any branch whose
+/// relaxation can't beat the best integer solution found so far gets pruned, since adding more
+/// constraints can never improve it.
+#[instrument(skip(system, relaxation))]
+pub fn branch_and_bound<'v>(
+    system: &'v LinProgSystem,
+    relaxation: SolutionSet<'_>,
+) -> Result<SolutionSet<'v>> {
+    branch_and_bound_with_progress(system, relaxation, |_, _| ())
+}
+
+/// Find the optimal integer solution like [`branch_and_bound`], calling `progress` after each node
+/// of the search tree with the number of nodes explored so far and the best objective value so
+/// far. Until we find an integer solution, the best value is the value of the latest relaxation.
+///
+/// Every branch counts as a node, including the infeasible ones and the ones that get pruned.
+#[instrument(skip(system, relaxation, progress))]
+pub fn branch_and_bound_with_progress<'v>(
+    system: &'v LinProgSystem,
+    relaxation: SolutionSet<'_>,
+    mut progress: impl FnMut(usize, Frac),
+) -> Result<SolutionSet<'v>> {
+    let minimise = system.objective_sense() == ObjectiveSense::Minimise;
+
+    // The branches are just continuous problems, and an infeasible branch is expected, so we don't
+    // want to look for an infeasible subsystem every time we find one
+    let mut config = system.borrow_config().clone();
+    let integer_variables = config.integer_variables;
+    config.integer_variables = IntegerMode::None;
+    config.report_infeasible_subsystem = false;
+    let relaxed_system = system.rebuild(config, |_, obj_func| obj_func, |_, cons| cons);
+
+    let mut incumbent = None;
+    let mut nodes = 0;
+    branch(
+        &relaxed_system,
+        relaxation,
+        &integer_variables,
+        minimise,
+        &mut incumbent,
+        &mut |best| {
+            nodes += 1;
+            progress(nodes, best);
+        },
+    )?;
+
+    let Some(incumbent) = incumbent else {
+        error!(err = %IntegerInfeasible);
+        return Err(IntegerInfeasible.into());
+    };
+    debug!(?incumbent);
+
+    let variables = &system.borrow_variables().0;
+    let variable_values = incumbent
+        .variable_values
+        .into_iter()
+        .map(|(name, value)| {
+            let name = variables
+                .get(&name)
+                .expect("Every variable in a branch should be in the original system");
+            (VariableType::Original(name.as_str()), value)
+        })
+        .collect();
+
+    // Integer solutions don't come from a single tableau, so they don't have a basis
+    Ok(SolutionSet {
+        objective_function_value: incumbent.objective_function_value,
+        variable_values,
+        basic_variables: HashSet::new(),
+        shadow_prices: HashMap::new(),
+        has_alternative_optima: false,
+        objective_name: None,
+        variable_naming: VariableNaming::default(),
+    })
+}
+
+/// Explore the branch with the given system and the solution to its relaxation, updating the
+/// incumbent if we find a better integer solution. The `progress` closure gets called once for
+/// this node and once for each node below it, with the best objective value so far.
+fn branch<'s>(
+    system: &'s LinProgSystem,
+    relaxation: SolutionSet<'s>,
+    integer_variables: &IntegerMode,
+    minimise: bool,
+    incumbent: &mut Option<Incumbent>,
+    progress: &mut impl FnMut(Frac),
+) -> Result<()> {
+    let value = relaxation.objective_function_value;
+    let best_so_far = |incumbent: &Option<Incumbent>| {
+        incumbent
+            .as_ref()
+            .map_or(value, |best| best.objective_function_value)
+    };
+
+    if let Some(best) = incumbent {
+        let improves = if minimise {
+            value < best.objective_function_value
+        } else {
+            value > best.objective_function_value
+        };
+        if !improves {
+            debug!(%value, best = %best.objective_function_value, "Pruning branch");
+            progress(best.objective_function_value);
+            return Ok(());
+        }
+    }
+
+    let original_values = relaxation
+        .variable_values
+        .iter()
+        .filter_map(|(var, &value)| match *var {
+            VariableType::Original(name) => Some((name, value)),
+            _ => None,
+        })
+        .sorted_by_key(|&(name, _)| name)
+        .collect_vec();
+
+    let Some(&(name, fractional)) = original_values
+        .iter()
+        .find(|&&(name, value)| integer_variables.is_integer(name) && value.floor() != value)
+    else {
+        debug!(%value, "Found a better integer solution");
+        *incumbent = Some(Incumbent {
+            objective_function_value: value,
+            variable_values: original_values
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        });
+        progress(value);
+        return Ok(());
+    };
+    progress(best_so_far(incumbent));
+
+    for (comparison, constant) in [
+        (Comparison::LessThanOrEqual, fractional.floor()),
+        (Comparison::GreaterThanOrEqual, fractional.ceil()),
+    ] {
+        let constraint = Constraint {
+            var_expression: Expression(vec![(1.into(), name)]),
+            comparison,
+            constant,
+        };
+        debug!(%constraint, "Branching");
+
+        let branch_system = system.with_extra_constraint(&constraint);
+        match solve_with_simplex_tableaux(&branch_system) {
+            Ok(solution) => branch(
+                &branch_system,
+                solution,
+                integer_variables,
+                minimise,
+                incumbent,
+                progress,
+            )?,
+            Err(e) if e.downcast_ref::<NoFeasibleSolution>().is_some() => {
+                debug!(%constraint, "Branch is infeasible");
+                progress(best_so_far(incumbent));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lin_prog::{config::Config, system::LinProgSystemBuilder, ObjectiveFunction, Variables},
+        simplex::{solve_with_simplex_tableaux, tests::ch7_example8_with_config},
+    };
+    use fraction::Zero;
+
+    /// Find the optimal integer solution by trying every point with coordinates from 0 to 10.
+    fn exhaustive_search(system: &LinProgSystem) -> Frac {
+        system.with_constraints(|cons| {
+            system.with_objective_function(|obj_func| {
+                (0..=10u32)
+                    .cartesian_product(0..=10u32)
+                    .map(|(x, y)| [("x", Frac::from(x)), ("y", Frac::from(y))])
+                    .filter(|point| cons.iter().all(|con| con.test(point)))
+                    .map(|point| obj_func.expression().evaluate(&point))
+                    .max()
+                    .expect("There should be at least one feasible point")
+            })
+        })
+    }
+
+    #[test]
+    fn branch_and_bound_test() {
+        for (obj_func, cons) in [
+            ("3x + 2y", ["5x + 7y <= 70", "10x + 3y <= 60"]),
+            ("x + 4y", ["3x + 8y <= 29", "4x - 2y <= 9"]),
+            ("5x + 8y", ["x + y <= 6", "5x + 9y <= 45"]),
+        ] {
+            let system = LinProgSystemBuilder {
+                variables: Variables::from(["x", "y"]),
+                config: Config {
+                    integer_variables: IntegerMode::All,
+                    ..Config::default()
+                },
+                objective_function_builder: |vars| {
+                    ObjectiveFunction::Maximise(
+                        Expression::nom_parse(obj_func, vars).unwrap().1,
+                        Frac::zero(),
+                    )
+                },
+                constraints_builder: |vars| {
+                    cons.iter()
+                        .map(|con| Constraint::nom_parse(con, vars).unwrap().1)
+                        .collect()
+                },
+            }
+            .build();
+
+            let solution = solve_with_simplex_tableaux(&system).unwrap();
+            assert!(solution
+                .variable_values
+                .values()
+                .all(|value| value.floor() == *value));
+            assert_eq!(
+                solution.objective_function_value,
+                exhaustive_search(&system),
+                "{obj_func}"
+            );
+        }
+    }
+
+    #[test]
+    fn branch_and_bound_mixed_integer_test() {
+        let system = ch7_example8_with_config(Config {
+            integer_variables: IntegerMode::Some(HashSet::from(["x".to_string()])),
+            ..Config::default()
+        });
+
+        // Only x needs to be an integer, so y can stay fractional
+        let solution = solve_with_simplex_tableaux(&system).unwrap();
+        assert_eq!(solution.objective_function_value, Frac::new(76u32, 3u32));
+        assert_eq!(
+            solution.variable_values,
+            HashMap::from([
+                (VariableType::Original("x"), 4.into()),
+                (VariableType::Original("y"), Frac::new(20u32, 3u32)),
+            ])
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! This module handles displaying solutions as continued fractions.
+
+use super::SolutionSet;
+use crate::Frac;
+use itertools::Itertools;
+use std::fmt;
+
+/// Expand the given fraction into its continued fraction, so `80/11` becomes `[7, 3, 1, 2]`,
+/// since `80/11 = 7 + 1/(3 + 1/(1 + 1/2))`.
+///
+/// The first term is the floor of the fraction, so it may be negative, but every other term is
+/// positive. Infinite and NaN values have no expansion, so they give an empty vec. This panics if
+/// any term is too big for an `i64`.
+pub fn frac_to_continued_fraction(frac: Frac) -> Vec<i64> {
+    let (Some(&numer), Some(&denom)) = (frac.numer(), frac.denom()) else {
+        return vec![];
+    };
+
+    // We need a wider type than the u64 in the fraction to hold the signed numerator
+    let mut numer = if frac < Frac::from(0) {
+        -i128::from(numer)
+    } else {
+        i128::from(numer)
+    };
+    let mut denom = i128::from(denom);
+    let mut terms = Vec::new();
+
+    while denom != 0 {
+        let term = numer.div_euclid(denom);
+        terms.push(i64::try_from(term).expect("Every term should fit in an i64"));
+        (numer, denom) = (denom, numer - term * denom);
+    }
+
+    terms
+}
+
+/// Format a fraction as a continued fraction in the usual notation, like `[7; 3, 1, 2]`.
+fn format_continued_fraction(frac: Frac) -> String {
+    match frac_to_continued_fraction(frac).split_first() {
+        None => frac.to_string(),
+        Some((first, [])) => format!("[{first}]"),
+        Some((first, rest)) => format!("[{first}; {}]", rest.iter().join(", ")),
+    }
+}
+
+/// A wrapper around a [`SolutionSet`] to display all its values as continued fractions.
+#[derive(Clone, Copy, Debug)]
+pub struct ContinuedFractions<'s, 'v>(pub &'s SolutionSet<'v>);
+
+impl<'s, 'v> fmt::Display for ContinuedFractions<'s, 'v> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "\n{} = {}",
+            self.0.objective_name(),
+            format_continued_fraction(self.0.objective_function_value)
+        )?;
+        for (var, value) in self
+            .0
+            .variable_values
+            .iter()
+            .sorted_by_key(|&(var_type, _)| var_type)
+        {
+            write!(
+                f,
+                "\n{} = {}",
+                var.display_with(self.0.variable_naming),
+                format_continued_fraction(*value)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frac_to_continued_fraction_test() {
+        assert_eq!(
+            frac_to_continued_fraction(Frac::new(80u32, 11u32)),
+            vec![7, 3, 1, 2]
+        );
+        assert_eq!(
+            frac_to_continued_fraction(Frac::new(415u32, 93u32)),
+            vec![4, 2, 6, 7]
+        );
+        assert_eq!(
+            frac_to_continued_fraction(Frac::new(3u32, 8u32)),
+            vec![0, 2, 1, 2]
+        );
+        assert_eq!(frac_to_continued_fraction(Frac::from(5)), vec![5]);
+        assert_eq!(frac_to_continued_fraction(Frac::from(0)), vec![0]);
+        assert_eq!(
+            frac_to_continued_fraction(-Frac::new(7u32, 2u32)),
+            vec![-4, 2]
+        );
+        assert_eq!(
+            frac_to_continued_fraction(Frac::infinity()),
+            Vec::<i64>::new()
+        );
+        assert_eq!(frac_to_continued_fraction(Frac::nan()), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn format_continued_fraction_test() {
+        assert_eq!(
+            format_continued_fraction(Frac::new(80u32, 11u32)),
+            "[7; 3, 1, 2]"
+        );
+        assert_eq!(format_continued_fraction(Frac::from(12)), "[12]");
+        assert_eq!(format_continued_fraction(Frac::nan()), "NaN");
+    }
+}
@@ -0,0 +1,466 @@
+//! This module contains diagnostics to help users understand problems with their systems.
+
+use super::{
+    solve_with_simplex_tableaux,
+    tableau::{IterationError, NoFeasibleSolution},
+};
+use crate::{
+    lin_prog::{
+        comparison::Comparison, config::IntegerMode, expression::Expression, system::LinProgSystem,
+        ObjectiveFunction,
+    },
+    Frac,
+};
+use color_eyre::{Report, Result};
+use fraction::Zero;
+use std::collections::HashSet;
+use thiserror::Error;
+use tracing::{debug, info, instrument, warn};
+
+/// The objective function is unbounded because it improves by moving a variable which isn't in
+/// any constraints. See [`check_objective_only_variables`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[error("Objective function is unbounded, since {0} is not in any constraints")]
+pub struct UnconstrainedVariable(pub String);
+
+/// Check for variables which appear in the objective function but not in any constraints.
+///
+/// If moving such a variable away from 0 would improve the objective function, then nothing stops
+/// it moving forever, so we report that the objective function is unbounded. Otherwise, the
+/// variable is optimally fixed at 0, which the tableau will do anyway, so we just log it.
+#[instrument(skip(system))]
+pub fn check_objective_only_variables(system: &LinProgSystem) -> Result<()> {
+    let constrained_vars: HashSet<String> = system.with_constraints(|cons| {
+        cons.iter()
+            .flat_map(|con| &con.var_expression.0)
+            .filter(|(coeff, _)| !coeff.is_zero())
+            .map(|&(_, var)| var.to_string())
+            .collect()
+    });
+    let non_positive = &system.borrow_config().non_positive_variables;
+
+    system.with_objective_function(|obj_func| {
+        let (maximise, expression) = match obj_func {
+            ObjectiveFunction::Maximise(exp, _) => (true, exp),
+            ObjectiveFunction::Minimise(exp, _) => (false, exp),
+        };
+
+        for &(coeff, var) in &expression.0 {
+            if coeff.is_zero() || constrained_vars.contains(var) {
+                continue;
+            }
+
+            // A non-positive variable can only decrease, which flips whether it helps
+            if ((coeff > Frac::zero()) == maximise) != non_positive.contains(var) {
+                return Err(Report::new(UnconstrainedVariable(var.to_string())));
+            }
+
+            info!(%var, "Variable is not in any constraints, so it's fixed at 0");
+        }
+
+        Ok(())
+    })
+}
+
+/// Check that the objective function references at least one variable with a nonzero coefficient.
+///
+/// An empty objective function makes every feasible point optimal, which is usually a mistake, so
+/// this is an error if the config has `require_nonempty_objective` set. Otherwise, we just warn
+/// about it and carry on.
+#[instrument(skip(system))]
+pub fn check_objective_not_empty(system: &LinProgSystem) -> Result<()> {
+    let is_empty = system.with_objective_function(|obj_func| {
+        let (ObjectiveFunction::Maximise(exp, _) | ObjectiveFunction::Minimise(exp, _)) = obj_func;
+        exp.0.iter().all(|(coeff, _)| coeff.is_zero())
+    });
+
+    if !is_empty {
+        Ok(())
+    } else if system.borrow_config().require_nonempty_objective {
+        Err(Report::msg(
+            "Objective function doesn't reference any variables",
+        ))
+    } else {
+        warn!(
+            "Objective function doesn't reference any variables, so any feasible point is optimal"
+        );
+        Ok(())
+    }
+}
+
+/// Check that every coefficient in the objective function and constraints is an integer, if the
+/// config has `integer_coefficients_only` set. The error names the first term that isn't.
+#[instrument(skip(system))]
+pub fn check_integer_coefficients(system: &LinProgSystem) -> Result<()> {
+    if !system.borrow_config().integer_coefficients_only {
+        return Ok(());
+    }
+
+    let non_integer = |expression: &Expression| {
+        expression
+            .0
+            .iter()
+            .find(|(coeff, _)| coeff.denom().is_some_and(|&denom| denom != 1))
+            .map(|&(coeff, var)| format!("{coeff}{var}"))
+    };
+
+    if let Some(term) =
+        system.with_objective_function(|obj_func| non_integer(obj_func.expression()))
+    {
+        return Err(Report::msg(format!(
+            "Term {term} in the objective function has a coefficient which isn't an integer"
+        )));
+    }
+
+    system.with_constraints(|cons| {
+        for con in cons {
+            if let Some(term) = non_integer(&con.var_expression) {
+                return Err(Report::msg(format!(
+                    "Term {term} in constraint {con} has a coefficient which isn't an integer"
+                )));
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Do all the checks that every solve runs on the system before building its initial tableau:
+/// [`check_objective_only_variables`], [`check_objective_not_empty`], and
+/// [`check_integer_coefficients`].
+pub fn check_system(system: &LinProgSystem) -> Result<()> {
+    check_objective_only_variables(system)?;
+    check_objective_not_empty(system)?;
+    check_integer_coefficients(system)
+}
+
+/// Check if the given system has any feasible solution at all, ignoring its objective function
+/// and any integer requirements.
+#[instrument(skip(system))]
+pub fn is_feasible(system: &LinProgSystem) -> Result<bool> {
+    let mut config = system.borrow_config().clone();
+    config.integer_variables = IntegerMode::None;
+    config.report_infeasible_subsystem = false;
+    config.require_nonempty_objective = false;
+
+    let feasibility_system = system.rebuild(
+        config,
+        |_, _| ObjectiveFunction::Maximise(Expression(vec![]), Frac::zero()),
+        |_, cons| cons,
+    );
+
+    match solve_with_simplex_tableaux(&feasibility_system) {
+        Ok(_) => Ok(true),
+        Err(e) if e.downcast_ref::<NoFeasibleSolution>().is_some() => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Find an irreducible infeasible subsystem of the given system, which is a set of constraints
+/// that has no feasible solution, but which would become feasible if any one of them was removed.
+///
+/// This uses a deletion filter: we try removing each constraint in turn, and if the rest are
+/// still infeasible, then that constraint isn't needed. This returns the indices of the
+/// constraints in the subsystem, or `None` if the system is actually feasible.
+#[instrument(skip(system))]
+pub fn find_irreducible_infeasible_subsystem(system: &LinProgSystem) -> Result<Option<Vec<usize>>> {
+    if is_feasible(system)? {
+        return Ok(None);
+    }
+
+    let mut subsystem: Vec<usize> = (0..system.num_constraints()).collect();
+    let mut idx = 0;
+
+    while idx < subsystem.len() {
+        let mut candidate = subsystem.clone();
+        candidate.remove(idx);
+
+        let candidate_system = system.rebuild(
+            system.borrow_config().clone(),
+            |_, obj_func| obj_func,
+            |_, cons| {
+                cons.into_iter()
+                    .enumerate()
+                    .filter(|(con_idx, _)| candidate.contains(con_idx))
+                    .map(|(_, con)| con)
+                    .collect()
+            },
+        );
+
+        if is_feasible(&candidate_system)? {
+            // This constraint is needed for the infeasibility, so we keep it
+            idx += 1;
+        } else {
+            subsystem = candidate;
+        }
+    }
+
+    debug!(?subsystem);
+    Ok(Some(subsystem))
+}
+
+impl LinProgSystem {
+    /// Find the constraints which are redundant, meaning that removing them doesn't change the
+    /// feasible region. This returns their indices, in order.
+    ///
+    /// For each constraint, we optimise its LHS subject to the other constraints. If the LHS can't
+    /// get past the constant, then the constraint can never be broken, so it's redundant. Once we
+    /// find a redundant constraint, we leave it out when checking the rest, so that only one of a
+    /// pair of duplicate constraints gets reported.
+    ///
+    /// An infeasible system has no region to change, so this just returns no constraints.
+    #[instrument(skip(self))]
+    pub fn redundant_constraints(&self) -> Result<Vec<usize>> {
+        if !is_feasible(self)? {
+            warn!("System is infeasible, so there are no redundant constraints to find");
+            return Ok(vec![]);
+        }
+
+        let mut config = self.borrow_config().clone();
+        config.integer_variables = IntegerMode::None;
+        config.report_infeasible_subsystem = false;
+        config.require_nonempty_objective = false;
+
+        let mut kept: Vec<usize> = (0..self.num_constraints()).collect();
+        let mut redundant = Vec::new();
+
+        for con_idx in 0..self.num_constraints() {
+            let comparison = self.with_constraints(|cons| cons[con_idx].comparison);
+            let constant = self.with_constraints(|cons| cons[con_idx].constant);
+
+            // A constraint like `<=` can only be broken by making the LHS bigger, and an equation
+            // can be broken both ways
+            let directions: &[bool] = match comparison {
+                Comparison::LessThan | Comparison::LessThanOrEqual => &[true],
+                Comparison::GreaterThan | Comparison::GreaterThanOrEqual => &[false],
+                Comparison::Equal => &[true, false],
+            };
+
+            let mut is_redundant = true;
+            for &maximise in directions {
+                let others = self.rebuild(
+                    config.clone(),
+                    |variables, _| {
+                        let exp = self.with_constraints(|cons| {
+                            cons[con_idx].var_expression.rebind(variables)
+                        });
+                        if maximise {
+                            ObjectiveFunction::Maximise(exp, Frac::zero())
+                        } else {
+                            ObjectiveFunction::Minimise(exp, Frac::zero())
+                        }
+                    },
+                    |_, cons| {
+                        cons.into_iter()
+                            .enumerate()
+                            .filter(|&(idx, _)| idx != con_idx && kept.contains(&idx))
+                            .map(|(_, con)| con)
+                            .collect()
+                    },
+                );
+
+                let value = match solve_with_simplex_tableaux(&others) {
+                    Ok(solution) => solution.objective_function_value,
+                    Err(e)
+                        if e.downcast_ref::<IterationError>()
+                            == Some(&IterationError::Unbounded)
+                            || e.downcast_ref::<UnconstrainedVariable>().is_some() =>
+                    {
+                        is_redundant = false;
+                        break;
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                let reachable = match comparison {
+                    Comparison::LessThan => value >= constant,
+                    Comparison::LessThanOrEqual => value > constant,
+                    Comparison::GreaterThan => value <= constant,
+                    Comparison::GreaterThanOrEqual => value < constant,
+                    Comparison::Equal if maximise => value > constant,
+                    Comparison::Equal => value < constant,
+                };
+                if reachable {
+                    is_redundant = false;
+                    break;
+                }
+            }
+
+            if is_redundant {
+                debug!(con_idx, "Constraint is redundant");
+                kept.retain(|&idx| idx != con_idx);
+                redundant.push(con_idx);
+            }
+        }
+
+        Ok(redundant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lin_prog::{
+        config::Config, constraint::Constraint, system::LinProgSystemBuilder, Variables,
+    };
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn find_irreducible_infeasible_subsystem_test() {
+        let system = LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config: Config::default(),
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("x + y", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("x + y <= 20", vars).unwrap().1,
+                    Constraint::nom_parse("x <= 4", vars).unwrap().1,
+                    Constraint::nom_parse("y <= 10", vars).unwrap().1,
+                    Constraint::nom_parse("x >= 6", vars).unwrap().1,
+                ]
+            },
+        }
+        .build();
+
+        assert!(!is_feasible(&system).unwrap());
+        assert_eq!(
+            find_irreducible_infeasible_subsystem(&system).unwrap(),
+            Some(vec![1, 3])
+        );
+
+        let feasible_system = system.rebuild(
+            Config::default(),
+            |_, obj_func| obj_func,
+            |_, cons| cons.into_iter().take(3).collect(),
+        );
+        assert!(is_feasible(&feasible_system).unwrap());
+        assert_eq!(
+            find_irreducible_infeasible_subsystem(&feasible_system).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn redundant_constraints_test() {
+        let system = LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config: Config::default(),
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("x + y", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("x <= 4", vars).unwrap().1,
+                    Constraint::nom_parse("y <= 10", vars).unwrap().1,
+                    Constraint::nom_parse("x + y <= 20", vars).unwrap().1,
+                    Constraint::nom_parse("x + 2y >= 0", vars).unwrap().1,
+                    Constraint::nom_parse("y <= 10", vars).unwrap().1,
+                ]
+            },
+        }
+        .build();
+
+        // Only one of the duplicate constraints gets reported
+        assert_eq!(system.redundant_constraints().unwrap(), vec![1, 2, 3]);
+
+        let tight_system = system.rebuild(
+            Config::default(),
+            |_, obj_func| obj_func,
+            |_, cons| cons.into_iter().take(2).collect(),
+        );
+        assert_eq!(
+            tight_system.redundant_constraints().unwrap(),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn check_integer_coefficients_test() {
+        let config = Config {
+            integer_coefficients_only: true,
+            ..Config::default()
+        };
+
+        let system = LinProgSystem::build_from_str_with_config(
+            "x y\nmaximise 3x + 2y\nx + 2.5y <= 10",
+            config.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            solve_with_simplex_tableaux(&system)
+                .unwrap_err()
+                .to_string(),
+            "Term 5/2y in constraint 1x + 5/2y ≤ 10 has a coefficient which isn't an integer"
+        );
+
+        let system = LinProgSystem::build_from_str_with_config(
+            "x y\nmaximise 0.5x + y\nx + y <= 10",
+            config.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            check_integer_coefficients(&system).unwrap_err().to_string(),
+            "Term 1/2x in the objective function has a coefficient which isn't an integer"
+        );
+
+        // Only the coefficients matter, not the constants
+        let system = LinProgSystem::build_from_str_with_config(
+            "x y\nmaximise 3x + 2y\nx + 2y <= 10.5",
+            config,
+        )
+        .unwrap();
+        assert!(check_integer_coefficients(&system).is_ok());
+
+        let lenient_system =
+            LinProgSystem::build_from_str("x y\nmaximise 3x + 2y\nx + 2.5y <= 10").unwrap();
+        assert!(check_integer_coefficients(&lenient_system).is_ok());
+    }
+
+    #[test]
+    #[traced_test]
+    fn check_objective_not_empty_test() {
+        let system = LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config: Config {
+                require_nonempty_objective: true,
+                ..Config::default()
+            },
+            objective_function_builder: |_| {
+                ObjectiveFunction::Maximise(Expression(vec![]), Frac::zero())
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("x + y <= 10", vars).unwrap().1,
+                    Constraint::nom_parse("x >= 2", vars).unwrap().1,
+                ]
+            },
+        }
+        .build();
+
+        assert_eq!(
+            solve_with_simplex_tableaux(&system)
+                .unwrap_err()
+                .to_string(),
+            "Objective function doesn't reference any variables"
+        );
+        assert!(is_feasible(&system).unwrap());
+
+        let lenient_system =
+            system.rebuild(Config::default(), |_, obj_func| obj_func, |_, cons| cons);
+        let solution = solve_with_simplex_tableaux(&lenient_system).unwrap();
+        assert_eq!(solution.objective_function_value, Frac::zero());
+        assert!(logs_contain(
+            "Objective function doesn't reference any variables"
+        ));
+    }
+}
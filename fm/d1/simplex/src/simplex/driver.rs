@@ -0,0 +1,109 @@
+//! This module handles driving a solve from outside, one pivot at a time, so that something like a
+//! REPL or a GUI can inspect the tableau between pivots. See [`SimplexDriver`].
+
+use super::{
+    diagnostics, outcome_error, solution_from_final_tableau, step_tableau, tableau::Tableau,
+    trace::PivotInfo, SolutionSet,
+};
+use crate::lin_prog::system::LinProgSystem;
+use color_eyre::Result;
+use tracing::{info, instrument};
+
+/// What happened when a [`SimplexDriver`] was asked to take a step.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The driver did one pivot.
+    Pivoted(PivotInfo),
+
+    /// The tableau is already optimal, so there's nothing left to do.
+    Optimal,
+
+    /// The system has no feasible solution.
+    Infeasible,
+
+    /// The objective function can be improved forever.
+    Unbounded,
+}
+
+/// A solve of a system which is advanced one pivot at a time with [`SimplexDriver::step`], rather
+/// than all at once. This does the same pivots as
+/// [`solve_with_simplex_tableaux`](super::solve_with_simplex_tableaux).
+#[derive(Clone, Debug)]
+pub struct SimplexDriver<'v> {
+    /// The system being solved.
+    system: &'v LinProgSystem,
+
+    /// The current tableau.
+    tableau: Tableau<'v>,
+
+    /// The number of pivots done so far.
+    iterations: usize,
+
+    /// How the solve finished, if it has. Every step after that gives the same outcome.
+    finished: Option<StepOutcome>,
+}
+
+impl<'v> SimplexDriver<'v> {
+    /// Start solving the given system from its initial tableau.
+    #[instrument(skip(system))]
+    pub fn new(system: &'v LinProgSystem) -> Result<Self> {
+        diagnostics::check_system(system)?;
+
+        let tableau = Tableau::create_initial(system)?;
+        info!(%tableau, "Initial tableau");
+
+        Ok(Self {
+            system,
+            tableau,
+            iterations: 0,
+            finished: None,
+        })
+    }
+
+    /// Do the next pivot, unless the solve has already finished.
+    ///
+    /// This only returns an error if the solve can't carry on for some other reason, like going
+    /// over the maximum number of iterations.
+    #[instrument(skip(self))]
+    pub fn step(&mut self) -> Result<StepOutcome> {
+        if let Some(outcome) = &self.finished {
+            return Ok(outcome.clone());
+        }
+
+        let outcome = step_tableau(self.system, &mut self.tableau, self.iterations, |_| ())?;
+        if let StepOutcome::Pivoted(_) = outcome {
+            self.iterations += 1;
+            info!(tableau = %self.tableau, iterations = self.iterations);
+        } else {
+            self.finished = Some(outcome.clone());
+        }
+
+        Ok(outcome)
+    }
+
+    /// The current tableau. Between steps, this is the tableau after the last pivot.
+    pub fn tableau(&self) -> &Tableau<'v> {
+        &self.tableau
+    }
+
+    /// The number of pivots done so far.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Get the solution once the solve has finished with [`StepOutcome::Optimal`]. Otherwise,
+    /// this returns the same error as
+    /// [`solve_with_simplex_tableaux`](super::solve_with_simplex_tableaux) would, or an error
+    /// saying that the tableau isn't optimal if the solve hasn't finished yet.
+    pub fn solution(self) -> Result<SolutionSet<'v>> {
+        if let Some(report) = self
+            .finished
+            .as_ref()
+            .and_then(|outcome| outcome_error(self.system, outcome))
+        {
+            return Err(report);
+        }
+
+        solution_from_final_tableau(self.system, self.tableau)
+    }
+}
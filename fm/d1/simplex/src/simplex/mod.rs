@@ -1,14 +1,41 @@
 //! This module handles execution of the actual simplex algorithm itself.
 
+pub mod branch_and_bound;
+pub mod continued_fraction;
+pub mod diagnostics;
+pub mod driver;
+pub mod parametric;
+mod serialize;
 mod tableau;
 #[cfg(test)]
 mod tests;
+pub mod trace;
 
-use self::tableau::Tableau;
-use crate::{lin_prog::system::LinProgSystem, Frac};
-use color_eyre::Result;
+use self::{
+    diagnostics::find_irreducible_infeasible_subsystem,
+    driver::StepOutcome,
+    tableau::{IterationError, NoFeasibleSolution, Tableau},
+    trace::PivotInfo,
+};
+use crate::{
+    lin_prog::{
+        comparison::Comparison, config::VariableNaming, constraint::Constraint,
+        expression::Expression, system::LinProgSystem, ObjectiveSense,
+    },
+    Frac,
+};
+use color_eyre::{Report, Result, Section, SectionExt};
+use fraction::{GenericFraction, Sign, ToPrimitive, Zero};
 use itertools::Itertools;
-use std::{cmp::Ordering, collections::HashMap, fmt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+    io::{self, Write},
+    iter,
+};
 use tracing::{info, instrument};
 
 /// The different types of variables that can be used in solving linear programming problems.
@@ -27,17 +54,76 @@ pub enum VariableType<'v> {
     Artificial(usize),
 }
 
-impl<'v> fmt::Display for VariableType<'v> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl<'v> VariableType<'v> {
+    /// The index of this slack, surplus, or artificial variable, or `None` for an original
+    /// variable, which has a name instead.
+    pub fn index(&self) -> Option<usize> {
+        match *self {
+            Self::Original(_) => None,
+            Self::Slack(idx) | Self::Surplus(idx) | Self::Artificial(idx) => Some(idx),
+        }
+    }
+
+    /// Is this an original variable from the system?
+    pub fn is_original(&self) -> bool {
+        matches!(self, Self::Original(_))
+    }
+
+    /// Is this a slack variable?
+    pub fn is_slack(&self) -> bool {
+        matches!(self, Self::Slack(_))
+    }
+
+    /// Is this a surplus variable?
+    pub fn is_surplus(&self) -> bool {
+        matches!(self, Self::Surplus(_))
+    }
+
+    /// Is this an artificial variable?
+    pub fn is_artificial(&self) -> bool {
+        matches!(self, Self::Artificial(_))
+    }
+
+    /// Display this variable with the given naming scheme for slack, surplus, and artificial
+    /// variables. The [`Display`](fmt::Display) impl uses [`VariableNaming::Symbolic`].
+    pub fn display_with(&self, naming: VariableNaming) -> String {
+        let (symbol, letter, idx) = match *self {
+            Self::Original(name) => return name.to_string(),
+            Self::Slack(idx) => ("sl#", 's', idx),
+            Self::Surplus(idx) => ("su#", 'e', idx),
+            Self::Artificial(idx) => ("ar#", 'a', idx),
+        };
+
+        match naming {
+            VariableNaming::Symbolic => format!("{symbol}{idx}"),
+            VariableNaming::Numbered => format!("{letter}{}", idx + 1),
+            VariableNaming::Subscripted => iter::once(letter)
+                .chain((idx + 1).to_string().chars().map(|digit| {
+                    let digit = digit.to_digit(10).expect("Should only have decimal digits");
+                    char::from_u32('₀' as u32 + digit).expect("Subscript digits should be valid")
+                }))
+                .collect(),
+        }
+    }
+
+    /// The rank of this type of variable, which orders the different types of variable as
+    /// original < slack < surplus < artificial.
+    fn rank(&self) -> u8 {
         match self {
-            Self::Original(name) => write!(f, "{name}"),
-            Self::Slack(num) => write!(f, "sl#{num}"),
-            Self::Surplus(num) => write!(f, "su#{num}"),
-            Self::Artificial(num) => write!(f, "ar#{num}"),
+            Self::Original(_) => 0,
+            Self::Slack(_) => 1,
+            Self::Surplus(_) => 2,
+            Self::Artificial(_) => 3,
         }
     }
 }
 
+impl<'v> fmt::Display for VariableType<'v> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_with(VariableNaming::Symbolic))
+    }
+}
+
 impl<'v> PartialOrd for VariableType<'v> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -48,22 +134,14 @@ impl<'v> Ord for VariableType<'v> {
     fn cmp(&self, other: &Self) -> Ordering {
         use VariableType::*;
 
-        match (*self, *other) {
-            (Original(a), Original(b)) => a.cmp(b),
-            (Slack(a), Slack(b)) => a.cmp(&b),
-            (Surplus(a), Surplus(b)) => a.cmp(&b),
-            (Artificial(a), Artificial(b)) => a.cmp(&b),
-
-            // Original < Slack < Surplus < Artificial
-            (Original(_), _) => Ordering::Less,
-            (_, Original(_)) => Ordering::Greater,
-            (Slack(_), Surplus(_)) => Ordering::Less,
-            (Slack(_), Artificial(_)) => Ordering::Less,
-            (Surplus(_), Slack(_)) => Ordering::Greater,
-            (Surplus(_), Artificial(_)) => Ordering::Less,
-            (Artificial(_), Surplus(_)) => Ordering::Greater,
-            (Artificial(_), Slack(_)) => Ordering::Greater,
-        }
+        // Different types of variable are ordered by rank, and variables of the same type are
+        // ordered by their name or index
+        self.rank()
+            .cmp(&other.rank())
+            .then_with(|| match (*self, *other) {
+                (Original(a), Original(b)) => a.cmp(b),
+                _ => self.index().cmp(&other.index()),
+            })
     }
 }
 
@@ -78,37 +156,639 @@ pub struct Equation<'v> {
 }
 
 /// A solution to a linear programming problem.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// This can be serialized and deserialized to save and load it, with the fractions in the format
+/// of [`frac_to_json`]. Deserializing it interns the names, so it can load a `SolutionSet<'static>`
+/// from anything.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SolutionSet<'v> {
     /// The value of the objective function for the optimal point.
+    #[serde(with = "serialize::frac")]
     objective_function_value: Frac,
 
     /// The values of the variables at the optimal point.
+    #[serde(with = "serialize::frac_map")]
     variable_values: HashMap<VariableType<'v>, Frac>,
+
+    /// The variables which were basic in the final tableau. This is empty for integer solutions.
+    #[serde(serialize_with = "serialize::sorted_variables")]
+    basic_variables: HashSet<VariableType<'v>>,
+
+    /// The shadow price of each constraint, keyed by its index in the system. This is empty for
+    /// integer solutions. See [`SolutionSet::shadow_prices`].
+    #[serde(with = "serialize::frac_map")]
+    shadow_prices: HashMap<usize, Frac>,
+
+    /// Whether the final tableau had a non-basic variable with a 0 in the objective row, meaning
+    /// that it could enter the basis without changing the value of the objective function. This is
+    /// always false for integer solutions.
+    has_alternative_optima: bool,
+
+    /// The name of the objective function, if the system gave it one. See
+    /// [`SolutionSet::objective_name`].
+    #[serde(with = "serialize::optional_name")]
+    objective_name: serialize::OptionalName<'v>,
+
+    /// How to name the slack, surplus, and artificial variables when displaying the solution.
+    variable_naming: VariableNaming,
 }
 
 impl<'v> fmt::Display for SolutionSet<'v> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "\nObjFunc# = {}", self.objective_function_value)?;
+        write!(
+            f,
+            "\n{} = {}",
+            self.objective_name(),
+            self.objective_function_value
+        )?;
+        if self.has_alternative_optima {
+            write!(f, " (alternative optima exist)")?;
+        }
         for (var, value) in self
             .variable_values
             .iter()
             .sorted_by_key(|&(var_type, _)| var_type)
         {
-            write!(f, "\n{var} = {value}")?;
+            write!(f, "\n{} = {value}", var.display_with(self.variable_naming))?;
         }
         Ok(())
     }
 }
 
+impl<'v> SolutionSet<'v> {
+    /// Convert the solution to JSON, in the same format that it gets serialized in. The values
+    /// are in the format of [`frac_to_json`], and the variables are keyed by their symbolic names.
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).expect("Serializing a solution to JSON should never fail")
+    }
+
+    /// Get the name of the objective function, like `C` in `minimise C = 3x + 2y`, or `ObjFunc#`
+    /// if it wasn't given a name.
+    pub fn objective_name(&self) -> &'v str {
+        self.objective_name.unwrap_or("ObjFunc#")
+    }
+
+    /// Return the number of basic and non-basic variables at the optimum, in that order. Basic
+    /// variables are the ones with a row in the final tableau.
+    pub fn basis_summary(&self) -> (usize, usize) {
+        let basic = self.basic_variables.len();
+        (basic, self.variable_values.len() - basic)
+    }
+
+    /// Get the shadow price (or dual value) of each constraint, keyed by the index of the
+    /// constraint in the system.
+    ///
+    /// The shadow price of a constraint is the rate at which the optimal value of the objective
+    /// function changes as the constant on the right hand side of the constraint increases, as
+    /// long as the final basis stays optimal. This convention is the same for maximising and
+    /// minimising, so when maximising, a binding `≤` constraint has a positive shadow price and a
+    /// binding `≥` constraint has a negative one, and when minimising it's the other way round.
+    /// Constraints which aren't binding always have a shadow price of 0.
+    ///
+    /// They come from the bottom row of the final tableau, under the slack and surplus columns.
+    /// Integer solutions don't come from a single tableau, so they don't have any shadow prices.
+    pub fn shadow_prices(&self) -> &HashMap<usize, Frac> {
+        &self.shadow_prices
+    }
+
+    /// Are there other optimal solutions with the same value of the objective function? See
+    /// [`Tableau::has_alternative_optima`].
+    pub fn has_alternative_optima(&self) -> bool {
+        self.has_alternative_optima
+    }
+
+    /// Turn the values of the original variables in this solution into constraints which fix each
+    /// variable at its value, so that the only feasible point of a system with these constraints
+    /// is this solution.
+    ///
+    /// The tableau doesn't support `=` constraints, so each variable gets a pair of `≤` and `≥`
+    /// constraints instead. They're sorted by variable name.
+    pub fn as_fixing_constraints(&self) -> Vec<Constraint<'v>> {
+        self.variable_values
+            .iter()
+            .filter_map(|(var, &value)| match *var {
+                VariableType::Original(name) => Some((name, value)),
+                _ => None,
+            })
+            .sorted_by_key(|&(name, _)| name)
+            .flat_map(|(name, value)| {
+                [Comparison::LessThanOrEqual, Comparison::GreaterThanOrEqual].map(|comparison| {
+                    Constraint {
+                        var_expression: Expression(vec![(1.into(), name)]),
+                        comparison,
+                        constant: value,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+/// Convert a fraction to a JSON object with its exact numerator and denominator, and a decimal
+/// approximation. The sign goes on the numerator. Infinite and NaN values have null fields.
+fn frac_to_json(frac: &Frac) -> Value {
+    match (frac.numer(), frac.denom()) {
+        (Some(&numer), Some(&denom)) => {
+            let numer = if frac.is_sign_negative() {
+                -i128::from(numer)
+            } else {
+                i128::from(numer)
+            };
+
+            json!({
+                "numerator": serde_json::to_value(numer).unwrap_or(Value::String(numer.to_string())),
+                "denominator": denom,
+                "decimal": frac_to_f64(frac),
+            })
+        }
+        _ => json!({
+            "numerator": null,
+            "denominator": null,
+            "decimal": null,
+        }),
+    }
+}
+
+/// Convert a fraction to the nearest `f64`, for showing it as a decimal. Infinite values become
+/// infinite floats with the same sign, and NaN becomes NaN. Every decimal output should use this,
+/// so that they all round the same way.
+pub fn frac_to_f64(frac: &Frac) -> f64 {
+    match frac {
+        GenericFraction::Rational(..) => frac.to_f64().unwrap_or(f64::NAN),
+        GenericFraction::Infinity(Sign::Plus) => f64::INFINITY,
+        GenericFraction::Infinity(Sign::Minus) => f64::NEG_INFINITY,
+        GenericFraction::NaN => f64::NAN,
+    }
+}
+
+/// Round the given value to the nearest integer if it's within `tolerance` of it, otherwise
+/// return it unchanged.
+///
+/// This is for values that come from inexact input, like `2.9999999`, which should really be
+/// `3`. Every solution path in this crate is exact, so only the tests use this for now.
+#[cfg(test)]
+pub(crate) fn snap_near_integer(value: Frac, tolerance: Frac) -> Frac {
+    let rounded = value.round();
+    if (value - rounded).abs() <= tolerance {
+        rounded
+    } else {
+        value
+    }
+}
+
 /// Solve the given linear programming system using simplex tableaux.
-#[instrument(skip(system))]
 pub fn solve_with_simplex_tableaux<'v>(system: &'v LinProgSystem) -> Result<SolutionSet<'v>> {
+    solve_with_simplex_tableaux_verbose(system, &mut io::sink())
+}
+
+/// A solution from [`solve_matrix`]. Unlike a [`SolutionSet`], this owns all of its data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatrixSolution {
+    /// The value of the objective function at the optimal point.
+    pub objective_function_value: Frac,
+
+    /// The value of each variable at the optimal point, in the same order as the columns of the
+    /// matrix, so the value of `x0` comes first.
+    pub variable_values: Vec<Frac>,
+}
+
+/// Solve a problem in matrix form, to maximise or minimise `c·x` subject to `A x ? b`, where each
+/// `?` is the comparison for that row of `a`. See [`LinProgSystem::from_matrix`] for how the
+/// system is built, and which dimensions are invalid.
+///
+/// A [`SolutionSet`] borrows the variable names from its system, but the system only lives as
+/// long as this function, so we return a [`MatrixSolution`] which owns everything instead.
+#[instrument]
+pub fn solve_matrix(
+    c: &[Frac],
+    a: &[Vec<Frac>],
+    b: &[Frac],
+    comparisons: &[Comparison],
+    maximise: bool,
+) -> Result<MatrixSolution> {
+    let sense = if maximise {
+        ObjectiveSense::Maximise
+    } else {
+        ObjectiveSense::Minimise
+    };
+    let system = LinProgSystem::from_matrix(c, a, b, comparisons, sense)?;
+    let solution = solve_with_simplex_tableaux(&system)?;
+
+    let values: HashMap<&str, Frac> = solution
+        .variable_values
+        .iter()
+        .filter_map(|(var, &value)| match var {
+            VariableType::Original(name) => Some((*name, value)),
+            _ => None,
+        })
+        .collect();
+    let variable_values = (0..c.len())
+        .map(|idx| {
+            values
+                .get(format!("x{idx}").as_str())
+                .copied()
+                .unwrap_or_else(Frac::zero)
+        })
+        .collect();
+
+    Ok(MatrixSolution {
+        objective_function_value: solution.objective_function_value,
+        variable_values,
+    })
+}
+
+/// Solve the given linear programming system using simplex tableaux, writing the initial tableau,
+/// the tableau for every iteration with its theta values and row operations, and the final
+/// tableau to the given writer. This shows all the working without needing any logging.
+#[instrument(skip(system, writer))]
+pub fn solve_with_simplex_tableaux_verbose<'v, W: Write>(
+    system: &'v LinProgSystem,
+    writer: &mut W,
+) -> Result<SolutionSet<'v>> {
+    // We can't return an error from inside the closure, so we keep the first one for later
+    let mut write_result = Ok(());
+    let solution = solve_showing_working(system, &mut |_, _| (), &mut |title, tableau| {
+        if write_result.is_ok() {
+            write_result = writeln!(writer, "{title}:{tableau}\n");
+        }
+    });
+
+    write_result?;
+    solution
+}
+
+/// Solve the given linear programming system using simplex tableaux, writing the initial tableau
+/// to the given writer, and then for every iteration, a sentence explaining why each variable
+/// enters and leaves the basis, followed by the resulting tableau. See
+/// [`Tableau::explain_next_pivot`].
+#[instrument(skip(system, writer))]
+pub fn solve_with_simplex_tableaux_explained<'v, W: Write>(
+    system: &'v LinProgSystem,
+    writer: &mut W,
+) -> Result<SolutionSet<'v>> {
+    diagnostics::check_system(system)?;
+
+    let mut tableau: Tableau = Tableau::create_initial(system)?;
+    writeln!(writer, "Initial tableau:{tableau}\n")?;
+
+    // We can't return an error from inside the closure, so we keep the first one for later
+    let mut write_result = Ok(());
+    let result = iterate_until_optimal(
+        system,
+        &mut tableau,
+        0,
+        &mut |_, _| (),
+        &mut |_, _| (),
+        Some(&mut |title, before, after| {
+            if write_result.is_ok() {
+                let explanation = before.explain_next_pivot();
+                write_result = writeln!(writer, "{title}: {explanation}{after}\n");
+            }
+        }),
+    );
+
+    write_result?;
+    result?;
+    solution_from_final_tableau(system, tableau)
+}
+
+/// Solve the given linear programming system using simplex tableaux, calling `progress` after
+/// every iteration with the number of iterations done so far and the current value of the
+/// objective function.
+///
+/// If the system needs integer solutions, then `progress` also gets called after every node of
+/// [`branch_and_bound`](branch_and_bound::branch_and_bound_with_progress), counting on from the
+/// iterations, with the best objective value found so far.
+#[instrument(skip(system, progress))]
+pub fn solve_with_simplex_tableaux_and_progress<'v>(
+    system: &'v LinProgSystem,
+    mut progress: impl FnMut(usize, Frac),
+) -> Result<SolutionSet<'v>> {
+    solve_showing_working(system, &mut progress, &mut |_, _| ())
+}
+
+/// Solve the given linear programming system using simplex tableaux, returning the final optimal
+/// tableau rather than just the solution. The solution can be found from it with
+/// [`solution_from_final_tableau`].
+#[instrument(skip(system))]
+pub fn solve_to_final_tableau<'v>(system: &'v LinProgSystem) -> Result<Tableau<'v>> {
+    final_tableau_showing_working(system, &mut |_, _| (), &mut |_, _| ())
+}
+
+/// Solve the given linear programming system using simplex tableaux, calling `progress` after
+/// every iteration, and calling `working` with a title and each tableau that should be shown as
+/// part of the working.
+fn solve_showing_working<'v>(
+    system: &'v LinProgSystem,
+    progress: &mut impl FnMut(usize, Frac),
+    working: &mut impl FnMut(&str, &Tableau),
+) -> Result<SolutionSet<'v>> {
+    let mut iterations = 0;
+    let tableau = final_tableau_showing_working(
+        system,
+        &mut |i, value| {
+            iterations = i;
+            progress(i, value);
+        },
+        working,
+    )?;
+
+    solution_from_final_tableau_and_progress(system, tableau, &mut |nodes, best| {
+        progress(iterations + nodes, best)
+    })
+}
+
+/// Get the solution to the system from its final optimal tableau. If the system needs integer
+/// solutions, then the solution from the tableau is just the relaxation, so we use
+/// [`branch_and_bound`](branch_and_bound::branch_and_bound) to find the integer solution.
+pub fn solution_from_final_tableau<'v>(
+    system: &'v LinProgSystem,
+    tableau: Tableau<'v>,
+) -> Result<SolutionSet<'v>> {
+    solution_from_final_tableau_and_progress(system, tableau, &mut |_, _| ())
+}
+
+/// Get the solution like [`solution_from_final_tableau`], calling `progress` after every node of
+/// branch and bound, if there is any.
+fn solution_from_final_tableau_and_progress<'v>(
+    system: &'v LinProgSystem,
+    tableau: Tableau<'v>,
+    progress: &mut impl FnMut(usize, Frac),
+) -> Result<SolutionSet<'v>> {
+    let relaxation = tableau.get_solution()?;
+    let solution = if system.borrow_config().integer_variables.requires_integers() {
+        branch_and_bound::branch_and_bound_with_progress(system, relaxation, progress)?
+    } else {
+        relaxation
+    };
+
+    Ok(named_by_config(system, solution))
+}
+
+/// Solve the given linear programming system, returning the solution of its continuous
+/// relaxation, which ignores any integer requirements, and then its actual solution. The gap
+/// between their objective values shows how much the integer requirements cost. If the system
+/// doesn't need integer solutions, then both solutions are the same.
+#[instrument(skip(system))]
+pub fn solve_with_both<'v>(
+    system: &'v LinProgSystem,
+) -> Result<(SolutionSet<'v>, SolutionSet<'v>)> {
+    let relaxation = named_by_config(system, solve_to_final_tableau(system)?.get_solution()?);
+    let solution = if system.borrow_config().integer_variables.requires_integers() {
+        named_by_config(
+            system,
+            branch_and_bound::branch_and_bound(system, relaxation.clone())?,
+        )
+    } else {
+        relaxation.clone()
+    };
+
+    Ok((relaxation, solution))
+}
+
+/// Give the solution the objective name and variable naming from the config of the system.
+fn named_by_config<'v>(system: &'v LinProgSystem, solution: SolutionSet<'v>) -> SolutionSet<'v> {
+    SolutionSet {
+        objective_name: system.borrow_config().objective_name.as_deref(),
+        variable_naming: system.borrow_config().variable_naming,
+        ..solution
+    }
+}
+
+/// Like [`solve_showing_working`], but return the final tableau instead of its solution.
+fn final_tableau_showing_working<'v>(
+    system: &'v LinProgSystem,
+    progress: &mut impl FnMut(usize, Frac),
+    working: &mut impl FnMut(&str, &Tableau),
+) -> Result<Tableau<'v>> {
+    diagnostics::check_system(system)?;
+
     let mut tableau: Tableau = Tableau::create_initial(system)?;
     info!(%tableau, "Initial tableau");
-    while tableau.negatives_in_bottom_row() {
-        tableau.do_iteration()?;
+    working("Initial tableau", &tableau);
+
+    iterate_until_optimal(system, &mut tableau, 0, progress, working, None)?;
+    working("Final tableau", &tableau);
+    Ok(tableau)
+}
+
+/// Solve the given linear programming system, starting from the basis of a previous tableau. This
+/// is useful when re-solving a slightly modified system, since the previous optimal basis is often
+/// close to optimal for the new system.
+///
+/// If the basis isn't feasible for the new system, then we use the dual simplex algorithm to make
+/// it feasible. See [`solve_from_basis`] for when the basis can't be used.
+pub fn solve_warm_start<'v>(
+    system: &'v LinProgSystem,
+    previous: &Tableau,
+) -> Result<SolutionSet<'v>> {
+    solve_warm_start_and_progress(system, previous, |_, _| ())
+}
+
+/// Solve the given linear programming system, starting from the basis of a previous tableau. See
+/// [`solve_warm_start`] and [`solve_with_simplex_tableaux_and_progress`].
+#[instrument(skip(system, previous, progress))]
+pub fn solve_warm_start_and_progress<'v>(
+    system: &'v LinProgSystem,
+    previous: &Tableau,
+    progress: impl FnMut(usize, Frac),
+) -> Result<SolutionSet<'v>> {
+    solve_from_basis_and_progress(system, previous.basis(), progress)
+}
+
+/// Solve the given linear programming system, starting from a tableau where the given variables
+/// are basic, rather than the slack and artificial variables. This is like [`solve_warm_start`],
+/// but the basis doesn't have to come from a tableau, so it could be saved from a previous solve.
+///
+/// The basis must have one variable for each row of the initial tableau, with no repeats and no
+/// artificial variables, otherwise we return an error. If the basis is neither feasible nor
+/// optimal for the system, then neither the primal nor the dual simplex algorithm can start from
+/// it, so we just solve from scratch.
+pub fn solve_from_basis<'v>(
+    system: &'v LinProgSystem,
+    basis: Vec<VariableType<'_>>,
+) -> Result<SolutionSet<'v>> {
+    solve_from_basis_and_progress(system, basis, |_, _| ())
+}
+
+/// Solve the given linear programming system, starting from the given basis. See
+/// [`solve_from_basis`] and [`solve_with_simplex_tableaux_and_progress`].
+#[instrument(skip(system, progress))]
+pub fn solve_from_basis_and_progress<'v>(
+    system: &'v LinProgSystem,
+    basis: Vec<VariableType<'_>>,
+    mut progress: impl FnMut(usize, Frac),
+) -> Result<SolutionSet<'v>> {
+    diagnostics::check_system(system)?;
+
+    let mut tableau = Tableau::create_with_basis(system, &basis)?;
+    info!(%tableau, "Warm started tableau");
+
+    let mut iterations = 0;
+    if tableau.negatives_in_value_column() {
+        if tableau.negatives_in_bottom_row() {
+            info!("Warm started basis is neither feasible nor optimal, so solving from scratch");
+            return solve_with_simplex_tableaux_and_progress(system, progress);
+        }
+
+        while tableau.negatives_in_value_column() {
+            check_iteration_limit(system, iterations)?;
+            if let Err(e) = tableau.do_dual_iteration() {
+                return Err(report_infeasibility(system, e));
+            }
+            iterations += 1;
+            progress(iterations, tableau.objective_function_value());
+        }
+    }
+
+    iterate_until_optimal(
+        system,
+        &mut tableau,
+        iterations,
+        &mut progress,
+        &mut |_, _| (),
+        None,
+    )?;
+    solution_from_final_tableau(system, tableau)
+}
+
+/// A closure which gets called with the title of an iteration, the tableau from before it, and
+/// the resulting tableau. See [`iterate_until_optimal`].
+type ObserveIteration<'o> = dyn FnMut(&str, &Tableau, &Tableau) + 'o;
+
+/// Do iterations of the simplex algorithm on the tableau until it's optimal, counting the
+/// iterations from `iterations` and calling `progress` after each one. The `working` closure gets
+/// the tableau for each iteration once its row operations are populated.
+///
+/// If there is an `observe` closure, then it gets called after each iteration with its title, the
+/// tableau from before the iteration, and the resulting tableau, even if the iteration found
+/// that the system is infeasible or unbounded. The tableau is only copied if it's needed.
+fn iterate_until_optimal(
+    system: &LinProgSystem,
+    tableau: &mut Tableau,
+    mut iterations: usize,
+    progress: &mut impl FnMut(usize, Frac),
+    working: &mut impl FnMut(&str, &Tableau),
+    mut observe: Option<&mut ObserveIteration>,
+) -> Result<()> {
+    loop {
+        let title = format!("Iteration {}", iterations + 1);
+        let before =
+            (observe.is_some() && tableau.negatives_in_bottom_row()).then(|| tableau.clone());
+        let outcome = step_tableau(system, tableau, iterations, |tableau| {
+            working(&title, tableau)
+        })?;
+        if let (Some(observe), Some(before)) = (&mut observe, &before) {
+            observe(&title, before, tableau);
+        }
+
+        match outcome {
+            StepOutcome::Pivoted(_) => (),
+            StepOutcome::Optimal => return Ok(()),
+            StepOutcome::Infeasible | StepOutcome::Unbounded => {
+                return Err(outcome_error(system, &outcome)
+                    .expect("An infeasible or unbounded outcome should have an error"))
+            }
+        }
+        iterations += 1;
+        progress(iterations, tableau.objective_function_value());
+    }
+}
+
+/// Do the next pivot on the tableau, which has already had `iterations` pivots, unless it's
+/// optimal. The `working` closure gets the tableau once its row operations are populated.
+///
+/// This is the single step shared by [`iterate_until_optimal`] and
+/// [`SimplexDriver::step`](driver::SimplexDriver::step), so that they always check the iteration
+/// limit and handle the end of the solve in the same way. Only going over the iteration limit or
+/// finding a zero pivot returns an error. See
+/// [`outcome_error`] for turning the other outcomes into errors.
+fn step_tableau(
+    system: &LinProgSystem,
+    tableau: &mut Tableau,
+    iterations: usize,
+    mut working: impl FnMut(&Tableau),
+) -> Result<StepOutcome> {
+    if !tableau.negatives_in_bottom_row() {
+        return Ok(StepOutcome::Optimal);
     }
 
-    Ok(tableau.get_solution())
+    check_iteration_limit(system, iterations)?;
+    let pivot = tableau.next_pivot();
+    let mut row_operations = Vec::new();
+    let result = tableau.do_iteration_showing_working(|tableau| {
+        row_operations = tableau.row_operations();
+        working(tableau);
+    });
+
+    match result {
+        Ok(()) => {
+            let (entering, leaving) =
+                pivot.expect("Every successful iteration should have a pivot");
+            Ok(StepOutcome::Pivoted(PivotInfo {
+                iteration: iterations + 1,
+                entering: entering.to_string(),
+                leaving: leaving.to_string(),
+                row_operations,
+            }))
+        }
+        Err(IterationError::NoFeasibleSolution(_)) => Ok(StepOutcome::Infeasible),
+        Err(IterationError::Unbounded) => Ok(StepOutcome::Unbounded),
+        Err(e @ IterationError::ZeroPivot(_)) => Err(Report::new(e)),
+    }
+}
+
+/// The error for a solve of the given system which finished with this outcome, or `None` if the
+/// outcome doesn't stop the solve from finding a solution. An infeasible system gets reported
+/// with [`report_infeasibility`].
+fn outcome_error(system: &LinProgSystem, outcome: &StepOutcome) -> Option<Report> {
+    match outcome {
+        StepOutcome::Infeasible => Some(report_infeasibility(system, NoFeasibleSolution)),
+        StepOutcome::Unbounded => Some(Report::new(IterationError::Unbounded)),
+        StepOutcome::Pivoted(_) | StepOutcome::Optimal => None,
+    }
+}
+
+/// Return an error if we've already done the maximum number of iterations allowed by the config.
+fn check_iteration_limit(system: &LinProgSystem, iterations: usize) -> Result<()> {
+    let max_iterations = system.borrow_config().max_iterations;
+    if iterations >= max_iterations {
+        Err(Report::msg(format!(
+            "Exceeded {max_iterations} iterations without converging"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Turn the infeasibility of the given system into a report, including an irreducible infeasible
+/// subsystem if the config asks for one.
+fn report_infeasibility(system: &LinProgSystem, err: NoFeasibleSolution) -> Report {
+    let report = Report::new(err);
+
+    if !system.borrow_config().report_infeasible_subsystem {
+        return report;
+    }
+
+    match find_irreducible_infeasible_subsystem(system) {
+        Ok(Some(indices)) => {
+            let ascii = system.borrow_config().ascii_output;
+            let constraints = system.with_constraints(|cons| {
+                indices
+                    .iter()
+                    .map(|&idx| {
+                        let constraint = if ascii {
+                            cons[idx].to_ascii_string()
+                        } else {
+                            cons[idx].to_string()
+                        };
+                        format!("{}: {constraint}", idx + 1)
+                    })
+                    .join("\n")
+            });
+            report.section(constraints.header("Irreducible infeasible subsystem:"))
+        }
+        Ok(None) => report,
+        Err(e) => report.section(format!("{e}").header("Failed to find infeasible subsystem:")),
+    }
 }
@@ -0,0 +1,240 @@
+//! This module handles parametric linear programming, where we solve the same system many times
+//! while changing one of its values, to see how the optimum changes. This includes solving a batch
+//! of different objective functions over the same constraints.
+
+use super::{solve_with_simplex_tableaux, SolutionSet, VariableType};
+use crate::{
+    lin_prog::{system::LinProgSystem, ObjectiveFunction},
+    Frac,
+};
+use color_eyre::Result;
+use std::{sync::mpsc, thread};
+use tracing::{debug, instrument};
+
+/// Solve the system once for each of the given values of the constant on the right of the
+/// constraint at `constraint_idx`, and return each value with the optimal value of the objective
+/// function for it.
+///
+/// Each value gets its own result, since some values may make the system infeasible or unbounded
+/// while others don't. This function will panic if there's no constraint at `constraint_idx`.
+#[instrument(skip(system))]
+pub fn solve_parametric_rhs(
+    system: &LinProgSystem,
+    constraint_idx: usize,
+    values: &[Frac],
+) -> Vec<(Frac, Result<Frac>)> {
+    values
+        .iter()
+        .map(|&value| {
+            let modified_system = system.with_constraint_constant(constraint_idx, value);
+            let result = solve_with_simplex_tableaux(&modified_system)
+                .map(|solution| solution.objective_function_value);
+            debug!(%value, ?result);
+            (value, result)
+        })
+        .collect()
+}
+
+/// Solve the system once for each of the given objective functions, keeping its constraints, and
+/// return the results in the same order as the objective functions.
+///
+/// Every variable in the objective functions must be in the system, otherwise this function will
+/// panic. See [`solve_objectives_parallel`] to solve them at the same time.
+#[instrument(skip_all)]
+pub fn solve_objectives<'v>(
+    system: &'v LinProgSystem,
+    objectives: &[ObjectiveFunction<'_>],
+) -> Vec<Result<SolutionSet<'v>>> {
+    objectives
+        .iter()
+        .map(|objective| solve_objective(system, objective))
+        .collect()
+}
+
+/// Solve the system for each of the given objective functions like [`solve_objectives`], but give
+/// each one its own thread. The solutions get sent back over a channel as they finish, and then
+/// they're put back in the same order as the objective functions.
+#[instrument(skip_all)]
+pub fn solve_objectives_parallel<'v>(
+    system: &'v LinProgSystem,
+    objectives: &[ObjectiveFunction<'_>],
+) -> Vec<Result<SolutionSet<'v>>> {
+    let (tx, rx) = mpsc::channel();
+
+    let mut results: Vec<(usize, Result<SolutionSet<'v>>)> = thread::scope(|scope| {
+        let mut handles = Vec::new();
+        for (idx, objective) in objectives.iter().enumerate() {
+            let tx_new = tx.clone();
+            handles.push(scope.spawn(move || {
+                tx_new
+                    .send((idx, solve_objective(system, objective)))
+                    .unwrap();
+            }));
+        }
+        drop(tx);
+
+        let results = rx.into_iter().collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        results
+    });
+
+    results.sort_by_key(|&(idx, _)| idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Solve a copy of the system with the given objective function, and rebind the solution to the
+/// variables of the original system, since the copy only lives for this function.
+fn solve_objective<'v>(
+    system: &'v LinProgSystem,
+    objective: &ObjectiveFunction<'_>,
+) -> Result<SolutionSet<'v>> {
+    let modified_system = system.rebuild(
+        system.borrow_config().clone(),
+        |variables, _| objective.rebind(variables),
+        |_, cons| cons,
+    );
+    let solution = solve_with_simplex_tableaux(&modified_system)?;
+    debug!(%objective, value = %solution.objective_function_value);
+
+    let variables = &system.borrow_variables().0;
+    let rebind = |var: VariableType<'_>| match var {
+        VariableType::Original(name) => VariableType::Original(
+            variables
+                .get(name)
+                .expect("Every variable in the copy should be in the original system")
+                .as_str(),
+        ),
+        VariableType::Slack(idx) => VariableType::Slack(idx),
+        VariableType::Surplus(idx) => VariableType::Surplus(idx),
+        VariableType::Artificial(idx) => VariableType::Artificial(idx),
+    };
+
+    Ok(SolutionSet {
+        variable_values: solution
+            .variable_values
+            .into_iter()
+            .map(|(var, value)| (rebind(var), value))
+            .collect(),
+        basic_variables: solution.basic_variables.into_iter().map(rebind).collect(),
+        objective_name: system.borrow_config().objective_name.as_deref(),
+        ..solution
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lin_prog::{
+            config::Config, constraint::Constraint, expression::Expression,
+            system::LinProgSystemBuilder, Variables,
+        },
+        simplex::tests::ch7_example8,
+    };
+    use fraction::Zero;
+    use itertools::Itertools;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn solve_parametric_rhs_test() {
+        let system = ch7_example8();
+
+        let values: Vec<Frac> = [0, 35, 70, 105, 140, 175]
+            .into_iter()
+            .map(Frac::from)
+            .collect();
+        let results = solve_parametric_rhs(&system, 0, &values);
+        assert_eq!(
+            results.iter().map(|&(value, _)| value).collect_vec(),
+            values
+        );
+
+        let objectives: Vec<Frac> = results
+            .into_iter()
+            .map(|(_, result)| result.unwrap())
+            .collect();
+        assert_eq!(objectives[0], Frac::zero());
+        assert_eq!(objectives[2], 26.into());
+
+        // Loosening a ≤ constraint in a maximisation problem can never make the optimum worse, and
+        // once the other constraint takes over, the optimum stops changing
+        assert!(objectives.iter().tuple_windows().all(|(a, b)| a <= b));
+        assert_eq!(objectives[4], 40.into());
+        assert_eq!(objectives[5], 40.into());
+
+        // The original system shouldn't be changed
+        system.with_constraints(|cons| assert_eq!(cons[0].constant, 70.into()));
+    }
+
+    #[test]
+    #[traced_test]
+    fn solve_parametric_rhs_infeasible_test() {
+        let system = LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config: Config::default(),
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("x + y", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("x + y <= 10", vars).unwrap().1,
+                    Constraint::nom_parse("x >= 4", vars).unwrap().1,
+                ]
+            },
+        }
+        .build();
+
+        let results = solve_parametric_rhs(&system, 1, &[2.into(), 8.into(), 12.into()]);
+        assert_eq!(results[0].1.as_ref().unwrap(), &Frac::from(10));
+        assert_eq!(results[1].1.as_ref().unwrap(), &Frac::from(10));
+        assert!(results[2].1.is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn solve_objectives_parallel_test() {
+        let system = ch7_example8();
+        let variables = Variables::from(["x", "y"]);
+        let objectives: Vec<ObjectiveFunction> = [
+            "maximise 3x + 2y",
+            "maximise x + 4y",
+            "minimise x + y",
+            "maximise 2x - y",
+            "minimise -x - y",
+        ]
+        .into_iter()
+        .map(|objective| ObjectiveFunction::parse(objective, &variables).unwrap())
+        .collect();
+
+        let sequential = solve_objectives(&system, &objectives);
+        let parallel = solve_objectives_parallel(&system, &objectives);
+        assert_eq!(parallel.len(), objectives.len());
+
+        for (sequential, parallel) in sequential.iter().zip(&parallel) {
+            assert_eq!(sequential.as_ref().ok(), parallel.as_ref().ok());
+        }
+
+        let values: Vec<Frac> = parallel
+            .into_iter()
+            .map(|result| result.unwrap().objective_function_value)
+            .collect();
+        assert_eq!(
+            values[0],
+            solve_with_simplex_tableaux(&system)
+                .unwrap()
+                .objective_function_value
+        );
+        assert_eq!(values[1], 40.into());
+        assert_eq!(values[2], Frac::zero());
+        assert_eq!(values[3], 12.into());
+
+        // The original system shouldn't be changed
+        assert_eq!(system.num_constraints(), 2);
+    }
+}
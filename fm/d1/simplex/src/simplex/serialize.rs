@@ -0,0 +1,230 @@
+//! This module handles serializing and deserializing solutions, so that they can be saved and
+//! loaded again. Variables are written with their symbolic names, like `x` or `sl#0`, and
+//! fractions are written like [`frac_to_json`], as objects with their exact numerator and
+//! denominator, and a decimal approximation which is ignored when loading.
+
+use super::{frac_to_json, VariableType};
+use crate::Frac;
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    sync::{Mutex, OnceLock},
+};
+
+impl<'v> Serialize for VariableType<'v> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, 'v> Deserialize<'de> for VariableType<'v> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Name(name) = Name::deserialize(deserializer)?;
+        Ok(VariableType::from_symbolic_name(name))
+    }
+}
+
+impl<'v> VariableType<'v> {
+    /// Parse a variable from its name with [`VariableNaming::Symbolic`], like `sl#0`, `su#0`, or
+    /// `ar#0`. Anything else is an original variable.
+    ///
+    /// [`VariableNaming::Symbolic`]: crate::lin_prog::config::VariableNaming::Symbolic
+    pub fn from_symbolic_name(name: &'v str) -> Self {
+        let index = |prefix: &str| name.strip_prefix(prefix)?.parse::<usize>().ok();
+
+        if let Some(idx) = index("sl#") {
+            Self::Slack(idx)
+        } else if let Some(idx) = index("su#") {
+            Self::Surplus(idx)
+        } else if let Some(idx) = index("ar#") {
+            Self::Artificial(idx)
+        } else {
+            Self::Original(name)
+        }
+    }
+}
+
+/// Get a copy of the given name which lives for the rest of the program. Each distinct name is
+/// only allocated once, so loading the same solutions again doesn't use any more memory.
+fn intern(name: &str) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut names = NAMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match names.get(name) {
+        Some(&interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(name.into());
+            names.insert(interned);
+            interned
+        }
+    }
+}
+
+/// A name in a solution, like a variable name or the name of the objective function. Names are
+/// interned rather than borrowed from the input, so that they can be loaded from anything, like
+/// a reader or a [`serde_json::Value`], and so that a loaded solution can outlive its input.
+struct Name(&'static str);
+
+impl<'de> Deserialize<'de> for Name {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl<'de> Visitor<'de> for NameVisitor {
+            type Value = Name;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a name like x or sl#0")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(Name(intern(v)))
+            }
+        }
+
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+/// The numerator of a fraction, which [`frac_to_json`] writes as a string if it doesn't fit in a
+/// JSON number.
+struct Numerator(i128);
+
+impl<'de> Deserialize<'de> for Numerator {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NumeratorVisitor;
+
+        impl<'de> Visitor<'de> for NumeratorVisitor {
+            type Value = Numerator;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an integer numerator")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(Numerator(v.into()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(Numerator(v.into()))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse()
+                    .map(Numerator)
+                    .map_err(|_| E::custom(format!("invalid numerator {v:?}")))
+            }
+        }
+
+        deserializer.deserialize_any(NumeratorVisitor)
+    }
+}
+
+/// A fraction in the format of [`frac_to_json`].
+#[derive(Deserialize)]
+struct FracJson {
+    /// The numerator, with the sign of the fraction, or `None` if it's infinite or NaN.
+    numerator: Option<Numerator>,
+
+    /// The denominator, or `None` if the fraction is infinite or NaN.
+    denominator: Option<u64>,
+}
+
+impl FracJson {
+    /// Convert this back to a fraction, checking that it's valid.
+    fn into_frac<E: de::Error>(self) -> Result<Frac, E> {
+        match (self.numerator, self.denominator) {
+            (Some(Numerator(numer)), Some(denom)) if denom != 0 => {
+                let unsigned = u64::try_from(numer.unsigned_abs())
+                    .map_err(|_| E::custom(format!("numerator {numer} is too big")))?;
+                let frac = Frac::new(unsigned, denom);
+                Ok(if numer < 0 { -frac } else { frac })
+            }
+            (None, None) => Ok(Frac::nan()),
+            (Some(Numerator(numer)), denom) => Err(E::custom(format!(
+                "invalid fraction with numerator {numer} and denominator {denom:?}"
+            ))),
+            (None, Some(denom)) => Err(E::custom(format!(
+                "invalid fraction with no numerator and denominator {denom}"
+            ))),
+        }
+    }
+}
+
+/// Serialize a single fraction with [`frac_to_json`]. This is meant for `#[serde(with = ...)]`.
+pub(super) mod frac {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(frac: &Frac, serializer: S) -> Result<S::Ok, S::Error> {
+        frac_to_json(frac).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Frac, D::Error> {
+        FracJson::deserialize(deserializer)?.into_frac()
+    }
+}
+
+/// Serialize a map of fractions with each value written by [`frac_to_json`]. This is meant for
+/// `#[serde(with = ...)]`.
+pub(super) mod frac_map {
+    use super::*;
+
+    pub fn serialize<K, S>(map: &HashMap<K, Frac>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_map(map.iter().map(|(key, frac)| (key, frac_to_json(frac))))
+    }
+
+    pub fn deserialize<'de, K, D>(deserializer: D) -> Result<HashMap<K, Frac>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        D: Deserializer<'de>,
+    {
+        HashMap::<K, FracJson>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, frac)| Ok((key, frac.into_frac()?)))
+            .collect()
+    }
+}
+
+/// Serialize a set of variables in order, so that the output is the same every time. This is
+/// meant for `#[serde(serialize_with = ...)]`.
+pub(super) fn sorted_variables<S: Serializer>(
+    variables: &HashSet<VariableType>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut variables: Vec<_> = variables.iter().collect();
+    variables.sort();
+    serializer.collect_seq(variables)
+}
+
+/// An optional name, like the name of the objective function. This is the same as
+/// `Option<&str>`, but serde would implicitly borrow a field of that type from the input, which
+/// would stop solutions being loaded from anything that can't be borrowed from. We intern the
+/// name with [`optional_name`] instead.
+pub(super) type OptionalName<'v> = Option<&'v str>;
+
+/// Serialize and deserialize an optional name, which gets interned when it's loaded. See
+/// [`Name`]. This is meant for `#[serde(with = ...)]`.
+pub(super) mod optional_name {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(name: &OptionalName, serializer: S) -> Result<S::Ok, S::Error> {
+        name.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, 'v, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<OptionalName<'v>, D::Error> {
+        Ok(Option::<Name>::deserialize(deserializer)?.map(|Name(name)| name))
+    }
+}
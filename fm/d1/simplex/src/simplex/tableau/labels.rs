@@ -1,6 +1,6 @@
 //! This module contains labels for rows and columns in tableaux.
 
-use crate::simplex::VariableType;
+use crate::{lin_prog::config::VariableNaming, simplex::VariableType};
 use std::fmt;
 
 /// A label to use for a row in the tableau.
@@ -14,18 +14,32 @@ pub(super) enum RowLabel<'v> {
 
     /// The expression of `I`, which is used in two stage simplex, where `I = -(a1 + a2 + ...)`
     TwoStageArtificial,
+
+    /// The multiples of `M` in the objective row when using the Big-M method. This is the same as
+    /// the row for `I` in two stage simplex, but it's combined with the objective row when
+    /// displaying the tableau.
+    BigMPenalty,
 }
 
-impl<'v> fmt::Display for RowLabel<'v> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl<'v> RowLabel<'v> {
+    /// Display this label, naming any variable with the given scheme. See
+    /// [`VariableType::display_with`].
+    pub fn display_with(&self, naming: VariableNaming) -> String {
         match self {
-            Self::Variable(var) => write!(f, "{var}"),
-            Self::ObjectiveFunction => write!(f, "ObjFunc#"),
-            Self::TwoStageArtificial => write!(f, "TwoStageAr#"),
+            Self::Variable(var) => var.display_with(naming),
+            Self::ObjectiveFunction => "ObjFunc#".to_string(),
+            Self::TwoStageArtificial => "TwoStageAr#".to_string(),
+            Self::BigMPenalty => "BigM#".to_string(),
         }
     }
 }
 
+impl<'v> fmt::Display for RowLabel<'v> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_with(VariableNaming::Symbolic))
+    }
+}
+
 impl<'v> TryFrom<ColumnLabel<'v>> for RowLabel<'v> {
     type Error = &'static str;
 
@@ -67,11 +81,19 @@ impl<'v> From<VariableType<'v>> for ColumnLabel<'v> {
     }
 }
 
-impl<'v> fmt::Display for ColumnLabel<'v> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl<'v> ColumnLabel<'v> {
+    /// Display this label, naming any variable with the given scheme. See
+    /// [`VariableType::display_with`].
+    pub fn display_with(&self, naming: VariableNaming) -> String {
         match self {
-            ColumnLabel::BasicString(s) => write!(f, "{s}"),
-            ColumnLabel::Variable(v) => write!(f, "{v}"),
+            ColumnLabel::BasicString(s) => s.clone(),
+            ColumnLabel::Variable(v) => v.display_with(naming),
         }
     }
 }
+
+impl<'v> fmt::Display for ColumnLabel<'v> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_with(VariableNaming::Symbolic))
+    }
+}
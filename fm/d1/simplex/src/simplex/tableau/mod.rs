@@ -6,18 +6,25 @@ use self::labels::{ColumnLabel, RowLabel};
 use crate::{
     lin_prog::{
         comparison::Comparison,
+        config::{ColumnTieBreak, PivotRule, SimplexMethod, VariableNaming},
+        constraint::Constraint,
         expression::{const_expression::VariableOrConst, ConstExpression},
         system::LinProgSystem,
-        ObjectiveFunction,
+        ObjectiveSense,
     },
-    simplex::{Equation, SolutionSet, VariableType},
+    simplex::{frac_to_json, Equation, SolutionSet, VariableType},
     Frac,
 };
 use color_eyre::{Report, Result};
-use fraction::Zero;
+use fraction::{One, Zero};
 use itertools::Itertools;
-use std::{collections::HashMap, fmt, iter};
-use tabled::{builder::Builder, Style};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde_json::{json, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, iter,
+};
+use tabled::{builder::Builder, object::Segment, Alignment, Modify, Style};
 use thiserror::Error;
 use tracing::{debug, error, info, instrument};
 
@@ -31,6 +38,42 @@ impl fmt::Display for NoFeasibleSolution {
     }
 }
 
+/// The pivot element was 0, so the pivot row can't be divided by it. This should never happen if
+/// the pivot row and column were chosen correctly, so it means there's a bug in the pivot rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+#[error("Zero pivot element encountered")]
+pub struct ZeroPivot;
+
+/// An error from a single iteration of the simplex algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum IterationError {
+    /// There is no feasible solution for the given [`LinProgSystem`].
+    #[error(transparent)]
+    NoFeasibleSolution(#[from] NoFeasibleSolution),
+
+    /// The objective function can be improved forever, because there are no positive numbers in
+    /// the pivot column.
+    #[error("Objective function is unbounded")]
+    Unbounded,
+
+    /// The pivot element was 0. See [`ZeroPivot`].
+    #[error(transparent)]
+    ZeroPivot(#[from] ZeroPivot),
+}
+
+/// An error from getting the solution from a tableau with [`Tableau::get_solution`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum SolutionError {
+    /// There are still negative numbers in the bottom row, so the tableau isn't optimal yet and
+    /// more iterations are needed.
+    #[error("The tableau isn't optimal, since there are negatives in the bottom row")]
+    NotOptimal,
+
+    /// The tableau doesn't have a row for the objective function, so we can't find its value.
+    #[error("The tableau has no objective function row")]
+    MissingObjectiveRow,
+}
+
 /// The operation to be applied to a particular row.
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum RowOperation {
@@ -45,6 +88,11 @@ enum RowOperation {
     /// The `usize` here is the index of the row, so it starts at 0. When printing it with
     /// [`Display`], we increment it.
     AddRow(Frac, usize),
+
+    /// Add `mM + c` times another row to this row, where `M` is the penalty of the Big-M method.
+    /// This is only used to display the row operations of the objective row and the row of
+    /// multiples of `M` together, and is never performed.
+    AddRowBigM(Frac, Frac, usize),
 }
 
 impl fmt::Display for RowOperation {
@@ -59,11 +107,161 @@ impl fmt::Display for RowOperation {
                     format!("{n} R{}", idx + 1)
                 }
             }
+            RowOperation::AddRowBigM(m, c, idx) => {
+                let sign = if (*m, *c) > (Frac::zero(), Frac::zero()) {
+                    "+"
+                } else {
+                    ""
+                };
+                format!("{sign}({}) R{}", big_m_to_text(*m, *c), idx + 1)
+            }
         };
         write!(f, "{s}")
     }
 }
 
+impl RowOperation {
+    /// Render the row operation as HTML. See [`frac_to_html`].
+    fn to_html(self) -> String {
+        match self {
+            RowOperation::Nop => "Nop".to_string(),
+            RowOperation::MulConst(n) => format!("×{}", frac_to_html(&n)),
+            RowOperation::AddRow(n, idx) => {
+                if n > Frac::zero() {
+                    format!("+{} R{}", frac_to_html(&n), idx + 1)
+                } else {
+                    format!("{} R{}", frac_to_html(&n), idx + 1)
+                }
+            }
+            RowOperation::AddRowBigM(m, c, idx) => {
+                let sign = if (m, c) > (Frac::zero(), Frac::zero()) {
+                    "+"
+                } else {
+                    ""
+                };
+                format!(
+                    "{sign}({}) R{}",
+                    big_m_to_string(m, c, frac_to_html, frac_to_html),
+                    idx + 1
+                )
+            }
+        }
+    }
+
+    /// Render the row operation as LaTeX maths, without the surrounding `$`. See
+    /// [`frac_to_latex`].
+    fn to_latex(self) -> String {
+        match self {
+            RowOperation::Nop => "\\mathrm{Nop}".to_string(),
+            RowOperation::MulConst(n) => format!("\\times {}", frac_to_latex(&n)),
+            RowOperation::AddRow(n, idx) => {
+                if n > Frac::zero() {
+                    format!("+{} R_{{{}}}", frac_to_latex(&n), idx + 1)
+                } else {
+                    format!("{} R_{{{}}}", frac_to_latex(&n), idx + 1)
+                }
+            }
+            RowOperation::AddRowBigM(m, c, idx) => {
+                let sign = if (m, c) > (Frac::zero(), Frac::zero()) {
+                    "+"
+                } else {
+                    ""
+                };
+                format!(
+                    "{sign}({}) R_{{{}}}",
+                    big_m_to_string(m, c, frac_to_latex, frac_to_latex),
+                    idx + 1
+                )
+            }
+        }
+    }
+
+    /// Combine the row operation of the objective row with the row operation of the row of
+    /// multiples of `M`, so that they can be displayed as a single row when using the Big-M
+    /// method.
+    fn merge_big_m(m_op: Self, c_op: Self) -> Self {
+        match (m_op, c_op) {
+            (Self::Nop, op) => op,
+            (Self::AddRow(m, idx), Self::Nop) => Self::AddRowBigM(m, Frac::zero(), idx),
+            (Self::AddRow(m, idx), Self::AddRow(c, _)) => Self::AddRowBigM(m, c, idx),
+            (m_op, c_op) => {
+                unreachable!("The objective rows can't have the row ops {m_op:?} and {c_op:?}")
+            }
+        }
+    }
+}
+
+/// Render a fraction as LaTeX maths, using `\frac` for anything that isn't a whole number. This
+/// doesn't include the surrounding `$`.
+fn frac_to_latex(n: &Frac) -> String {
+    match (n.numer(), n.denom()) {
+        (Some(&numer), Some(&denom)) if numer != 0 && denom != 1 => {
+            let sign = if n.is_sign_negative() { "-" } else { "" };
+            format!("{sign}\\frac{{{numer}}}{{{denom}}}")
+        }
+        _ => n.to_string(),
+    }
+}
+
+/// Escape the characters in a label which are special in LaTeX.
+fn latex_escape(label: &str) -> String {
+    label
+        .replace('#', "\\#")
+        .replace('_', "\\_")
+        .replace('θ', "$\\theta$")
+}
+
+/// Render a fraction as HTML, using `<sup>` and `<sub>` for the numerator and denominator of
+/// anything that isn't a whole number.
+fn frac_to_html(n: &Frac) -> String {
+    match (n.numer(), n.denom()) {
+        (Some(&numer), Some(&denom)) if numer != 0 && denom != 1 => {
+            let sign = if n.is_sign_negative() { "-" } else { "" };
+            format!("{sign}<sup>{numer}</sup>&frasl;<sub>{denom}</sub>")
+        }
+        _ => n.to_string(),
+    }
+}
+
+/// Render the number `mM + c`, where `M` is the penalty of the Big-M method, using `render` for
+/// the constant and `render_coeff` for the multiple of `M`. Any parts which are 0 are left out.
+fn big_m_to_string(
+    m: Frac,
+    c: Frac,
+    render: impl Fn(&Frac) -> String,
+    render_coeff: impl Fn(&Frac) -> String,
+) -> String {
+    let m_part = if m == Frac::one() {
+        "M".to_string()
+    } else if m == -Frac::one() {
+        "-M".to_string()
+    } else {
+        format!("{}M", render_coeff(&m))
+    };
+
+    if m.is_zero() {
+        render(&c)
+    } else if c.is_zero() {
+        m_part
+    } else if c > Frac::zero() {
+        format!("{m_part} + {}", render(&c))
+    } else {
+        format!("{m_part} - {}", render(&-c))
+    }
+}
+
+/// Render the number `mM + c` as plain text. A fractional multiple of `M` is put in brackets, so
+/// that `1/2M` can't be read as `1/(2M)`.
+fn big_m_to_text(m: Frac, c: Frac) -> String {
+    big_m_to_string(m, c, ToString::to_string, |n| {
+        if n.denom() == Some(&1) {
+            n.to_string()
+        } else {
+            format!("({n})")
+        }
+    })
+}
+
 /// A number to use in a tableau. This is used to allow certain values (like theta) to be optional,
 /// as well as allowing for the row operation columns.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -71,6 +269,10 @@ enum TableauNumber {
     /// A simple number.
     Simple(Frac),
 
+    /// A number of the form `mM + c` in the Big-M method, stored as `BigM(m, c)`. This is only
+    /// used when displaying the tableau.
+    BigM(Frac, Frac),
+
     /// A theta value, which will not exist at first, since it must be populated later.
     Theta(Option<Frac>),
 
@@ -83,6 +285,7 @@ impl fmt::Display for TableauNumber {
         let s = match self {
             Self::Simple(n) => n.to_string(),
             Self::Theta(Some(n)) => n.to_string(),
+            Self::BigM(m, c) => big_m_to_text(*m, *c),
             Self::RowOperation(Some(op)) => op.to_string(),
             Self::Theta(None) | Self::RowOperation(None) => String::new(),
         };
@@ -91,6 +294,38 @@ impl fmt::Display for TableauNumber {
 }
 
 impl TableauNumber {
+    /// Render the number as HTML. See [`frac_to_html`].
+    fn to_html(self) -> String {
+        match self {
+            Self::Simple(n) | Self::Theta(Some(n)) => frac_to_html(&n),
+            Self::BigM(m, c) => big_m_to_string(m, c, frac_to_html, frac_to_html),
+            Self::RowOperation(Some(op)) => op.to_html(),
+            Self::Theta(None) | Self::RowOperation(None) => String::new(),
+        }
+    }
+
+    /// Render the number as LaTeX maths, without the surrounding `$`. See [`frac_to_latex`].
+    fn to_latex(self) -> String {
+        match self {
+            Self::Simple(n) | Self::Theta(Some(n)) => frac_to_latex(&n),
+            Self::BigM(m, c) => big_m_to_string(m, c, frac_to_latex, frac_to_latex),
+            Self::RowOperation(Some(op)) => op.to_latex(),
+            Self::Theta(None) | Self::RowOperation(None) => String::new(),
+        }
+    }
+
+    /// Convert the number to JSON. Fractions use [`frac_to_json`], Big-M numbers are objects with
+    /// the multiple of `M` and the constant, row operations are strings, and missing values are
+    /// null.
+    fn to_json(self) -> Value {
+        match self {
+            Self::Simple(n) | Self::Theta(Some(n)) => frac_to_json(&n),
+            Self::BigM(m, c) => json!({ "m": frac_to_json(&m), "constant": frac_to_json(&c) }),
+            Self::RowOperation(Some(op)) => Value::String(op.to_string()),
+            Self::Theta(None) | Self::RowOperation(None) => Value::Null,
+        }
+    }
+
     /// Return the number if this is a simple tableau number, otherwise panic.
     ///
     /// This method should only be used if you know the number is simple.
@@ -100,6 +335,15 @@ impl TableauNumber {
             x => panic!("TableauNumber::simple_num() called on a non-simple number: {x:?}"),
         }
     }
+
+    /// Check if this is a negative simple or Big-M number.
+    fn is_negative(&self) -> bool {
+        match *self {
+            Self::Simple(n) => n < Frac::zero(),
+            Self::BigM(m, c) => (m, c) < (Frac::zero(), Frac::zero()),
+            Self::Theta(_) | Self::RowOperation(_) => false,
+        }
+    }
 }
 
 /// A single tableau for simplex tableaux.
@@ -119,8 +363,42 @@ pub struct Tableau<'v> {
     /// Whether to minimise the objective function rather than the default of maximising it.
     minimise: bool,
 
-    /// Whether we need integer solutions.
-    integer_solutions: bool,
+    /// What the objective function was multiplied by to clear its denominators, which we divide
+    /// by again when reading its value. This is 1 unless the config asks to clear denominators.
+    objective_scale: Frac,
+
+    /// What each slack and surplus variable was multiplied by when its constraint had its
+    /// denominators cleared. Variables which weren't scaled aren't in the map.
+    variable_scales: HashMap<VariableType<'v>, Frac>,
+
+    /// The rule used to choose the pivot column.
+    pivot_rule: PivotRule,
+
+    /// How to break ties between pivot columns which the pivot rule doesn't separate.
+    column_tie_break: ColumnTieBreak,
+
+    /// The seed for choosing pivot columns with [`PivotRule::Random`].
+    rng_seed: u64,
+
+    /// The number of iterations done so far. This is combined with the seed for each random
+    /// choice, so that choosing the pivot column doesn't need to mutate the tableau.
+    iterations: u64,
+
+    /// Whether to use Bland's rule instead of the pivot rule.
+    anti_cycling: bool,
+
+    /// Whether to keep the artificial columns after the first stage of the two-stage simplex,
+    /// rather than removing them.
+    keep_artificial_columns: bool,
+
+    /// Whether to right-align the numbers when displaying the tableau.
+    right_align_numbers: bool,
+
+    /// Whether to only use ASCII characters when displaying the tableau.
+    ascii_output: bool,
+
+    /// How to name the slack, surplus, and artificial variables when displaying the tableau.
+    variable_naming: VariableNaming,
 
     /// The index of the value column.
     value_idx: usize,
@@ -134,18 +412,46 @@ pub struct Tableau<'v> {
 
 impl<'v> fmt::Display for Tableau<'v> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The only non-ASCII characters in the cells are θ and × for multiplying rows
+        let cell = |s: String| {
+            if self.ascii_output {
+                s.replace('θ', "theta").replace('×', "*")
+            } else {
+                s
+            }
+        };
+
         let mut builder = Builder::default();
         builder.add_record(
-            iter::once("Basic var".into()).chain(self.column_labels.iter().map(|s| s.to_string())),
+            iter::once("Basic var".into()).chain(
+                self.column_labels
+                    .iter()
+                    .map(|label| cell(label.display_with(self.variable_naming))),
+            ),
         );
 
-        for (label, nums) in &self.rows {
+        for (label, nums) in &self.display_rows() {
             builder.add_record(
-                iter::once(label.to_string()).chain(nums.iter().map(|n| n.to_string())),
+                iter::once(label.display_with(self.variable_naming))
+                    .chain(nums.iter().map(|n| cell(n.to_string()))),
+            );
+        }
+
+        let mut table = builder.build();
+        if self.ascii_output {
+            table.with(Style::ascii());
+        } else {
+            table.with(Style::modern());
+        }
+
+        // Right-align every number, but not the labels or row ops
+        if self.right_align_numbers {
+            table.with(
+                Modify::new(Segment::new(1.., 1..self.row_ops_idx + 1)).with(Alignment::right()),
             );
         }
 
-        let table = builder.build().with(Style::modern()).to_string();
+        let table = table.to_string();
 
         write!(f, "\n{table}")
     }
@@ -155,12 +461,23 @@ impl<'v> Tableau<'v> {
     /// Generate the initial tableau for the given system with its variables and equations.
     #[instrument(skip(system))]
     pub fn create_initial(system: &'v LinProgSystem) -> Result<Self> {
-        let minimise = system
-            .with_objective_function(|obj_func| matches!(obj_func, ObjectiveFunction::Minimise(_)));
+        let minimise = system.objective_sense() == ObjectiveSense::Minimise;
+
+        // A non-positive variable `x` is replaced by `-x`, which is non-negative, so we negate all
+        // of its coefficients, and negate its value again in the solution
+        let non_positive = &system.borrow_config().non_positive_variables;
+        let signed = |coeff: Frac, var: &str| {
+            if non_positive.contains(var) {
+                -coeff
+            } else {
+                coeff
+            }
+        };
 
         // Convert the original variables from the system into [`VariableType::Original`] variables.
         // This HashMap maps variables to their current values. These values will change during the
-        // execution of the algorithm.
+        // execution of the algorithm. The variables are stored in a `HashSet`, so we sort them
+        // before converting them to make the column order independent of its iteration order.
         let mut variables: Vec<(VariableType<'v>, Frac)> = system
             .borrow_variables()
             .0
@@ -175,11 +492,36 @@ impl<'v> Tableau<'v> {
         let mut surplus_counter = 0;
         let mut artificial_counter = 0;
         let mut equations = vec![];
+        let mut variable_scales = HashMap::new();
+        let standard_form = system.borrow_config().standard_form;
+        let clear_denominators = system.borrow_config().clear_denominators;
+        let objective_scale = if clear_denominators {
+            Frac::from(
+                system.with_objective_function(|obj_func| obj_func.expression().denominator_lcm()),
+            )
+        } else {
+            Frac::one()
+        };
 
         // Convert the constraints to equations, creating necessary slack variables
         system.with_constraints(|cons| {
-            for constraint in cons {
-                match constraint.comparison {
+            for constraint in cons.iter().cloned().map(Constraint::normalise) {
+                // Scaling a constraint scales its slack or surplus variable too, so we need to
+                // divide by the scale again when reading its value
+                let scale = Frac::from(constraint.denominator_lcm());
+                let constraint = if clear_denominators {
+                    constraint.clear_denominators()
+                } else {
+                    constraint
+                };
+
+                let comparison = if standard_form {
+                    Comparison::Equal
+                } else {
+                    constraint.comparison
+                };
+
+                match comparison {
                     Comparison::LessThanOrEqual => {
                         // When creating a new slack variable, we need to increment the counter for the
                         // next one and add it to the simplex variables set, with a starting value of the
@@ -187,6 +529,9 @@ impl<'v> Tableau<'v> {
                         let slack = VariableType::Slack(slack_counter);
                         slack_counter += 1;
                         variables.push((slack, constraint.constant));
+                        if clear_denominators {
+                            variable_scales.insert(slack, scale);
+                        }
 
                         // Convert the old variables from the constraint into the required type and add the
                         // slack variable for this equation
@@ -194,7 +539,7 @@ impl<'v> Tableau<'v> {
                             .var_expression
                             .0
                             .iter()
-                            .map(|&(coeff, var)| (coeff, VariableType::Original(var)))
+                            .map(|&(coeff, var)| (signed(coeff, var), VariableType::Original(var)))
                             .chain(iter::once((1.into(), slack)))
                             .collect();
 
@@ -209,6 +554,9 @@ impl<'v> Tableau<'v> {
                         surplus_counter += 1;
                         // The surplus variable starts at 0
                         variables.push((surplus, Frac::zero()));
+                        if clear_denominators {
+                            variable_scales.insert(surplus, scale);
+                        }
 
                         let artificial = VariableType::Artificial(artificial_counter);
                         artificial_counter += 1;
@@ -219,7 +567,7 @@ impl<'v> Tableau<'v> {
                             .var_expression
                             .0
                             .iter()
-                            .map(|&(coeff, var)| (coeff, VariableType::Original(var)))
+                            .map(|&(coeff, var)| (signed(coeff, var), VariableType::Original(var)))
                             .chain(
                                 [(-Frac::new(1u32, 1u32), surplus), (1.into(), artificial)]
                                     .into_iter(),
@@ -231,6 +579,26 @@ impl<'v> Tableau<'v> {
                             constant: constraint.constant,
                         })
                     }
+                    // Systems in standard form have no slack or surplus variables, so each
+                    // equation just needs an artificial variable to start in the basis
+                    Comparison::Equal if standard_form => {
+                        let artificial = VariableType::Artificial(artificial_counter);
+                        artificial_counter += 1;
+                        variables.push((artificial, constraint.constant));
+
+                        let eqn_variables = constraint
+                            .var_expression
+                            .0
+                            .iter()
+                            .map(|&(coeff, var)| (signed(coeff, var), VariableType::Original(var)))
+                            .chain(iter::once((1.into(), artificial)))
+                            .collect();
+
+                        equations.push(Equation {
+                            variables: eqn_variables,
+                            constant: constraint.constant,
+                        })
+                    }
                     _ => {
                         error!(
                             comparison = ?constraint.comparison,
@@ -257,18 +625,21 @@ impl<'v> Tableau<'v> {
             .chain(["Value".into(), "θ".into(), "Row op".into()].into_iter())
             .collect();
 
+        // The Big-M method has the same row as the first stage of the two stage simplex, but it's
+        // part of the objective function rather than a separate stage
+        let artificial_row_label = match system.borrow_config().method {
+            SimplexMethod::TwoStage => RowLabel::TwoStageArtificial,
+            SimplexMethod::BigM => RowLabel::BigMPenalty,
+        };
+
         // Each row has n + 3 columns, where n is the number of variables. We have a column for each
         // variable, a column for the value, a column for theta, and a column for the row operation
         let rows = variables
             .iter()
-            // Filter the variables to just the slack, surplus, and artificial variables. These are
-            // the basic variables at the start
-            .filter_map(|&(var, _)| match var {
-                VariableType::Original(_) | VariableType::Surplus(_) => None,
-                VariableType::Slack(_) | VariableType::Artificial(_) => {
-                    Some(RowLabel::Variable(var))
-                },
-            })
+            // Filter the variables to just the slack and artificial variables. These are the basic
+            // variables at the start
+            .filter(|&&(var, _)| var.is_slack() || var.is_artificial())
+            .map(|&(var, _)| RowLabel::Variable(var))
             .map(|label| {
                 (
                     label.clone(),
@@ -329,6 +700,7 @@ impl<'v> Tableau<'v> {
                                     .iter()
                                     .find_map(|&(coeff, of_var)| {
                                         if VariableType::Original(of_var) == *var {
+                                            let coeff = signed(coeff, of_var) * objective_scale;
                                             if minimise {
                                                 Some(coeff)
                                             } else {
@@ -347,13 +719,14 @@ impl<'v> Tableau<'v> {
                 ))
             )
             // Add the row for the new objective function for the first stage of the two stage
-            // simplex if necessary
+            // simplex if necessary. With the Big-M method, this row holds the multiples of `M` in
+            // the objective function instead
             .chain(
                 {
                     let iterator: Box<dyn Iterator<Item = (RowLabel, Vec<Frac>)>> =
                         if surplus_counter > 0 || artificial_counter > 0 {
                             Box::new(iter::once((
-                                RowLabel::TwoStageArtificial,
+                                artificial_row_label,
                                 {
                                     // We want a new objective function I = -sum(artificials)
                                     let new_obj_func: ConstExpression<'_, VariableType<'_>> = equations
@@ -363,7 +736,7 @@ impl<'v> Tableau<'v> {
                                         .filter(|&eq| {
                                             eq.variables
                                                 .iter()
-                                                .find(|&(_, var)| matches!(var, VariableType::Artificial(_)))
+                                                .find(|&(_, var)| var.is_artificial())
                                                 .is_some()
                                         })
                                         // Solve for each artificial variable
@@ -374,7 +747,7 @@ impl<'v> Tableau<'v> {
                                                         .iter()
                                                         // Filter out artificials, since we're
                                                         // solving for the artificials
-                                                        .filter(|&(_, var)| !matches!(var, VariableType::Artificial(_)))
+                                                        .filter(|&(_, var)| !var.is_artificial())
                                                         .map(|(coeff, var)| VariableOrConst::Variable(-coeff, var))
                                                 )
                                                 .collect()
@@ -430,143 +803,579 @@ impl<'v> Tableau<'v> {
             rows,
             system,
             minimise,
-            integer_solutions: system.borrow_config().integer_solutions,
+            objective_scale,
+            variable_scales,
+            pivot_rule: system.borrow_config().pivot_rule,
+            column_tie_break: system.borrow_config().column_tie_break,
+            rng_seed: system.borrow_config().rng_seed.unwrap_or_else(rand::random),
+            iterations: 0,
+            anti_cycling: system.borrow_config().anti_cycling,
+            keep_artificial_columns: system.borrow_config().keep_artificial_columns,
+            right_align_numbers: system.borrow_config().right_align_numbers,
+            ascii_output: system.borrow_config().ascii_output,
+            variable_naming: system.borrow_config().variable_naming,
             value_idx,
             theta_idx: value_idx + 1,
             row_ops_idx: value_idx + 2,
         })
     }
 
-    /// Return a reference to the bottom row of the table.
-    fn bottom_row(&self) -> &(RowLabel<'v>, Vec<TableauNumber>) {
-        self.rows.last().expect("There should be a bottom row")
-    }
-
-    /// Return the values in the theta column.
-    fn theta_column(&self) -> Vec<Option<Frac>> {
-        self.rows
+    /// Convert the tableau to JSON, with the column labels and a list of rows, where each row has
+    /// a label and the values in each column.
+    pub fn to_json(&self) -> Value {
+        let rows: Vec<Value> = self
+            .display_rows()
             .iter()
-            .map(|(_, numbers)| match numbers[self.theta_idx] {
-                TableauNumber::Theta(n) => n,
-                other => panic!("The theta column must only contain theta values, not {other:?}"),
+            .map(|(label, nums)| {
+                json!({
+                    "label": label.to_string(),
+                    "values": nums.iter().map(|n| n.to_json()).collect::<Vec<_>>(),
+                })
             })
-            .collect()
+            .collect();
+
+        json!({
+            "column_labels": self.column_labels.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            "rows": rows,
+        })
     }
 
-    /// Check if there are any negative numbers in the bottom row of the tableau.
-    pub fn negatives_in_bottom_row(&self) -> bool {
-        self.bottom_row()
-            .1
+    /// Render the tableau as an HTML `<table>`, with the same layout as the [`Display`](fmt::Display)
+    /// impl. Fractions are written with `<sup>` and `<sub>`.
+    pub fn to_html(&self) -> String {
+        let header = iter::once("Basic var".to_string())
+            .chain(
+                self.column_labels
+                    .iter()
+                    .map(|label| label.display_with(self.variable_naming)),
+            )
+            .map(|label| format!("<th>{label}</th>"))
+            .join("");
+
+        let rows = self
+            .display_rows()
             .iter()
-            .take(self.value_idx)
-            .any(|&n| match n {
-                TableauNumber::Simple(n) => n < Frac::zero(),
-                _ => false,
+            .map(|(label, nums)| {
+                let cells = nums
+                    .iter()
+                    .map(|n| format!("<td>{}</td>", n.to_html()))
+                    .join("");
+                format!(
+                    "<tr><th scope=\"row\">{}</th>{cells}</tr>",
+                    label.display_with(self.variable_naming)
+                )
             })
+            .join("\n");
+
+        format!(
+            "<table class=\"tableau\" style=\"border-collapse: collapse; text-align: left;\">\n\
+            <thead>\n<tr>{header}</tr>\n</thead>\n\
+            <tbody>\n{rows}\n</tbody>\n\
+            </table>"
+        )
     }
 
-    /// Return the index of the pivot column. This is calculated by finding the most negative
-    /// number in the bottom row.
-    fn find_pivot_column(&self) -> usize {
-        self.bottom_row()
-            .1
+    /// Render the tableau as a LaTeX `tabular`, with the same layout as the
+    /// [`Display`](fmt::Display) impl. Fractions are written with `\frac`.
+    ///
+    /// If the row operations are populated, then the pivot element is bold. Negative numbers in
+    /// the bottom row are underlined, since those are the columns that the pivot could be in.
+    pub fn to_latex(&self) -> String {
+        let display_rows = self.display_rows();
+        let pivot = self.populated_pivot(&display_rows);
+        let bottom_row_idx = display_rows.len() - 1;
+
+        let column_spec = format!("l|{}|c|c|l", "c".repeat(self.value_idx));
+        let header = iter::once("Basic var".to_string())
+            .chain(
+                self.column_labels
+                    .iter()
+                    .map(|label| latex_escape(&label.display_with(self.variable_naming))),
+            )
+            .join(" & ");
+
+        let rows = display_rows
             .iter()
             .enumerate()
-            .take(self.value_idx)
-            .filter_map(|(idx, num)| match num {
-                TableauNumber::Simple(n) if *n < Frac::zero() => Some((idx, *n)),
-                _ => None,
-            })
-            .fold(
-                (0, Frac::zero()),
-                |(acc_idx, acc_min), (this_idx, this_num)| {
-                    if this_num < acc_min {
-                        (this_idx, this_num)
+            .map(|(row_idx, (label, nums))| {
+                let cells = nums.iter().enumerate().map(|(col_idx, n)| {
+                    let latex = n.to_latex();
+                    if latex.is_empty() {
+                        latex
+                    } else if pivot == Some((row_idx, col_idx)) {
+                        format!("$\\mathbf{{{latex}}}$")
+                    } else if row_idx == bottom_row_idx
+                        && col_idx < self.value_idx
+                        && n.is_negative()
+                    {
+                        format!("\\underline{{${latex}$}}")
                     } else {
-                        (acc_idx, acc_min)
+                        format!("${latex}$")
                     }
-                },
-            )
-            .0
+                });
+
+                // Separate the objective rows from the constraint rows
+                let hline = if matches!(label, RowLabel::Variable(_)) {
+                    ""
+                } else {
+                    "\\hline\n"
+                };
+
+                format!(
+                    "{hline}{} & {} \\\\",
+                    latex_escape(&label.display_with(self.variable_naming)),
+                    cells.collect::<Vec<_>>().join(" & ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "\\begin{{tabular}}{{{column_spec}}}\n\
+            {header} \\\\\n\
+            \\hline\n\
+            {rows}\n\
+            \\end{{tabular}}"
+        )
     }
 
-    /// Return the index of the pivot row. This is calculated by finding the smallest positive
-    /// theta value.
-    fn find_pivot_row(&self) -> usize {
-        self.theta_column()
+    /// Render the tableau as a GitHub-flavoured Markdown table, with the same columns as the
+    /// [`Display`](fmt::Display) impl.
+    ///
+    /// If the row operations are populated, then the pivot element is bold, like in
+    /// [`Tableau::to_latex`].
+    pub fn to_markdown(&self) -> String {
+        let display_rows = self.display_rows();
+        let pivot = self.populated_pivot(&display_rows);
+
+        let header = iter::once("Basic var".to_string())
+            .chain(
+                self.column_labels
+                    .iter()
+                    .map(|label| label.display_with(self.variable_naming)),
+            )
+            .collect_vec();
+        let separator = vec!["---"; header.len()];
+
+        let rows = display_rows
             .iter()
             .enumerate()
-            .filter_map(|(idx, &theta)| match theta {
-                Some(n) if n > Frac::zero() => Some((idx, n)),
-                _ => None,
-            })
-            .fold(
-                (0, Frac::infinity()),
-                |(acc_idx, acc_min), (this_idx, this_num)| {
-                    if this_num < acc_min {
-                        (this_idx, this_num)
+            .map(|(row_idx, (label, nums))| {
+                let cells = nums.iter().enumerate().map(|(col_idx, n)| {
+                    if pivot == Some((row_idx, col_idx)) {
+                        format!("**{n}**")
                     } else {
-                        (acc_idx, acc_min)
+                        n.to_string()
                     }
-                },
-            )
-            .0
+                });
+                let row = iter::once(label.display_with(self.variable_naming))
+                    .chain(cells)
+                    .join(" | ");
+                format!("| {row} |")
+            })
+            .join("\n");
+
+        format!(
+            "| {} |\n| {} |\n{rows}",
+            header.join(" | "),
+            separator.join(" | ")
+        )
     }
 
-    /// Populate this tableau with theta values.
-    fn populate_theta_values(&mut self) {
-        let pivot_col = self.find_pivot_column();
-        for (label, numbers) in &mut self.rows {
-            match label {
-                RowLabel::Variable(_) => {
-                    numbers[self.theta_idx] = TableauNumber::Theta(Some(
-                        numbers[self.value_idx].simple_num() / numbers[pivot_col].simple_num(),
-                    ));
+    /// Return the indices of the pivot element in the given display rows, if the row operations
+    /// are populated. The pivot row is the one being divided by the pivot element, and its label
+    /// has already been changed to the variable of the pivot column.
+    fn populated_pivot(
+        &self,
+        display_rows: &[(RowLabel<'v>, Vec<TableauNumber>)],
+    ) -> Option<(usize, usize)> {
+        display_rows
+            .iter()
+            .enumerate()
+            .find_map(|(row_idx, (label, nums))| match nums[self.row_ops_idx] {
+                TableauNumber::RowOperation(Some(RowOperation::MulConst(_))) => {
+                    let col_idx = self.column_labels.iter().position(|col_label| {
+                        match (col_label, label) {
+                            (ColumnLabel::Variable(col_var), RowLabel::Variable(row_var)) => {
+                                col_var == row_var
+                            }
+                            _ => false,
+                        }
+                    })?;
+                    Some((row_idx, col_idx))
                 }
-                RowLabel::ObjectiveFunction | RowLabel::TwoStageArtificial => (),
-            }
-        }
+                _ => None,
+            })
     }
 
-    /// Change the label of the pivot row to be that of the pivot column.
-    fn change_pivot_row_label(&mut self) {
-        let pivot_col = self.find_pivot_column();
-        let pivot_row = self.find_pivot_row();
+    /// Return the rows to display. With the Big-M method, the row of multiples of `M` is merged
+    /// into the objective row, so that it shows numbers like `-2M + 3`.
+    fn display_rows(&self) -> Vec<(RowLabel<'v>, Vec<TableauNumber>)> {
+        let Some((_, m_nums)) = self
+            .rows
+            .iter()
+            .find(|(label, _)| *label == RowLabel::BigMPenalty)
+        else {
+            return self.rows.clone();
+        };
 
-        self.rows[pivot_row].0 = self.column_labels[pivot_col]
-            .clone()
-            .try_into()
-            .expect("The pivot column should have a variable label");
+        self.rows
+            .iter()
+            .filter(|(label, _)| *label != RowLabel::BigMPenalty)
+            .map(|(label, nums)| {
+                if *label != RowLabel::ObjectiveFunction {
+                    return (label.clone(), nums.clone());
+                }
+
+                let merged = nums
+                    .iter()
+                    .zip(m_nums)
+                    .map(|(&c_num, &m_num)| match (m_num, c_num) {
+                        (TableauNumber::Simple(m), TableauNumber::Simple(c)) => {
+                            TableauNumber::BigM(m, c)
+                        }
+                        (
+                            TableauNumber::RowOperation(Some(m_op)),
+                            TableauNumber::RowOperation(Some(c_op)),
+                        ) => {
+                            TableauNumber::RowOperation(Some(RowOperation::merge_big_m(m_op, c_op)))
+                        }
+                        (_, c_num) => c_num,
+                    })
+                    .collect();
+                (label.clone(), merged)
+            })
+            .collect()
     }
 
-    /// Populate this tableau with row operations.
-    fn populate_row_ops(&mut self) {
-        let pivot_col = self.find_pivot_column();
-        let pivot_row = self.find_pivot_row();
+    /// Return the simple number in the cell with the given row and column labels, or `None` if
+    /// there is no such cell. The labels are compared with their [`Display`](fmt::Display) forms.
+    #[cfg(test)]
+    pub(super) fn cell(&self, row_label: &str, col_label: &str) -> Option<Frac> {
+        let col_idx = self
+            .column_labels
+            .iter()
+            .position(|label| label.to_string() == col_label)?;
+        let (_, nums) = self
+            .rows
+            .iter()
+            .find(|(label, _)| label.to_string() == row_label)?;
 
-        for (idx, (_label, nums)) in self.rows.iter_mut().enumerate() {
-            if idx == pivot_row {
-                nums[self.row_ops_idx] = TableauNumber::RowOperation(Some(RowOperation::MulConst(
-                    nums[pivot_col].simple_num().recip(),
-                )));
-            } else {
-                let row_op_coeff = -nums[pivot_col].simple_num();
-                nums[self.row_ops_idx] = if row_op_coeff != Frac::zero() {
-                    TableauNumber::RowOperation(Some(RowOperation::AddRow(row_op_coeff, pivot_row)))
-                } else {
-                    TableauNumber::RowOperation(Some(RowOperation::Nop))
-                };
-            }
+        match nums[col_idx] {
+            TableauNumber::Simple(n) => Some(n),
+            _ => None,
         }
     }
 
-    /// Perform the row operations that were previously calculated, and then clear the theta and
-    /// row op columns.
-    fn perform_row_ops(&mut self) {
-        // First pass to apply row op to the pivot row
-        for (_label, nums) in &mut self.rows {
-            let row_op = match nums[self.row_ops_idx] {
-                TableauNumber::RowOperation(Some(op)) => op,
+    /// Return a reference to the bottom row of the table.
+    fn bottom_row(&self) -> &(RowLabel<'v>, Vec<TableauNumber>) {
+        self.rows.last().expect("There should be a bottom row")
+    }
+
+    /// Return the values in the theta column.
+    fn theta_column(&self) -> Vec<Option<Frac>> {
+        self.rows
+            .iter()
+            .map(|(_, numbers)| match numbers[self.theta_idx] {
+                TableauNumber::Theta(n) => n,
+                other => panic!("The theta column must only contain theta values, not {other:?}"),
+            })
+            .collect()
+    }
+
+    /// Return the number in the bottom row of each variable column, as a pair of the multiple of
+    /// `M` and the constant. Since `M` is bigger than any constant, comparing these pairs
+    /// lexicographically compares the numbers. The multiples of `M` are all 0 unless we're using
+    /// the Big-M method.
+    ///
+    /// If the artificial columns were kept for the second stage of the two-stage simplex, then
+    /// their costs are given as 0, so that they can never be chosen as the pivot column.
+    fn bottom_row_costs(&self) -> Vec<(Frac, Frac)> {
+        let simple_nums = |nums: &[TableauNumber]| -> Vec<Frac> {
+            nums[..self.value_idx]
+                .iter()
+                .map(|n| *n.simple_num())
+                .collect()
+        };
+
+        let (label, bottom_nums) = self.bottom_row();
+        if *label == RowLabel::BigMPenalty {
+            let (_, objective_nums) = self
+                .rows
+                .iter()
+                .find(|(label, _)| *label == RowLabel::ObjectiveFunction)
+                .expect("The tableau must have an objective function row");
+            simple_nums(bottom_nums)
+                .into_iter()
+                .zip(simple_nums(objective_nums))
+                .collect()
+        } else {
+            let ignore_artificials = *label == RowLabel::ObjectiveFunction;
+            simple_nums(bottom_nums)
+                .into_iter()
+                .zip(&self.column_labels)
+                .map(|(n, col_label)| match col_label {
+                    ColumnLabel::Variable(var) if ignore_artificials && var.is_artificial() => {
+                        (Frac::zero(), Frac::zero())
+                    }
+                    _ => (Frac::zero(), n),
+                })
+                .collect()
+        }
+    }
+
+    /// Check if there are any negative numbers in the bottom row of the tableau.
+    pub fn negatives_in_bottom_row(&self) -> bool {
+        self.bottom_row_costs()
+            .iter()
+            .any(|&cost| cost < (Frac::zero(), Frac::zero()))
+    }
+
+    /// Check if there are any negative numbers in the value column of the tableau, which means
+    /// that the current basic solution isn't feasible.
+    pub fn negatives_in_value_column(&self) -> bool {
+        self.rows.iter().any(|(label, nums)| {
+            matches!(label, RowLabel::Variable(_))
+                && *nums[self.value_idx].simple_num() < Frac::zero()
+        })
+    }
+
+    /// Return the index of the pivot column. This is calculated by finding the most negative
+    /// number in the bottom row, and ties are broken according to the [`PivotRule`]. If that
+    /// doesn't separate the tied columns, then we choose the first or last of them according to
+    /// the [`ColumnTieBreak`].
+    ///
+    /// With anti-cycling on, we use Bland's rule instead, and choose the first column with a
+    /// negative number in the bottom row. With [`PivotRule::Random`], we choose any of those
+    /// columns at random.
+    fn find_pivot_column(&self) -> usize {
+        let zero = (Frac::zero(), Frac::zero());
+        let costs = self.bottom_row_costs();
+
+        if self.anti_cycling {
+            return costs.iter().position(|&cost| cost < zero).unwrap_or(0);
+        }
+
+        if self.pivot_rule == PivotRule::Random {
+            let negative_columns = costs.iter().positions(|&cost| cost < zero).collect_vec();
+            let mut rng = StdRng::seed_from_u64(self.rng_seed.wrapping_add(self.iterations));
+            return negative_columns.choose(&mut rng).copied().unwrap_or(0);
+        }
+
+        costs
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, cost)| cost < zero)
+            .fold((0, zero), |(acc_idx, acc_min), (this_idx, this_num)| {
+                if this_num < acc_min
+                    || (this_num == acc_min && self.prefer_tied_column(this_idx, acc_idx))
+                {
+                    (this_idx, this_num)
+                } else {
+                    (acc_idx, acc_min)
+                }
+            })
+            .0
+    }
+
+    /// Check if the column at `this_idx` should be chosen over the column at `acc_idx` when they
+    /// have the same number in the bottom row, according to the [`PivotRule`] and then the
+    /// [`ColumnTieBreak`].
+    fn prefer_tied_column(&self, this_idx: usize, acc_idx: usize) -> bool {
+        match self.pivot_rule {
+            PivotRule::MostNegative | PivotRule::Lexicographic | PivotRule::Random => {
+                match self.column_tie_break {
+                    ColumnTieBreak::LowestIndex => this_idx < acc_idx,
+                    ColumnTieBreak::HighestIndex => this_idx > acc_idx,
+                }
+            }
+            PivotRule::PreferOriginal => {
+                match (&self.column_labels[this_idx], &self.column_labels[acc_idx]) {
+                    (ColumnLabel::Variable(this_var), ColumnLabel::Variable(acc_var)) => {
+                        this_var < acc_var
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// Return the index of the pivot row. This is calculated by finding the smallest positive
+    /// theta value, unless we're using [`PivotRule::Lexicographic`].
+    fn find_pivot_row(&self, pivot_col: usize) -> usize {
+        if self.anti_cycling {
+            return self.find_pivot_row_bland(pivot_col);
+        }
+
+        if self.pivot_rule == PivotRule::Lexicographic {
+            return self.find_pivot_row_lexicographic(pivot_col);
+        }
+
+        // Only rows with a positive number in the pivot column limit how far we can increase the
+        // pivot variable, and a theta of 0 is still valid when the value in that row is 0
+        self.theta_column()
+            .iter()
+            .zip(&self.rows)
+            .enumerate()
+            .filter_map(|(idx, (&theta, (_, nums)))| match theta {
+                Some(n) if *nums[pivot_col].simple_num() > Frac::zero() && n >= Frac::zero() => {
+                    Some((idx, n))
+                }
+                _ => None,
+            })
+            .fold(
+                (0, Frac::infinity()),
+                |(acc_idx, acc_min), (this_idx, this_num)| {
+                    if this_num < acc_min {
+                        (this_idx, this_num)
+                    } else {
+                        (acc_idx, acc_min)
+                    }
+                },
+            )
+            .0
+    }
+
+    /// Return the index of the pivot row using Bland's rule.
+    ///
+    /// We choose the smallest ratio of the value to the positive numbers in the pivot column, and
+    /// break ties by choosing the row whose basic variable has the smallest column index.
+    fn find_pivot_row_bland(&self, pivot_col: usize) -> usize {
+        let basic_column_idx = |label: &RowLabel<'v>| {
+            self.column_labels
+                .iter()
+                .position(|col_label| match (col_label, label) {
+                    (ColumnLabel::Variable(col_var), RowLabel::Variable(row_var)) => {
+                        col_var == row_var
+                    }
+                    _ => false,
+                })
+                .unwrap_or(usize::MAX)
+        };
+
+        self.rows
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (label, nums))| {
+                let num = *nums[pivot_col].simple_num();
+                if matches!(label, RowLabel::Variable(_)) && num > Frac::zero() {
+                    let ratio = *nums[self.value_idx].simple_num() / num;
+                    Some((idx, ratio, basic_column_idx(label)))
+                } else {
+                    None
+                }
+            })
+            .min_by(|(_, this_ratio, this_col), (_, other_ratio, other_col)| {
+                this_ratio
+                    .partial_cmp(other_ratio)
+                    .expect("Finite fractions should always be comparable")
+                    .then(this_col.cmp(other_col))
+            })
+            .map_or(0, |(idx, _, _)| idx)
+    }
+
+    /// Return the index of the pivot row using the lexicographic ratio test.
+    ///
+    /// Each row with a positive number in the pivot column is divided by that number, and we
+    /// choose the lexicographically smallest result, comparing the value column first, then the
+    /// slack and artificial columns (which start as the identity), and then the rest. This is the
+    /// same as perturbing the constants by `ε, ε², ...` and taking the smallest theta value, so
+    /// there are never any ties and the algorithm can't cycle.
+    fn find_pivot_row_lexicographic(&self, pivot_col: usize) -> usize {
+        let column_order: Vec<usize> = iter::once(self.value_idx)
+            .chain(
+                (0..self.value_idx).sorted_by_key(|&idx| match self.column_labels[idx] {
+                    ColumnLabel::Variable(var) if var.is_slack() || var.is_artificial() => 0,
+                    _ => 1,
+                }),
+            )
+            .collect();
+
+        let scaled_row = |nums: &[TableauNumber]| -> Vec<Frac> {
+            let divisor = *nums[pivot_col].simple_num();
+            column_order
+                .iter()
+                .map(|&idx| *nums[idx].simple_num() / divisor)
+                .collect()
+        };
+
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, (label, nums))| {
+                matches!(label, RowLabel::Variable(_))
+                    && *nums[pivot_col].simple_num() > Frac::zero()
+            })
+            .map(|(idx, (_, nums))| (idx, scaled_row(nums)))
+            .min_by(|(_, this_row), (_, other_row)| {
+                this_row
+                    .partial_cmp(other_row)
+                    .expect("Finite fractions should always be comparable")
+            })
+            .map_or(0, |(idx, _)| idx)
+    }
+
+    /// Populate this tableau with theta values for the given pivot column. Rows with a 0 in the
+    /// pivot column don't get a theta value, since they don't limit the pivot variable.
+    fn populate_theta_values(&mut self, pivot_col: usize) {
+        for (label, numbers) in &mut self.rows {
+            match label {
+                RowLabel::Variable(_) => {
+                    let divisor = *numbers[pivot_col].simple_num();
+                    numbers[self.theta_idx] = TableauNumber::Theta(if divisor.is_zero() {
+                        None
+                    } else {
+                        Some(numbers[self.value_idx].simple_num() / divisor)
+                    });
+                }
+                RowLabel::ObjectiveFunction
+                | RowLabel::TwoStageArtificial
+                | RowLabel::BigMPenalty => (),
+            }
+        }
+    }
+
+    /// Change the label of the pivot row to be that of the pivot column.
+    fn change_pivot_row_label(&mut self, pivot_row: usize, pivot_col: usize) {
+        self.rows[pivot_row].0 = self.column_labels[pivot_col]
+            .clone()
+            .try_into()
+            .expect("The pivot column should have a variable label");
+    }
+
+    /// Populate this tableau with row operations for the given pivot row and column. This fails
+    /// without changing the tableau if the pivot element is 0.
+    fn populate_row_ops(&mut self, pivot_row: usize, pivot_col: usize) -> Result<(), ZeroPivot> {
+        if self.rows[pivot_row].1[pivot_col].simple_num().is_zero() {
+            error!(pivot_row, pivot_col, err = %ZeroPivot);
+            return Err(ZeroPivot);
+        }
+
+        for (idx, (_label, nums)) in self.rows.iter_mut().enumerate() {
+            if idx == pivot_row {
+                nums[self.row_ops_idx] = TableauNumber::RowOperation(Some(RowOperation::MulConst(
+                    nums[pivot_col].simple_num().recip(),
+                )));
+            } else {
+                let row_op_coeff = -nums[pivot_col].simple_num();
+                nums[self.row_ops_idx] = if row_op_coeff != Frac::zero() {
+                    TableauNumber::RowOperation(Some(RowOperation::AddRow(row_op_coeff, pivot_row)))
+                } else {
+                    TableauNumber::RowOperation(Some(RowOperation::Nop))
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Perform the row operations that were previously calculated for the given pivot row, and
+    /// then clear the theta and row op columns.
+    fn perform_row_ops(&mut self, pivot_row: usize) {
+        // First pass to apply row op to the pivot row
+        for (_label, nums) in &mut self.rows {
+            let row_op = match nums[self.row_ops_idx] {
+                TableauNumber::RowOperation(Some(op)) => op,
                 other => panic!("The row op must exist at this point and not be {other:?}"),
             };
 
@@ -582,10 +1391,12 @@ impl<'v> Tableau<'v> {
                 }
                 // Do nothing on this pass
                 RowOperation::AddRow(_, _) => (),
+                RowOperation::AddRowBigM(..) => {
+                    unreachable!("Big-M row operations are only used for display")
+                }
             };
         }
 
-        let pivot_row = self.find_pivot_row();
         let (_, pivot_row_nums) = self.rows[pivot_row].clone();
 
         // Second pass to apply other row ops and clear theta and row op columns
@@ -611,7 +1422,7 @@ impl<'v> Tableau<'v> {
                     }
                 }
                 // These have already been dealt with
-                RowOperation::Nop | RowOperation::MulConst(_) => (),
+                RowOperation::Nop | RowOperation::MulConst(_) | RowOperation::AddRowBigM(..) => (),
             }
 
             // Clear the theta and row op columns
@@ -620,18 +1431,130 @@ impl<'v> Tableau<'v> {
         }
     }
 
+    /// Pivot on the given row and column, by changing the label of the pivot row, and then
+    /// populating and performing the row operations. The `working` closure gets called with the
+    /// tableau once the row operations are populated, before they're performed.
+    ///
+    /// If the pivot element is 0, then this returns an error without changing the tableau.
+    fn pivot(
+        &mut self,
+        pivot_row: usize,
+        pivot_col: usize,
+        working: &mut impl FnMut(&Self),
+    ) -> Result<(), ZeroPivot> {
+        self.populate_row_ops(pivot_row, pivot_col)?;
+        self.change_pivot_row_label(pivot_row, pivot_col);
+        debug!(%self, "After populating row ops and changing pivot row label");
+        working(self);
+
+        self.perform_row_ops(pivot_row);
+        info!(%self, "After performing row ops");
+        Ok(())
+    }
+
+    /// Pivot any artificial variables which are still basic at the end of the first stage of the
+    /// two stage simplex out of the basis, so that removing their columns doesn't leave them
+    /// behind in the basis, where later pivots could make them non-zero.
+    ///
+    /// They must have a value of 0 at this point, so pivoting on any non-zero number in their row
+    /// keeps the solution feasible, even if it's negative. If there isn't one, then the constraint
+    /// that the row came from is redundant, so we just remove the row.
+    fn drive_out_artificial_variables(&mut self) {
+        while let Some(row) = self
+            .rows
+            .iter()
+            .position(|(label, _)| matches!(label, RowLabel::Variable(var) if var.is_artificial()))
+        {
+            let col = self
+                .column_labels
+                .iter()
+                .take(self.value_idx)
+                .enumerate()
+                .position(|(idx, label)| match label {
+                    ColumnLabel::Variable(var) => {
+                        !var.is_artificial() && !self.rows[row].1[idx].simple_num().is_zero()
+                    }
+                    ColumnLabel::BasicString(_) => false,
+                });
+
+            match col {
+                Some(col) => {
+                    debug!(row, col, "Pivoting artificial variable out of the basis");
+                    self.pivot(row, col, &mut |_| ())
+                        .expect("The pivot element was chosen to be non-zero");
+                }
+                None => {
+                    debug!(row, "Removing redundant row of artificial variable");
+                    self.rows.remove(row);
+                }
+            }
+        }
+    }
+
+    /// Remove the columns of the artificial variables, which are no longer needed after the first
+    /// stage of the two stage simplex.
+    fn remove_artificial_columns(&mut self) {
+        // Get the indices of the artificial variable columns
+        let artificial_indices = self
+            .column_labels
+            .iter()
+            .enumerate()
+            .filter(
+                |&(_, label)| matches!(label, ColumnLabel::Variable(var) if var.is_artificial()),
+            )
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+
+        // Remove the artificial variables from the rows of the tableau
+        for (_label, numbers) in self.rows.iter_mut() {
+            *numbers = numbers
+                .iter_mut()
+                .enumerate()
+                .filter(|(idx, _)| !artificial_indices.contains(idx))
+                .map(|(_, num)| *num)
+                .collect();
+        }
+
+        // Keep all the non-artificial variables in the column labels
+        self.column_labels
+            .retain(|label| !matches!(label, ColumnLabel::Variable(var) if var.is_artificial()));
+
+        // Update the internal indices
+        let offset = artificial_indices.len();
+        self.value_idx -= offset;
+        self.theta_idx -= offset;
+        self.row_ops_idx -= offset;
+    }
+
     /// Do a single iteration of the simplex tableaux algorithm.
-    #[instrument(skip(self))]
-    pub fn do_iteration(&mut self) -> Result<(), NoFeasibleSolution> {
-        self.populate_theta_values();
+    pub fn do_iteration(&mut self) -> Result<(), IterationError> {
+        self.do_iteration_showing_working(|_| ())
+    }
+
+    /// Do a single iteration of the simplex tableaux algorithm, calling `working` with the tableau
+    /// once its theta values and row operations are populated, before the row operations are
+    /// performed. This is the tableau you'd write out when showing your working.
+    #[instrument(skip(self, working))]
+    pub fn do_iteration_showing_working(
+        &mut self,
+        mut working: impl FnMut(&Self),
+    ) -> Result<(), IterationError> {
+        let pivot_col = self.find_pivot_column();
+        self.populate_theta_values(pivot_col);
         debug!(%self, "After populating theta values");
 
-        self.change_pivot_row_label();
-        self.populate_row_ops();
-        debug!(%self, "After populating row ops and changing pivot row label");
+        // If nothing in the pivot column is positive, then the pivot variable can increase
+        // forever without any basic variables becoming negative
+        if !self.rows.iter().any(|(label, nums)| {
+            matches!(label, RowLabel::Variable(_)) && *nums[pivot_col].simple_num() > Frac::zero()
+        }) {
+            error!(err = %IterationError::Unbounded);
+            return Err(IterationError::Unbounded);
+        }
 
-        self.perform_row_ops();
-        info!(%self, "After performing row ops");
+        let pivot_row = self.find_pivot_row(pivot_col);
+        self.pivot(pivot_row, pivot_col, &mut working)?;
+        self.iterations += 1;
 
         // If there are no negatives in the bottom row, then we need to check the value
         let bottom_row = self.bottom_row();
@@ -640,191 +1563,835 @@ impl<'v> Tableau<'v> {
             // solution. Otherwise, there is no feasible solution
             if bottom_row.1[self.value_idx] == TableauNumber::Simple(Frac::zero()) {
                 // Remove the bottom row
+                self.drive_out_artificial_variables();
                 self.rows.remove(self.rows.len() - 1);
-
-                // Get the indices of the artificial variable columns
-                let artificial_indices = self
-                    .column_labels
-                    .iter()
-                    .enumerate()
-                    .filter(|&(_, label)| {
-                        matches!(label, ColumnLabel::Variable(VariableType::Artificial(_)))
-                    })
-                    .map(|(idx, _)| idx)
-                    .collect::<Vec<_>>();
-
-                // Remove the artificial variables from the rows of the tableau
-                for (_label, numbers) in self.rows.iter_mut() {
-                    *numbers = numbers
-                        .iter_mut()
-                        .enumerate()
-                        .filter(|(idx, _)| !artificial_indices.contains(idx))
-                        .map(|(_, num)| *num)
-                        .collect();
+                if !self.keep_artificial_columns {
+                    self.remove_artificial_columns();
                 }
 
-                // Keep all the non-artificial variables in the column labels
-                self.column_labels.retain(|label| {
-                    !matches!(label, ColumnLabel::Variable(VariableType::Artificial(_)))
-                });
-
-                // Update the internal indices
-                let offset = artificial_indices.len();
-                self.value_idx -= offset;
-                self.theta_idx -= offset;
-                self.row_ops_idx -= offset;
-
                 debug!(%self, "After removing TwoStageAr#");
             } else {
                 error!(err = %NoFeasibleSolution {});
-                return Err(NoFeasibleSolution);
+                return Err(NoFeasibleSolution.into());
             }
         }
 
+        // With the Big-M method, if the tableau is optimal but an artificial variable is still
+        // positive, then there is no feasible solution
+        let bottom_row = self.bottom_row();
+        if bottom_row.0 == RowLabel::BigMPenalty
+            && !self.negatives_in_bottom_row()
+            && self.rows.iter().any(|(label, nums)| {
+                matches!(label, RowLabel::Variable(var) if var.is_artificial())
+                    && *nums[self.value_idx].simple_num() > Frac::zero()
+            })
+        {
+            error!(err = %NoFeasibleSolution {});
+            return Err(NoFeasibleSolution.into());
+        }
+
         Ok(())
     }
 
-    pub fn get_solution(self) -> SolutionSet<'v> {
-        if self.negatives_in_bottom_row() {
-            panic!("There must not be negatives in the bottom row when getting the solution");
+    /// Return the variables which will enter and leave the basis in the next iteration, in that
+    /// order, or `None` if the tableau is already optimal or the next pivot column has nothing
+    /// positive in it, meaning the problem is unbounded.
+    pub fn next_pivot(&self) -> Option<(VariableType<'v>, VariableType<'v>)> {
+        let (pivot_row, pivot_col) = self.find_next_pivot()?;
+
+        match (&self.column_labels[pivot_col], &self.rows[pivot_row].0) {
+            (ColumnLabel::Variable(entering), RowLabel::Variable(leaving)) => {
+                Some((*entering, *leaving))
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the value of the pivot element for the next iteration, before any row operations are
+    /// applied, or `None` if the tableau is already optimal or the problem is unbounded. See
+    /// [`Tableau::next_pivot`].
+    pub fn pivot_element(&self) -> Option<Frac> {
+        let (pivot_row, pivot_col) = self.find_next_pivot()?;
+        Some(*self.rows[pivot_row].1[pivot_col].simple_num())
+    }
+
+    /// Return the indices of the pivot row and pivot column for the next iteration, or `None` if
+    /// the tableau is already optimal or the pivot column has nothing positive in it.
+    fn find_next_pivot(&self) -> Option<(usize, usize)> {
+        if !self.negatives_in_bottom_row() {
+            return None;
+        }
+
+        let pivot_col = self.find_pivot_column();
+        if !self.rows.iter().any(|(label, nums)| {
+            matches!(label, RowLabel::Variable(_)) && *nums[pivot_col].simple_num() > Frac::zero()
+        }) {
+            return None;
+        }
+
+        let mut tableau = self.clone();
+        tableau.populate_theta_values(pivot_col);
+        Some((tableau.find_pivot_row(pivot_col), pivot_col))
+    }
+
+    /// Return the row operations which have been populated for the current pivot, as pairs of the
+    /// label of each row and its operation, as they appear in the row op column when displaying
+    /// the tableau. Rows without an operation, or with a no-op, are skipped.
+    pub fn row_operations(&self) -> Vec<(String, String)> {
+        self.display_rows()
+            .iter()
+            .filter_map(|(label, nums)| match nums[self.row_ops_idx] {
+                TableauNumber::RowOperation(Some(op)) if op != RowOperation::Nop => {
+                    Some((label.to_string(), op.to_string()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Explain in words which variable will enter and which will leave the basis in the next
+    /// iteration, and why, according to the [`PivotRule`] or Bland's rule. This is meant for
+    /// students learning the method, so ties are only mentioned when there is one.
+    pub fn explain_next_pivot(&self) -> String {
+        let pivot_col = self.find_pivot_column();
+        let entering = self.column_labels[pivot_col].display_with(self.variable_naming);
+
+        let costs = self.bottom_row_costs();
+        let (m, c) = costs[pivot_col];
+        let cost = if self.bottom_row().0 == RowLabel::BigMPenalty {
+            big_m_to_text(m, c)
+        } else {
+            c.to_string()
+        };
+        let column_tie = costs.iter().filter(|&&other| other == (m, c)).count() > 1;
+
+        let entering_sentence = if self.anti_cycling {
+            format!(
+                "The first column with a negative reduced cost is {entering}, with {cost}, so \
+                {entering} enters by Bland's rule."
+            )
+        } else if self.pivot_rule == PivotRule::Random {
+            format!(
+                "Column {entering} has a negative reduced cost of {cost} and was chosen at \
+                random, so {entering} enters."
+            )
+        } else {
+            let tie_break = match (column_tie, self.pivot_rule) {
+                (false, _) => "",
+                (true, PivotRule::PreferOriginal) => {
+                    ", which is preferred to the other tied columns by its type of variable"
+                }
+                (true, PivotRule::MostNegative | PivotRule::Lexicographic | PivotRule::Random) => {
+                    match self.column_tie_break {
+                        ColumnTieBreak::LowestIndex => ", which is the first of the tied columns",
+                        ColumnTieBreak::HighestIndex => ", which is the last of the tied columns",
+                    }
+                }
+            };
+            format!(
+                "Most negative reduced cost is {cost} under column {entering}{tie_break}, so \
+                {entering} enters."
+            )
+        };
+
+        let ratios: Vec<Frac> = self
+            .rows
+            .iter()
+            .filter(|(label, nums)| {
+                matches!(label, RowLabel::Variable(_))
+                    && *nums[pivot_col].simple_num() > Frac::zero()
+            })
+            .map(|(_, nums)| nums[self.value_idx].simple_num() / nums[pivot_col].simple_num())
+            .collect();
+        if ratios.is_empty() {
+            return format!(
+                "{entering_sentence} Nothing in column {entering} is positive, so it can increase \
+                forever and the problem is unbounded."
+            );
+        }
+
+        let mut tableau = self.clone();
+        tableau.populate_theta_values(pivot_col);
+        let pivot_row = tableau.find_pivot_row(pivot_col);
+        let leaving = self.rows[pivot_row].0.display_with(self.variable_naming);
+        let ratio = self.rows[pivot_row].1[self.value_idx].simple_num()
+            / self.rows[pivot_row].1[pivot_col].simple_num();
+        let ratio_kind = if ratio > Frac::zero() {
+            "minimum positive ratio"
+        } else {
+            "minimum ratio"
+        };
+
+        let tie_break = match ratios.iter().filter(|&&other| other == ratio).count() > 1 {
+            false => "",
+            true if self.anti_cycling => ", breaking the tie by Bland's rule",
+            true if self.pivot_rule == PivotRule::Lexicographic => {
+                ", breaking the tie lexicographically"
+            }
+            true => ", which is the first of the tied rows",
+        };
+
+        format!(
+            "{entering_sentence} The {ratio_kind} is {ratio} in row {leaving}{tie_break}, so \
+            {leaving} leaves."
+        )
+    }
+
+    /// Generate a tableau for the given system, and then pivot it so that the given variables are
+    /// basic. This is used to warm start the algorithm from the basis of a previous tableau.
+    ///
+    /// The basis must have one variable for each row, with no repeats and no artificial
+    /// variables, and the resulting tableau may have negative values in its value column if the
+    /// basis isn't feasible for this system.
+    #[instrument(skip(system))]
+    pub fn create_with_basis(
+        system: &'v LinProgSystem,
+        basis: &[VariableType<'_>],
+    ) -> Result<Self> {
+        let mut tableau = Self::create_initial(system)?;
+
+        let rows = tableau.basis().len();
+        if basis.len() != rows {
+            return Err(Report::msg(format!(
+                "The basis has {} variables, but the tableau has {rows} rows",
+                basis.len()
+            )));
+        }
+        if let Some(var) = basis.iter().duplicates().next() {
+            return Err(Report::msg(format!(
+                "Variable {var} is in the basis more than once"
+            )));
+        }
+
+        for var in basis {
+            if var.is_artificial() {
+                return Err(Report::msg(format!(
+                    "Cannot warm start with artificial variable {var} in the basis"
+                )));
+            }
+
+            let pivot_col = tableau
+                .column_labels
+                .iter()
+                .position(|label| matches!(label, ColumnLabel::Variable(v) if v == var))
+                .ok_or_else(|| Report::msg(format!("Variable {var} is not in the tableau")))?;
+
+            if tableau
+                .rows
+                .iter()
+                .any(|(label, _)| matches!(label, RowLabel::Variable(v) if v == var))
+            {
+                continue;
+            }
+
+            // Replace a basic variable that isn't in the new basis
+            let pivot_row = tableau
+                .rows
+                .iter()
+                .position(|(label, nums)| match label {
+                    RowLabel::Variable(v) => {
+                        !basis.iter().any(|b| b == v)
+                            && *nums[pivot_col].simple_num() != Frac::zero()
+                    }
+                    RowLabel::ObjectiveFunction
+                    | RowLabel::TwoStageArtificial
+                    | RowLabel::BigMPenalty => false,
+                })
+                .ok_or_else(|| Report::msg(format!("Unable to make {var} basic")))?;
+
+            tableau.pivot(pivot_row, pivot_col, &mut |_| ())?;
+        }
+
+        if matches!(
+            tableau.bottom_row().0,
+            RowLabel::TwoStageArtificial | RowLabel::BigMPenalty
+        ) {
+            if tableau
+                .rows
+                .iter()
+                .any(|(label, _)| matches!(label, RowLabel::Variable(v) if v.is_artificial()))
+            {
+                return Err(Report::msg(
+                    "The basis must replace every artificial variable",
+                ));
+            }
+
+            tableau.rows.remove(tableau.rows.len() - 1);
+            tableau.remove_artificial_columns();
+        }
+
+        Ok(tableau)
+    }
+
+    /// Return the basic variables of this tableau, in the order of the rows.
+    pub fn basis(&self) -> Vec<VariableType<'v>> {
+        self.rows
+            .iter()
+            .filter_map(|(label, _)| match label {
+                RowLabel::Variable(var) => Some(*var),
+                RowLabel::ObjectiveFunction
+                | RowLabel::TwoStageArtificial
+                | RowLabel::BigMPenalty => None,
+            })
+            .collect()
+    }
+
+    /// Return the current value of the given variable, which is the value in its row if it's basic,
+    /// or 0 if it isn't.
+    fn variable_value(&self, var: VariableType<'v>) -> Frac {
+        let value = self
+            .rows
+            .iter()
+            .find_map(|(row_label, nums)| {
+                if *row_label == RowLabel::Variable(var) {
+                    Some(*nums[self.value_idx].simple_num())
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(Frac::zero());
+        let value = match self.variable_scales.get(&var) {
+            Some(&scale) => value / scale,
+            None => value,
+        };
+
+        // The column of a non-positive variable holds its negation
+        match var {
+            VariableType::Original(name)
+                if self
+                    .system
+                    .borrow_config()
+                    .non_positive_variables
+                    .contains(name) =>
+            {
+                -value
+            }
+            _ => value,
         }
+    }
 
-        // Find the value of the objective function.
-        let mut objective_function_value = *self
+    /// Return the current values of the original variables, sorted by name. These are the
+    /// coordinates of the vertex which this tableau is at, although the first stage of the
+    /// two-stage simplex can start at a vertex outside the feasible region.
+    pub fn vertex(&self) -> Vec<(&'v str, Frac)> {
+        self.column_labels
+            .iter()
+            .filter_map(|label| match *label {
+                ColumnLabel::Variable(var @ VariableType::Original(name)) => {
+                    Some((name, self.variable_value(var)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Return the current value of the objective function in this tableau.
+    pub fn objective_function_value(&self) -> Frac {
+        let value = *self
             .rows
             .iter()
             .find(|&(label, _)| matches!(label, RowLabel::ObjectiveFunction))
             .expect("The objective function must have a value")
             .1[self.value_idx]
-            .simple_num();
+            .simple_num()
+            / self.objective_scale;
 
         if self.minimise {
-            objective_function_value *= -1.;
+            -value
+        } else {
+            value
+        }
+    }
+
+    /// Do a single iteration of the dual simplex algorithm, which keeps the bottom row
+    /// non-negative while removing negatives from the value column.
+    ///
+    /// The pivot row has the most negative value, and the pivot column is the one with the
+    /// smallest ratio of the bottom row to the negative numbers in the pivot row. If there are no
+    /// negative numbers in the pivot row, then there is no feasible solution.
+    #[instrument(skip(self))]
+    pub fn do_dual_iteration(&mut self) -> Result<(), NoFeasibleSolution> {
+        let (pivot_row, _) = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (label, nums))| match label {
+                RowLabel::Variable(_) => Some((idx, *nums[self.value_idx].simple_num())),
+                RowLabel::ObjectiveFunction
+                | RowLabel::TwoStageArtificial
+                | RowLabel::BigMPenalty => None,
+            })
+            .fold(
+                (0, Frac::zero()),
+                |(acc_idx, acc_min), (this_idx, this_num)| {
+                    if this_num < acc_min {
+                        (this_idx, this_num)
+                    } else {
+                        (acc_idx, acc_min)
+                    }
+                },
+            );
+
+        let pivot_row_nums = &self.rows[pivot_row].1;
+        let bottom_row_nums = &self.bottom_row().1;
+        let pivot_col = (0..self.value_idx)
+            .filter_map(|idx| {
+                let num = *pivot_row_nums[idx].simple_num();
+                if num < Frac::zero() {
+                    Some((idx, *bottom_row_nums[idx].simple_num() / -num))
+                } else {
+                    None
+                }
+            })
+            .fold(
+                None,
+                |acc: Option<(usize, Frac)>, (this_idx, this_ratio)| match acc {
+                    Some((_, acc_ratio)) if acc_ratio <= this_ratio => acc,
+                    _ => Some((this_idx, this_ratio)),
+                },
+            );
+
+        match pivot_col {
+            Some((pivot_col, _)) => {
+                self.pivot(pivot_row, pivot_col, &mut |_| ())
+                    .expect("The pivot element was chosen to be negative");
+                Ok(())
+            }
+            None => {
+                error!(err = %NoFeasibleSolution {});
+                Err(NoFeasibleSolution)
+            }
+        }
+    }
+
+    /// Does this optimal tableau have alternative optima? That's when a variable which isn't
+    /// currently basic has a 0 in the objective row, since pivoting on its column would give a
+    /// different solution with the same value of the objective function. Artificial variables
+    /// aren't part of the actual problem, so we ignore them.
+    pub fn has_alternative_optima(&self) -> bool {
+        let basis = self.basis();
+
+        self.column_labels
+            .iter()
+            .zip(self.bottom_row_costs())
+            .any(|(label, cost)| match label {
+                ColumnLabel::Variable(var) => {
+                    !var.is_artificial()
+                        && !basis.contains(var)
+                        && cost == (Frac::zero(), Frac::zero())
+                }
+                ColumnLabel::BasicString(_) => false,
+            })
+    }
+
+    /// Read the shadow price of each constraint from the bottom row of this tableau. See
+    /// [`SolutionSet::shadow_prices`] for the sign convention.
+    ///
+    /// Systems in standard form have no slack or surplus columns, so they have no shadow prices.
+    fn shadow_prices(&self) -> HashMap<usize, Frac> {
+        if self.system.borrow_config().standard_form {
+            return HashMap::new();
         }
 
+        let objective_row = &self
+            .rows
+            .iter()
+            .find(|(label, _)| *label == RowLabel::ObjectiveFunction)
+            .expect("The tableau must have an objective function row")
+            .1;
+        // Scaling a constraint by `k` divides its price by `k`, and scaling the objective
+        // function multiplies every price
+        let entry = |var: VariableType<'v>| {
+            let idx = self
+                .column_labels
+                .iter()
+                .position(|label| *label == ColumnLabel::Variable(var))
+                .expect("Every slack and surplus variable should have a column");
+            let scale = self
+                .variable_scales
+                .get(&var)
+                .copied()
+                .unwrap_or(Frac::one());
+            *objective_row[idx].simple_num() * scale / self.objective_scale
+        };
+
+        let mut slack_counter = 0;
+        let mut surplus_counter = 0;
+        self.system.with_constraints(|cons| {
+            cons.iter()
+                .enumerate()
+                .filter_map(|(idx, constraint)| {
+                    // The constraints were normalised when creating the initial tableau, so we
+                    // need to match them to their slack and surplus variables in the same way
+                    let flipped = constraint.constant < Frac::zero();
+                    let comparison = if flipped {
+                        constraint.comparison.flip()
+                    } else {
+                        constraint.comparison
+                    };
+
+                    // Increasing the constant of a `≤` constraint is like decreasing its slack
+                    // variable, and the bottom row holds the coefficients of the objective
+                    // function when it's written as `P + ... = value`
+                    let price = match comparison {
+                        Comparison::LessThanOrEqual => {
+                            slack_counter += 1;
+                            entry(VariableType::Slack(slack_counter - 1))
+                        }
+                        Comparison::GreaterThanOrEqual => {
+                            surplus_counter += 1;
+                            -entry(VariableType::Surplus(surplus_counter - 1))
+                        }
+                        _ => return None,
+                    };
+
+                    // Normalising negates the constant, and minimising negates the bottom row
+                    let price = if flipped != self.minimise {
+                        -price
+                    } else {
+                        price
+                    };
+                    Some((idx, price))
+                })
+                .collect()
+        })
+    }
+
+    /// Get the solution from this optimal tableau. This ignores any integer requirements of the
+    /// system, which are handled by [`branch_and_bound`](crate::simplex::branch_and_bound).
+    ///
+    /// If the tableau isn't optimal yet, or it has no objective function row, then there's no
+    /// solution to get, so we return an error.
+    pub fn get_solution(self) -> Result<SolutionSet<'v>, SolutionError> {
+        if !self
+            .rows
+            .iter()
+            .any(|(label, _)| *label == RowLabel::ObjectiveFunction)
+        {
+            return Err(SolutionError::MissingObjectiveRow);
+        }
+        if self.negatives_in_bottom_row() {
+            return Err(SolutionError::NotOptimal);
+        }
+
+        // The constant in the objective function isn't in the tableau, so we add it here
+        let constant = self
+            .system
+            .with_objective_function(|obj_func| obj_func.constant());
+        let objective_function_value = self.objective_function_value() + constant;
+        // With the Big-M method, artificial variables are still in the tableau, but they aren't
+        // part of the solution
+        let basic_variables: HashSet<VariableType> = self
+            .basis()
+            .into_iter()
+            .filter(|var| !var.is_artificial())
+            .collect();
+        let shadow_prices = self.shadow_prices();
+        let has_alternative_optima = self.has_alternative_optima();
+
         let variable_values: HashMap<VariableType, Frac> = self
             // Get the variables from the column labels
             .column_labels
-            .into_iter()
-            .filter_map(|label| match label {
-                ColumnLabel::Variable(var) => Some(var),
+            .iter()
+            .filter_map(|label| match *label {
+                ColumnLabel::Variable(var) if !var.is_artificial() => Some(var),
                 _ => None,
             })
-            // Find the values for each basic variable, defaulting to 0 if there's no row for them
-            .map(|var| {
-                (
-                    var,
-                    self.rows
-                        .iter()
-                        .find_map(|(row_label, nums)| {
-                            if *row_label == RowLabel::Variable(var) {
-                                Some(*nums[self.value_idx].simple_num())
-                            } else {
-                                None
-                            }
-                        })
-                        .unwrap_or(Frac::zero()),
-                )
-            })
+            .map(|var| (var, self.variable_value(var)))
             .collect();
 
-        if !self.integer_solutions {
-            SolutionSet {
-                objective_function_value,
-                variable_values,
+        Ok(SolutionSet {
+            objective_function_value,
+            variable_values,
+            basic_variables,
+            shadow_prices,
+            has_alternative_optima,
+            objective_name: None,
+            variable_naming: self.variable_naming,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lin_prog::{
+        config::Config, constraint::Constraint, expression::Expression,
+        system::LinProgSystemBuilder, ObjectiveFunction, Variables,
+    };
+    use tracing_test::traced_test;
+
+    /// A small system to maximise x + y subject to x + y ≤ 4 and x ≤ 3, with the given config.
+    fn small_system(config: Config) -> LinProgSystem {
+        LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config,
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("x + y", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("x + y <= 4", vars).unwrap().1,
+                    Constraint::nom_parse("x <= 3", vars).unwrap().1,
+                ]
+            },
+        }
+        .build()
+    }
+
+    #[test]
+    #[traced_test]
+    fn create_initial_deterministic_test() {
+        let build_system = |variables: [&str; 5]| {
+            LinProgSystemBuilder {
+                variables: Variables::from(variables),
+                config: Config::default(),
+                objective_function_builder: |vars| {
+                    ObjectiveFunction::Minimise(
+                        Expression::nom_parse("v + 2w + 3x + 4y + 5z", vars)
+                            .unwrap()
+                            .1,
+                        Frac::zero(),
+                    )
+                },
+                constraints_builder: |vars| {
+                    vec![
+                        Constraint::nom_parse("z + y + x <= 10", vars).unwrap().1,
+                        Constraint::nom_parse("v + w >= 2", vars).unwrap().1,
+                        Constraint::nom_parse("x + z >= 3", vars).unwrap().1,
+                    ]
+                },
             }
-        } else {
-            let variable_options: HashMap<&str, (Frac, Frac)> = variable_values
-                .iter()
+            .build()
+        };
 
-                // We only care about the original variables here
-                .filter_map(|(&var, &num)| match var {
-                    VariableType::Original(v) => Some((v, num)),
-                    _ => None,
-                })
+        let system = build_system(["v", "w", "x", "y", "z"]);
+        let expected = Tableau::create_initial(&system).unwrap().to_string();
 
-                .map(|(var, num)| (var, (num.floor(), num.ceil())))
-                .collect();
-            debug!(?variable_options);
+        // Every `HashSet` has its own random iteration order, so building the same system many
+        // times would catch any column order that depended on it
+        for _ in 0..20 {
+            let system = build_system(["z", "y", "x", "w", "v"]);
+            let tableau = Tableau::create_initial(&system).unwrap();
+            assert_eq!(tableau.to_string(), expected);
 
-            let var_count = variable_options.len();
-            let points_around_optimal = variable_options
-                .into_iter()
+            let original_columns = tableau
+                .column_labels
+                .iter()
+                .filter_map(|label| match label {
+                    ColumnLabel::Variable(VariableType::Original(name)) => Some(*name),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(original_columns, ["v", "w", "x", "y", "z"]);
+        }
+    }
 
-                // Split the interior tuples and flatten so we get a tuple for each possibility
-                .map(|(var, (a, b))| [(var, a), (var, b)])
-                .flatten()
+    #[test]
+    #[traced_test]
+    fn standard_form_test() {
+        let system = LinProgSystemBuilder {
+            variables: Variables::from(["x", "y", "s", "t"]),
+            config: Config {
+                standard_form: true,
+                ..Config::default()
+            },
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("3x + 2y", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("x + y + s = 4", vars).unwrap().1,
+                    // This is treated as an equality too, so `t` acts as its slack variable
+                    Constraint::nom_parse("x + 3y + t <= 6", vars).unwrap().1,
+                ]
+            },
+        }
+        .build();
 
-                // Filter out negatives
-                .filter(|&(_, num)| num >= Frac::zero())
+        let tableau = Tableau::create_initial(&system).unwrap();
+        let variables = tableau
+            .column_labels
+            .iter()
+            .filter_map(|label| match label {
+                ColumnLabel::Variable(var) => Some(*var),
+                ColumnLabel::BasicString(_) => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            variables,
+            [
+                VariableType::Original("s"),
+                VariableType::Original("t"),
+                VariableType::Original("x"),
+                VariableType::Original("y"),
+                VariableType::Artificial(0),
+                VariableType::Artificial(1),
+            ]
+        );
 
-                // Find all the permutations and get rid of any with duplicated variables like
-                // [("x", 3), ("x", 4)]
-                .permutations(var_count)
-                .map(|possibility| {
-                    possibility
-                        .into_iter()
-                        .unique_by(|&(var, _)| var)
-                        .collect_vec()
-                })
-                .filter(|possibility| possibility.len() == var_count)
+        let solution = crate::simplex::solve_with_simplex_tableaux(&system).unwrap();
+        assert_eq!(solution.objective_function_value, 12.into());
+        assert_eq!(
+            solution.variable_values[&VariableType::Original("x")],
+            4.into()
+        );
+        assert_eq!(
+            solution.variable_values[&VariableType::Original("t")],
+            2.into()
+        );
+        assert!(solution.shadow_prices.is_empty());
+    }
 
-                // Sort the variables in each possibility and eliminate duplicates
-                .map(|possibility| {
-                    possibility.into_iter()
-                        .sorted_by_key(|(var, _)| var.to_string()).collect_vec()
-                })
-                .unique_by(|possibility| format!("{possibility:?}"))
-                .collect_vec();
-            debug!(?points_around_optimal);
-
-            let in_feasible_region = self.system.with_constraints(|cons| {
-                points_around_optimal
-                    .into_iter()
-                    // Filter to get just the possibilities that satisfy every constraint
-                    .filter(|possibility| {
-                        cons.iter()
-                            .all(|con| {
-                                con.test(
-                                    &possibility
-                                    .iter()
-                                    .map(|&tuple| tuple)
-                                    .collect_vec()
-                                )
-                            })
-                    })
-                    .collect_vec()
+    #[test]
+    #[traced_test]
+    fn prefer_original_pivot_rule_test() {
+        for (pivot_rule, expected_label) in [
+            (PivotRule::MostNegative, VariableType::Slack(0)),
+            (PivotRule::PreferOriginal, VariableType::Original("x")),
+        ] {
+            let system = small_system(Config {
+                pivot_rule,
+                ..Config::default()
             });
-            debug!(?in_feasible_region);
-
-            let (vars, objective_function_value) =
-                self.system.with_objective_function(|obj_func| {
-                    in_feasible_region
-                    .into_iter()
-                    .map(|possibility| {
-                        let value = obj_func.expression().evaluate(&possibility);
-                        (possibility, value)
-                    })
-                    //.max_by_key(|&(vars, value)| value)
-                    .fold((vec![], Frac::zero()), |(acc_vars, acc_value), (cur_vars, cur_value)| {
-                        if cur_value > acc_value {
-                            (cur_vars, cur_value)
-                        } else {
-                            (acc_vars, acc_value)
-                        }
-                    })
-                });
-            let variable_values = vars
-                .into_iter()
-                .map(|(var, num)| (VariableType::Original(var), num))
-                .collect();
+            let mut tableau = Tableau::create_initial(&system).unwrap();
 
-            debug!(?objective_function_value, ?variable_values);
+            // Put sl#0 in front of x and make them tie in the bottom row
+            let sl0_idx = tableau
+                .column_labels
+                .iter()
+                .position(|label| *label == ColumnLabel::Variable(VariableType::Slack(0)))
+                .unwrap();
+            tableau.column_labels.swap(0, sl0_idx);
+            for (_, nums) in &mut tableau.rows {
+                nums.swap(0, sl0_idx);
+            }
+            let bottom_row = &mut tableau.rows.last_mut().unwrap().1;
+            bottom_row[0] = TableauNumber::Simple(-Frac::new(1u32, 1u32));
+            bottom_row[sl0_idx] = TableauNumber::Simple(-Frac::new(1u32, 1u32));
+
+            assert_eq!(
+                tableau.column_labels[tableau.find_pivot_column()],
+                ColumnLabel::Variable(expected_label),
+                "{pivot_rule:?}"
+            );
+        }
+    }
 
-            SolutionSet {
-                objective_function_value,
-                variable_values,
+    #[test]
+    #[traced_test]
+    fn column_tie_break_test() {
+        let build_system = |pivot_rule, column_tie_break| {
+            LinProgSystemBuilder {
+                variables: Variables::from(["x", "y", "z"]),
+                config: Config {
+                    pivot_rule,
+                    column_tie_break,
+                    ..Config::default()
+                },
+                objective_function_builder: |vars| {
+                    ObjectiveFunction::Maximise(
+                        Expression::nom_parse("2x + 3y + 3z", vars).unwrap().1,
+                        Frac::zero(),
+                    )
+                },
+                constraints_builder: |vars| {
+                    vec![Constraint::nom_parse("x + y + z <= 4", vars).unwrap().1]
+                },
             }
+            .build()
+        };
+
+        // y and z tie with -3 in the bottom row, and x isn't tied, so it's never chosen
+        for (pivot_rule, column_tie_break, expected_var) in [
+            (PivotRule::MostNegative, ColumnTieBreak::LowestIndex, "y"),
+            (PivotRule::MostNegative, ColumnTieBreak::HighestIndex, "z"),
+            (PivotRule::Lexicographic, ColumnTieBreak::LowestIndex, "y"),
+            (PivotRule::Lexicographic, ColumnTieBreak::HighestIndex, "z"),
+        ] {
+            let system = build_system(pivot_rule, column_tie_break);
+            let tableau = Tableau::create_initial(&system).unwrap();
+
+            assert_eq!(
+                tableau.column_labels[tableau.find_pivot_column()],
+                ColumnLabel::Variable(VariableType::Original(expected_var)),
+                "{pivot_rule:?}, {column_tie_break:?}"
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn get_solution_test() {
+        let system = small_system(Config::default());
+        let tableau = Tableau::create_initial(&system).unwrap();
+
+        assert_eq!(
+            tableau.clone().get_solution(),
+            Err(SolutionError::NotOptimal)
+        );
+
+        // An objective row of all zeros is optimal, even though nothing has been done
+        let mut all_zero = tableau.clone();
+        for num in &mut all_zero.rows.last_mut().unwrap().1[..=all_zero.value_idx] {
+            *num = TableauNumber::Simple(Frac::zero());
         }
+        let solution = all_zero.get_solution().unwrap();
+        assert_eq!(solution.objective_function_value, Frac::zero());
+        assert_eq!(
+            solution.variable_values[&VariableType::Slack(0)],
+            Frac::new(4u32, 1u32)
+        );
+
+        let mut missing_row = tableau;
+        missing_row
+            .rows
+            .retain(|(label, _)| *label != RowLabel::ObjectiveFunction);
+        assert_eq!(
+            missing_row.get_solution(),
+            Err(SolutionError::MissingObjectiveRow)
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn zero_pivot_test() {
+        let system = small_system(Config::default());
+        let mut tableau = Tableau::create_initial(&system).unwrap();
+
+        // The row of sl#1 comes from x <= 3, so it has a 0 in the column of y
+        let y_col = tableau
+            .column_labels
+            .iter()
+            .position(|label| *label == ColumnLabel::Variable(VariableType::Original("y")))
+            .unwrap();
+        let sl1_row = tableau
+            .rows
+            .iter()
+            .position(|(label, _)| *label == RowLabel::Variable(VariableType::Slack(1)))
+            .unwrap();
+        assert_eq!(
+            tableau.rows[sl1_row].1[y_col],
+            TableauNumber::Simple(Frac::zero())
+        );
+
+        // That row doesn't get a theta value, rather than an infinite one
+        tableau.populate_theta_values(y_col);
+        assert_eq!(tableau.theta_column()[sl1_row], None);
+
+        // Pivoting on the 0 fails without changing the tableau
+        let rows_before = tableau.rows.clone();
+        assert_eq!(tableau.pivot(sl1_row, y_col, &mut |_| ()), Err(ZeroPivot));
+        assert_eq!(tableau.rows, rows_before);
+        assert_eq!(
+            IterationError::from(ZeroPivot).to_string(),
+            "Zero pivot element encountered"
+        );
     }
 }
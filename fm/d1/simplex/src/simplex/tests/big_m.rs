@@ -0,0 +1,136 @@
+use crate::{
+    lin_prog::{
+        config::{Config, SimplexMethod},
+        system::LinProgSystem,
+    },
+    simplex::{solve_with_simplex_tableaux, tableau::Tableau},
+};
+use tracing_test::traced_test;
+
+/// Build the system from the string, with the Big-M method if `big_m` is true.
+fn system_with_method(input: &str, big_m: bool) -> LinProgSystem {
+    let system = LinProgSystem::build_from_str(input).unwrap();
+    let method = if big_m {
+        SimplexMethod::BigM
+    } else {
+        SimplexMethod::TwoStage
+    };
+
+    system.rebuild(
+        Config {
+            method,
+            ..system.borrow_config().clone()
+        },
+        |_, obj_func| obj_func,
+        |_, cons| cons,
+    )
+}
+
+#[test]
+#[traced_test]
+fn big_m_matches_two_stage_test() {
+    for input in [
+        // Ch 7 Example 15
+        "x y z\nmaximise 3x - 2y + z\nx + y + 2z <= 10; 2x - 3y + z >= 5; x + y >= 8",
+        "x y\nminimise x + 2y\nx + y <= 4; -x <= -1",
+        "x y\nmaximise 5x + 8y\nx + y <= 6; 5x + 9y <= 45; x <= 2; y >= 4; x <= 1; y >= 5",
+        "x y\nminimise 2x + 3y\nx + y >= 4; x + 3y >= 6; x <= 5",
+    ] {
+        let two_stage_system = system_with_method(input, false);
+        let big_m_system = system_with_method(input, true);
+        let two_stage = solve_with_simplex_tableaux(&two_stage_system).unwrap();
+        let big_m = solve_with_simplex_tableaux(&big_m_system).unwrap();
+
+        assert_eq!(
+            big_m.objective_function_value, two_stage.objective_function_value,
+            "{input:?}"
+        );
+        assert_eq!(
+            big_m.variable_values, two_stage.variable_values,
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+#[traced_test]
+fn big_m_infeasible_test() {
+    // Ch 7 Example 16
+    let system = system_with_method(
+        "x y z\nmaximise 3x - 2y + z\nx + y + 2z <= 8; 2x - 3y + z >= 5; x + y >= 10",
+        true,
+    );
+
+    assert!(solve_with_simplex_tableaux(&system).is_err_and(|err| err
+        .to_string()
+        .contains("No feasible solution for the given system")));
+}
+
+#[test]
+#[traced_test]
+fn big_m_tableau_display_test() {
+    use pretty_assertions::assert_eq;
+
+    // Ch 7 Example 15
+    let system = system_with_method(
+        "x y z\nmaximise 3x - 2y + z\nx + y + 2z <= 10; 2x - 3y + z >= 5; x + y >= 8",
+        true,
+    );
+    let mut tableau = Tableau::create_initial(&system).unwrap();
+
+    assert_eq!(
+        tableau.to_string(),
+        r#"
+┌───────────┬─────────┬────────┬────────┬──────┬──────┬──────┬──────┬──────┬───────┬───┬────────┐
+│ Basic var │ x       │ y      │ z      │ sl#0 │ su#0 │ su#1 │ ar#0 │ ar#1 │ Value │ θ │ Row op │
+├───────────┼─────────┼────────┼────────┼──────┼──────┼──────┼──────┼──────┼───────┼───┼────────┤
+│ sl#0      │ 1       │ 1      │ 2      │ 1    │ 0    │ 0    │ 0    │ 0    │ 10    │   │        │
+├───────────┼─────────┼────────┼────────┼──────┼──────┼──────┼──────┼──────┼───────┼───┼────────┤
+│ ar#0      │ 2       │ -3     │ 1      │ 0    │ -1   │ 0    │ 1    │ 0    │ 5     │   │        │
+├───────────┼─────────┼────────┼────────┼──────┼──────┼──────┼──────┼──────┼───────┼───┼────────┤
+│ ar#1      │ 1       │ 1      │ 0      │ 0    │ 0    │ -1   │ 0    │ 1    │ 8     │   │        │
+├───────────┼─────────┼────────┼────────┼──────┼──────┼──────┼──────┼──────┼───────┼───┼────────┤
+│ ObjFunc#  │ -3M - 3 │ 2M + 2 │ -M - 1 │ 0    │ M    │ M    │ 0    │ 0    │ -13M  │   │        │
+└───────────┴─────────┴────────┴────────┴──────┴──────┴──────┴──────┴──────┴───────┴───┴────────┘"#,
+        "Ch 7 Example 15 initial"
+    );
+
+    let mut working = String::new();
+    tableau
+        .do_iteration_showing_working(|tableau| working = tableau.to_string())
+        .unwrap();
+
+    assert_eq!(
+        working,
+        r#"
+┌───────────┬─────────┬────────┬────────┬──────┬──────┬──────┬──────┬──────┬───────┬─────┬──────────────┐
+│ Basic var │ x       │ y      │ z      │ sl#0 │ su#0 │ su#1 │ ar#0 │ ar#1 │ Value │ θ   │ Row op       │
+├───────────┼─────────┼────────┼────────┼──────┼──────┼──────┼──────┼──────┼───────┼─────┼──────────────┤
+│ sl#0      │ 1       │ 1      │ 2      │ 1    │ 0    │ 0    │ 0    │ 0    │ 10    │ 10  │ -1 R2        │
+├───────────┼─────────┼────────┼────────┼──────┼──────┼──────┼──────┼──────┼───────┼─────┼──────────────┤
+│ x         │ 2       │ -3     │ 1      │ 0    │ -1   │ 0    │ 1    │ 0    │ 5     │ 5/2 │ ×1/2         │
+├───────────┼─────────┼────────┼────────┼──────┼──────┼──────┼──────┼──────┼───────┼─────┼──────────────┤
+│ ar#1      │ 1       │ 1      │ 0      │ 0    │ 0    │ -1   │ 0    │ 1    │ 8     │ 8   │ -1 R2        │
+├───────────┼─────────┼────────┼────────┼──────┼──────┼──────┼──────┼──────┼───────┼─────┼──────────────┤
+│ ObjFunc#  │ -3M - 3 │ 2M + 2 │ -M - 1 │ 0    │ M    │ M    │ 0    │ 0    │ -13M  │     │ +(3M + 3) R2 │
+└───────────┴─────────┴────────┴────────┴──────┴──────┴──────┴──────┴──────┴───────┴─────┴──────────────┘"#,
+        "Ch 7 Example 15 first iteration working"
+    );
+
+    assert_eq!(
+        tableau.to_string(),
+        r#"
+┌───────────┬───┬───────────────┬──────────────┬──────┬───────────────┬──────┬──────────────┬──────┬─────────────────┬───┬────────┐
+│ Basic var │ x │ y             │ z            │ sl#0 │ su#0          │ su#1 │ ar#0         │ ar#1 │ Value           │ θ │ Row op │
+├───────────┼───┼───────────────┼──────────────┼──────┼───────────────┼──────┼──────────────┼──────┼─────────────────┼───┼────────┤
+│ sl#0      │ 0 │ 5/2           │ 3/2          │ 1    │ 1/2           │ 0    │ -1/2         │ 0    │ 15/2            │   │        │
+├───────────┼───┼───────────────┼──────────────┼──────┼───────────────┼──────┼──────────────┼──────┼─────────────────┼───┼────────┤
+│ x         │ 1 │ -3/2          │ 1/2          │ 0    │ -1/2          │ 0    │ 1/2          │ 0    │ 5/2             │   │        │
+├───────────┼───┼───────────────┼──────────────┼──────┼───────────────┼──────┼──────────────┼──────┼─────────────────┼───┼────────┤
+│ ar#1      │ 0 │ 5/2           │ -1/2         │ 0    │ 1/2           │ -1   │ -1/2         │ 1    │ 11/2            │   │        │
+├───────────┼───┼───────────────┼──────────────┼──────┼───────────────┼──────┼──────────────┼──────┼─────────────────┼───┼────────┤
+│ ObjFunc#  │ 0 │ (-5/2)M - 5/2 │ (1/2)M + 1/2 │ 0    │ (-1/2)M - 3/2 │ M    │ (3/2)M + 3/2 │ 0    │ (-11/2)M + 15/2 │   │        │
+└───────────┴───┴───────────────┴──────────────┴──────┴───────────────┴──────┴──────────────┴──────┴─────────────────┴───┴────────┘"#,
+        "Ch 7 Example 15 after 1 complete iteration"
+    );
+}
@@ -1,2 +1,209 @@
+mod big_m;
 mod one_stage;
 mod two_stage;
+
+use super::{frac_to_f64, snap_near_integer, tableau::Tableau, VariableType};
+use crate::{
+    lin_prog::{
+        config::{Config, VariableNaming},
+        constraint::Constraint,
+        expression::Expression,
+        system::{LinProgSystem, LinProgSystemBuilder},
+        ObjectiveFunction, Variables,
+    },
+    Frac,
+};
+use fraction::Zero;
+use itertools::Itertools;
+
+/// Ch 7 Example 8 from the textbook, which is to maximise 3x + 2y subject to 5x + 7y ≤ 70 and
+/// 10x + 3y ≤ 60, with the default config. The optimum is 286/11 at (42/11, 80/11).
+pub(super) fn ch7_example8() -> LinProgSystem {
+    ch7_example8_with_config(Config::default())
+}
+
+/// [`ch7_example8`] with the given config.
+pub(super) fn ch7_example8_with_config(config: Config) -> LinProgSystem {
+    LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config,
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("3x + 2y", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("5x + 7y <= 70", vars).unwrap().1,
+                Constraint::nom_parse("10x + 3y <= 60", vars).unwrap().1,
+            ]
+        },
+    }
+    .build()
+}
+
+/// Assert that the cell in the given row and column of the tableau is exactly the expected
+/// fraction. The labels are the same as they appear in the rendered tableau.
+#[track_caller]
+fn assert_tableau_cell(tableau: &Tableau, row_label: &str, col_label: &str, expected: Frac) {
+    match tableau.cell(row_label, col_label) {
+        Some(actual) => assert_eq!(
+            actual, expected,
+            "Tableau cell at row {row_label:?} and column {col_label:?} is {actual}, not {expected}"
+        ),
+        None => panic!("No tableau cell at row {row_label:?} and column {col_label:?}"),
+    }
+}
+
+#[test]
+fn snap_near_integer_test() {
+    let tolerance = Frac::new(1u32, 1_000_000u32);
+
+    assert_eq!(
+        snap_near_integer(Frac::new(29_999_999u32, 10_000_000u32), tolerance),
+        Frac::from(3)
+    );
+    assert_eq!(
+        snap_near_integer(-Frac::new(10_000_001u32, 10_000_000u32), tolerance),
+        Frac::from(-1)
+    );
+    assert_eq!(
+        snap_near_integer(Frac::new(28u32, 10u32), tolerance),
+        Frac::new(28u32, 10u32)
+    );
+    assert_eq!(snap_near_integer(Frac::from(5), tolerance), Frac::from(5));
+}
+
+#[test]
+fn frac_to_f64_test() {
+    assert_eq!(frac_to_f64(&Frac::new(1u32, 4u32)), 0.25);
+    assert_eq!(frac_to_f64(&-Frac::new(3u32, 8u32)), -0.375);
+    assert_eq!(frac_to_f64(&Frac::from(42)), 42.0);
+    assert_eq!(frac_to_f64(&Frac::zero()), 0.0);
+    assert_eq!(frac_to_f64(&Frac::infinity()), f64::INFINITY);
+    assert_eq!(frac_to_f64(&Frac::neg_infinity()), f64::NEG_INFINITY);
+    assert!(frac_to_f64(&Frac::nan()).is_nan());
+}
+
+#[test]
+fn variable_type_display_with_test() {
+    let variables = [
+        VariableType::Original("x"),
+        VariableType::Slack(0),
+        VariableType::Surplus(1),
+        VariableType::Artificial(11),
+    ];
+
+    assert_eq!(
+        variables.map(|var| var.display_with(VariableNaming::Symbolic)),
+        ["x", "sl#0", "su#1", "ar#11"]
+    );
+    assert_eq!(
+        variables.map(|var| var.display_with(VariableNaming::Numbered)),
+        ["x", "s1", "e2", "a12"]
+    );
+    assert_eq!(
+        variables.map(|var| var.display_with(VariableNaming::Subscripted)),
+        ["x", "s₁", "e₂", "a₁₂"]
+    );
+    assert_eq!(
+        variables.map(|var| var.to_string()),
+        ["x", "sl#0", "su#1", "ar#11"]
+    );
+}
+
+#[test]
+fn variable_type_helpers_test() {
+    let variables = [
+        VariableType::Original("x"),
+        VariableType::Slack(1),
+        VariableType::Surplus(2),
+        VariableType::Artificial(3),
+    ];
+
+    assert_eq!(
+        variables.map(|var| var.index()),
+        [None, Some(1), Some(2), Some(3)]
+    );
+    assert_eq!(
+        variables.map(|var| var.is_original()),
+        [true, false, false, false]
+    );
+    assert_eq!(
+        variables.map(|var| var.is_slack()),
+        [false, true, false, false]
+    );
+    assert_eq!(
+        variables.map(|var| var.is_surplus()),
+        [false, false, true, false]
+    );
+    assert_eq!(
+        variables.map(|var| var.is_artificial()),
+        [false, false, false, true]
+    );
+}
+
+#[test]
+fn variable_type_ordering_test() {
+    let sorted = [
+        VariableType::Original("x"),
+        VariableType::Original("y"),
+        VariableType::Slack(0),
+        VariableType::Slack(1),
+        VariableType::Surplus(0),
+        VariableType::Surplus(1),
+        VariableType::Artificial(0),
+        VariableType::Artificial(1),
+    ];
+
+    // Every pair must compare consistently with their positions in the sorted order
+    for ((i, a), (j, b)) in sorted
+        .iter()
+        .enumerate()
+        .cartesian_product(sorted.iter().enumerate())
+    {
+        assert_eq!(a.cmp(b), i.cmp(&j), "Comparing {a} and {b}");
+    }
+
+    // Sorting any permutation must give the sorted order
+    for permutation in sorted.iter().copied().permutations(sorted.len()) {
+        assert_eq!(
+            permutation.iter().copied().sorted().collect::<Vec<_>>(),
+            sorted,
+            "Sorting {permutation:?}"
+        );
+    }
+}
+
+mod assert_tableau_cell_tests {
+    use super::*;
+
+    #[test]
+    fn assert_tableau_cell_test() {
+        let system = ch7_example8();
+        let tableau = Tableau::create_initial(&system).unwrap();
+
+        assert_tableau_cell(&tableau, "sl#0", "x", 5.into());
+        assert_tableau_cell(&tableau, "sl#1", "Value", 60.into());
+        assert_tableau_cell(&tableau, "ObjFunc#", "y", -Frac::new(2u32, 1u32));
+    }
+
+    #[test]
+    #[should_panic(expected = "is 10, not 5")]
+    fn assert_tableau_cell_wrong_value_test() {
+        let system = ch7_example8();
+        let tableau = Tableau::create_initial(&system).unwrap();
+
+        assert_tableau_cell(&tableau, "sl#1", "x", 5.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "No tableau cell")]
+    fn assert_tableau_cell_missing_label_test() {
+        let system = ch7_example8();
+        let tableau = Tableau::create_initial(&system).unwrap();
+
+        assert_tableau_cell(&tableau, "sl#2", "x", 5.into());
+    }
+}
@@ -1,17 +1,28 @@
+use super::{assert_tableau_cell, ch7_example8, ch7_example8_with_config};
 use crate::{
     lin_prog::{
-        config::Config, constraint::Constraint, expression::Expression,
-        system::LinProgSystemBuilder, ObjectiveFunction, Variables,
+        comparison::Comparison,
+        config::{ColumnTieBreak, Config, IntegerMode, PivotRule, VariableNaming},
+        constraint::Constraint,
+        expression::Expression,
+        system::{LinProgSystem, LinProgSystemBuilder},
+        ObjectiveFunction, ObjectiveSense, Variables,
     },
     simplex::{
-        solve_with_simplex_tableaux,
-        tableau::{NoFeasibleSolution, Tableau},
-        SolutionSet, VariableType,
+        driver::{SimplexDriver, StepOutcome},
+        solve_from_basis, solve_from_basis_and_progress, solve_matrix, solve_to_final_tableau,
+        solve_warm_start_and_progress, solve_with_both, solve_with_simplex_tableaux,
+        solve_with_simplex_tableaux_and_progress, solve_with_simplex_tableaux_explained,
+        solve_with_simplex_tableaux_verbose,
+        tableau::{IterationError, NoFeasibleSolution, Tableau},
+        trace::{solve_with_trace, PivotInfo},
+        MatrixSolution, SolutionSet, VariableType,
     },
     Frac,
 };
 use fraction::Zero;
-use std::collections::HashMap;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
 use tracing_test::traced_test;
 
 #[test]
@@ -25,6 +36,7 @@ fn solve_with_simplex_tableaux_test() {
                 objective_function_builder: |vars| {
                     ObjectiveFunction::Maximise(
                         Expression::nom_parse("10x + 12y + 8z", vars).unwrap().1,
+                        Frac::zero(),
                     )
                 },
                 constraints_builder: |vars| vec![
@@ -43,7 +55,15 @@ fn solve_with_simplex_tableaux_test() {
                 (VariableType::Original("z"), Frac::new(15u32, 8u32)),
                 (VariableType::Slack(0), Frac::zero()),
                 (VariableType::Slack(1), Frac::zero()),
-            ])
+            ]),
+            basic_variables: HashSet::from([
+                VariableType::Original("y"),
+                VariableType::Original("z")
+            ]),
+            shadow_prices: HashMap::from([(0, 3.into()), (1, 2.into())]),
+            has_alternative_optima: false,
+            objective_name: None,
+            variable_naming: VariableNaming::Symbolic,
         },
         "Ch 7 Example 7 or 10"
     );
@@ -54,7 +74,10 @@ fn solve_with_simplex_tableaux_test() {
                 variables: Variables::from(["x", "y"]),
                 config: Config::default(),
                 objective_function_builder: |vars| {
-                    ObjectiveFunction::Maximise(Expression::nom_parse("3x + 2y", vars).unwrap().1)
+                    ObjectiveFunction::Maximise(
+                        Expression::nom_parse("3x + 2y", vars).unwrap().1,
+                        Frac::zero(),
+                    )
                 },
                 constraints_builder: |vars| vec![
                     Constraint::nom_parse("5x + 7y ≤ 70", vars).unwrap().1,
@@ -71,7 +94,15 @@ fn solve_with_simplex_tableaux_test() {
                 (VariableType::Original("y"), Frac::new(80u32, 11u32)),
                 (VariableType::Slack(0), Frac::zero()),
                 (VariableType::Slack(1), Frac::zero()),
-            ])
+            ]),
+            basic_variables: HashSet::from([
+                VariableType::Original("x"),
+                VariableType::Original("y")
+            ]),
+            shadow_prices: HashMap::from([(0, Frac::new(1u32, 5u32)), (1, Frac::new(1u32, 5u32))]),
+            has_alternative_optima: false,
+            objective_name: None,
+            variable_naming: VariableNaming::Symbolic,
         },
         "Ch 7 Example 8"
     );
@@ -82,7 +113,10 @@ fn solve_with_simplex_tableaux_test() {
                 variables: Variables::from(["x", "y"]),
                 config: Config::default(),
                 objective_function_builder: |vars| {
-                    ObjectiveFunction::Minimise(Expression::nom_parse("3x - y", vars).unwrap().1)
+                    ObjectiveFunction::Minimise(
+                        Expression::nom_parse("3x - y", vars).unwrap().1,
+                        Frac::zero(),
+                    )
                 },
                 constraints_builder: |vars| vec![
                     Constraint::nom_parse("2x + y ≤ 12", vars).unwrap().1,
@@ -99,7 +133,12 @@ fn solve_with_simplex_tableaux_test() {
                 (VariableType::Original("y"), 2.into()),
                 (VariableType::Slack(0), 10.into()),
                 (VariableType::Slack(1), Frac::zero()),
-            ])
+            ]),
+            basic_variables: HashSet::from([VariableType::Original("y"), VariableType::Slack(0)]),
+            shadow_prices: HashMap::from([(0, Frac::zero()), (1, -Frac::new(1u32, 4u32))]),
+            has_alternative_optima: false,
+            objective_name: None,
+            variable_naming: VariableNaming::Symbolic,
         },
         "Ch 7 Example 9 (minimise)"
     );
@@ -112,6 +151,7 @@ fn solve_with_simplex_tableaux_test() {
                 objective_function_builder: |vars| {
                     ObjectiveFunction::Maximise(
                         Expression::nom_parse("3x + 4y - 5z", vars).unwrap().1,
+                        Frac::zero(),
                     )
                 },
                 constraints_builder: |vars| vec![
@@ -132,7 +172,20 @@ fn solve_with_simplex_tableaux_test() {
                 (VariableType::Slack(0), Frac::zero()),
                 (VariableType::Slack(1), Frac::zero()),
                 (VariableType::Slack(2), Frac::new(30u32, 7u32)),
-            ])
+            ]),
+            basic_variables: HashSet::from([
+                VariableType::Original("x"),
+                VariableType::Original("y"),
+                VariableType::Slack(2)
+            ]),
+            shadow_prices: HashMap::from([
+                (0, Frac::new(2u32, 7u32)),
+                (1, Frac::new(17u32, 7u32)),
+                (2, Frac::zero()),
+            ]),
+            has_alternative_optima: false,
+            objective_name: None,
+            variable_naming: VariableNaming::Symbolic,
         },
         "Ch 7 Example 11"
     );
@@ -140,33 +193,318 @@ fn solve_with_simplex_tableaux_test() {
 
 #[test]
 #[traced_test]
-fn solve_with_simplex_tableaux_integer_solutions_test() {
+fn solve_with_simplex_tableaux_fraction_constant_test() {
     assert_eq!(
         solve_with_simplex_tableaux(
             &LinProgSystemBuilder {
                 variables: Variables::from(["x", "y"]),
-                config: Config {
-                    integer_solutions: true,
-                },
+                config: Config::default(),
                 objective_function_builder: |vars| {
-                    ObjectiveFunction::Maximise(Expression::nom_parse("3x + 2y", vars).unwrap().1)
-                },
-                constraints_builder: |vars| {
-                    vec![
-                        Constraint::nom_parse("5x + 7y <= 70", vars).unwrap().1,
-                        Constraint::nom_parse("10x + 3y <= 60", vars).unwrap().1,
-                    ]
+                    ObjectiveFunction::Maximise(
+                        Expression::nom_parse("x + y", vars).unwrap().1,
+                        Frac::zero(),
+                    )
                 },
+                constraints_builder: |vars| vec![
+                    Constraint::nom_parse("3x <= 1/3", vars).unwrap().1,
+                    Constraint::nom_parse("x + 2y <= 2/3", vars).unwrap().1,
+                ]
             }
             .build()
         )
         .unwrap(),
         SolutionSet {
-            objective_function_value: 23.into(),
+            objective_function_value: Frac::new(7u32, 18u32),
+            variable_values: HashMap::from([
+                (VariableType::Original("x"), Frac::new(1u32, 9u32)),
+                (VariableType::Original("y"), Frac::new(5u32, 18u32)),
+                (VariableType::Slack(0), Frac::zero()),
+                (VariableType::Slack(1), Frac::zero()),
+            ]),
+            basic_variables: HashSet::from([
+                VariableType::Original("x"),
+                VariableType::Original("y")
+            ]),
+            shadow_prices: HashMap::from([(0, Frac::new(1u32, 6u32)), (1, Frac::new(1u32, 2u32))]),
+            has_alternative_optima: false,
+            objective_name: None,
+            variable_naming: VariableNaming::Symbolic,
+        }
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_non_positive_test() {
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config: Config {
+            non_positive_variables: HashSet::from(["x".to_string()]),
+            ..Config::default()
+        },
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("y - 2x", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("y - x <= 6", vars).unwrap().1,
+                Constraint::nom_parse("x >= -4", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+
+    assert_eq!(solution.objective_function_value, 10.into());
+    assert_eq!(
+        solution.variable_values,
+        HashMap::from([
+            (VariableType::Original("x"), (-4).into()),
+            (VariableType::Original("y"), 2.into()),
+            (VariableType::Slack(0), Frac::zero()),
+            (VariableType::Slack(1), Frac::zero()),
+        ])
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_matrix_test() {
+    let frac = |n: u64| Frac::new(n, 1u64);
+
+    // Ch 7 Example 8
+    assert_eq!(
+        solve_matrix(
+            &[frac(3), frac(2)],
+            &[vec![frac(5), frac(7)], vec![frac(10), frac(3)]],
+            &[frac(70), frac(60)],
+            &[Comparison::LessThanOrEqual; 2],
+            true,
+        )
+        .unwrap(),
+        MatrixSolution {
+            objective_function_value: 26.into(),
+            variable_values: vec![Frac::new(42u64, 11u64), Frac::new(80u64, 11u64)],
+        }
+    );
+
+    assert_eq!(
+        solve_matrix(
+            &[frac(1), frac(1)],
+            &[vec![frac(1), frac(2)], vec![frac(3), frac(1)]],
+            &[frac(4), frac(6)],
+            &[Comparison::GreaterThanOrEqual; 2],
+            false,
+        )
+        .unwrap(),
+        MatrixSolution {
+            objective_function_value: Frac::new(14u64, 5u64),
+            variable_values: vec![Frac::new(8u64, 5u64), Frac::new(6u64, 5u64)],
+        }
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_matrix_dimension_error_test() {
+    let one = Frac::new(1u64, 1u64);
+    let solve = |c: &[Frac], a: &[Vec<Frac>], b: &[Frac], comparisons: &[Comparison]| {
+        solve_matrix(c, a, b, comparisons, true)
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(
+        solve(
+            &[one, one],
+            &[vec![one, one], vec![one, one, one]],
+            &[one, one],
+            &[Comparison::LessThanOrEqual; 2]
+        ),
+        "Row 2 of the matrix has 3 entries, but there are 2 variables"
+    );
+    assert_eq!(
+        solve(
+            &[one, one],
+            &[vec![one, one]],
+            &[one, one],
+            &[Comparison::LessThanOrEqual]
+        ),
+        "Expected one constant and one comparison for each of the 1 rows of the matrix, but got \
+        2 constants and 1 comparisons"
+    );
+    assert_eq!(
+        solve(&[one], &[vec![one]], &[one], &[]),
+        "Expected one constant and one comparison for each of the 1 rows of the matrix, but got \
+        1 constants and 0 comparisons"
+    );
+    assert_eq!(
+        solve(&[], &[], &[], &[]),
+        "The objective function must have at least one variable"
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_both_test() {
+    let system = ch7_example8_with_config(Config {
+        integer_variables: IntegerMode::All,
+        ..Config::default()
+    });
+    let (relaxation, solution) = solve_with_both(&system).unwrap();
+
+    assert_eq!(relaxation.objective_function_value, 26.into());
+    assert_eq!(
+        relaxation.variable_values[&VariableType::Original("x")],
+        Frac::new(42u32, 11u32)
+    );
+    assert_eq!(solution, solve_with_simplex_tableaux(&system).unwrap());
+    assert!(
+        relaxation.objective_function_value > solution.objective_function_value,
+        "Ch 7 Example 12 has a gap between the relaxation and the integer optimum"
+    );
+}
+
+#[test]
+#[traced_test]
+fn constraint_only_variables_test() {
+    let system = LinProgSystem::build_from_str(
+        "w x y z\nmaximise 3x + 2y\nx + y + z <= 4; x + 3y <= 6; z >= 1; x + w <= 10",
+    )
+    .unwrap();
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+
+    // Variables which are only in the constraints are still in the solution, even at 0
+    assert_eq!(solution.objective_function_value, 9.into());
+    assert_eq!(
+        ["w", "x", "y", "z"].map(|var| solution.variable_values[&VariableType::Original(var)]),
+        [0.into(), 3.into(), 0.into(), 1.into()]
+    );
+}
+
+#[test]
+#[traced_test]
+fn clear_denominators_test() {
+    for input in [
+        "x y\nmaximise 1/2x + 2/3y\n1/3x + 1/4y <= 2; 1/2x + 3/2y <= 4; x + y >= 1/2",
+        "x y\nminimise 3/4x + 5/6y + 1\n2/3x + y >= 3; x + 1/5y >= 2/7",
+    ] {
+        let system = LinProgSystem::build_from_str(input).unwrap();
+        let cleared = LinProgSystem::build_from_str_with_config(
+            input,
+            Config {
+                clear_denominators: true,
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let tableau = Tableau::create_initial(&cleared).unwrap();
+        let json = tableau.to_json();
+        assert!(
+            json["rows"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .flat_map(|row| row["values"].as_array().unwrap())
+                .filter(|value| value.is_object())
+                .all(|value| value["denominator"] == 1),
+            "{input:?} should have an initial tableau with integer coefficients:{tableau}"
+        );
+
+        assert_eq!(
+            solve_with_simplex_tableaux(&cleared).unwrap(),
+            solve_with_simplex_tableaux(&system).unwrap(),
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+#[traced_test]
+fn solution_serde_round_trip_test() {
+    let system = LinProgSystem::build_from_str(
+        "x y\nmaximise P = 3x + 2y\n5x + 7y <= 70; 10x + 3y <= 60; x + y >= 1",
+    )
+    .unwrap();
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+
+    let json = serde_json::to_string(&solution).unwrap();
+    assert_eq!(serde_json::to_value(&solution).unwrap(), solution.to_json());
+    assert_eq!(
+        solution.to_json()["variable_values"]["x"],
+        json!({"numerator": 42, "denominator": 11, "decimal": 42.0 / 11.0})
+    );
+    assert_eq!(solution.to_json()["objective_name"], json!("P"));
+
+    let loaded: SolutionSet = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, solution);
+    assert_eq!(
+        loaded.basic_variables,
+        HashSet::from([
+            VariableType::Original("x"),
+            VariableType::Original("y"),
+            VariableType::Surplus(0)
+        ])
+    );
+
+    // The names don't need to be borrowed from the input
+    let escaped = json
+        .replace(r#""x""#, r#""\u0078""#)
+        .replace(r#""P""#, r#""\u0050""#);
+    assert_ne!(escaped, json);
+    assert_eq!(
+        serde_json::from_str::<SolutionSet>(&escaped).unwrap(),
+        solution
+    );
+    assert_eq!(
+        serde_json::from_value::<SolutionSet>(solution.to_json()).unwrap(),
+        solution
+    );
+    assert_eq!(
+        serde_json::from_reader::<_, SolutionSet>(json.as_bytes()).unwrap(),
+        solution
+    );
+
+    let mut zero_denominator = solution.to_json();
+    zero_denominator["variable_values"]["x"]["denominator"] = json!(0);
+    assert!(serde_json::from_value::<SolutionSet>(zero_denominator).is_err());
+
+    let mut negative = solution.to_json();
+    negative["objective_function_value"] = json!({"numerator": -3, "denominator": 2});
+    assert_eq!(
+        serde_json::from_value::<SolutionSet>(negative)
+            .unwrap()
+            .objective_function_value,
+        Frac::new_neg(3u64, 2u64)
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_integer_solutions_test() {
+    assert_eq!(
+        solve_with_simplex_tableaux(&ch7_example8_with_config(Config {
+            integer_variables: IntegerMode::All,
+            ..Config::default()
+        }))
+        .unwrap(),
+        // Rounding the relaxed optimum of (42/11, 80/11) gives (3, 7) with a value of 23, but the
+        // actual integer optimum isn't adjacent to it
+        SolutionSet {
+            objective_function_value: 24.into(),
             variable_values: HashMap::from([
-                (VariableType::Original("x"), 3.into()),
-                (VariableType::Original("y"), 7.into()),
-            ])
+                (VariableType::Original("x"), 4.into()),
+                (VariableType::Original("y"), 6.into()),
+            ]),
+            basic_variables: HashSet::new(),
+            shadow_prices: HashMap::new(),
+            has_alternative_optima: false,
+            objective_name: None,
+            variable_naming: VariableNaming::Symbolic,
         },
         "Ch 7 Example 12"
     );
@@ -176,11 +514,13 @@ fn solve_with_simplex_tableaux_integer_solutions_test() {
             &LinProgSystemBuilder {
                 variables: Variables::from(["x", "y", "z"]),
                 config: Config {
-                    integer_solutions: true,
+                    integer_variables: IntegerMode::All,
+                    ..Config::default()
                 },
                 objective_function_builder: |vars| {
                     ObjectiveFunction::Maximise(
                         Expression::nom_parse("10x + 12y + 8z", vars).unwrap().1,
+                        Frac::zero(),
                     )
                 },
                 constraints_builder: |vars| {
@@ -199,7 +539,12 @@ fn solve_with_simplex_tableaux_integer_solutions_test() {
                 (VariableType::Original("x"), Frac::zero()),
                 (VariableType::Original("y"), 2.into()),
                 (VariableType::Original("z"), 2.into()),
-            ])
+            ]),
+            basic_variables: HashSet::new(),
+            shadow_prices: HashMap::new(),
+            has_alternative_optima: false,
+            objective_name: None,
+            variable_naming: VariableNaming::Symbolic,
         },
         "Ch 7 Example 12"
     );
@@ -216,7 +561,8 @@ fn create_initial_tableau_test() {
                 variables: Variables::from(["x", "y", "z"]),
                 config: Config::default(),
                 objective_function_builder: |vars| ObjectiveFunction::Maximise(
-                    Expression::nom_parse("3x + 5y - z", vars).unwrap().1
+                    Expression::nom_parse("3x + 5y - z", vars).unwrap().1,
+                    Frac::zero()
                 ),
                 constraints_builder: |vars| vec![
                     Constraint::nom_parse("x - 2y + 10z <= 100", vars)
@@ -253,7 +599,8 @@ fn create_initial_tableau_test() {
                 variables: Variables::from(["x", "y", "z", "w"]),
                 config: Config::default(),
                 objective_function_builder: |vars| ObjectiveFunction::Maximise(
-                    Expression::nom_parse("3x + 5y - z + 1.5w", vars).unwrap().1
+                    Expression::nom_parse("3x + 5y - z + 1.5w", vars).unwrap().1,
+                    Frac::zero()
                 ),
                 constraints_builder: |vars| vec![
                     Constraint::nom_parse("x - 10z <= 100", vars).unwrap().1,
@@ -289,23 +636,10 @@ fn create_initial_tableau_test() {
 
 #[test]
 #[traced_test]
-fn tableau_iteration_test() -> Result<(), NoFeasibleSolution> {
+fn tableau_iteration_test() -> Result<(), IterationError> {
     use pretty_assertions::assert_eq;
 
-    let system = LinProgSystemBuilder {
-        variables: Variables::from(["x", "y"]),
-        config: Config::default(),
-        objective_function_builder: |vars| {
-            ObjectiveFunction::Maximise(Expression::nom_parse("3x + 2y", vars).unwrap().1)
-        },
-        constraints_builder: |vars| {
-            vec![
-                Constraint::nom_parse("5x + 7y <= 70", vars).unwrap().1,
-                Constraint::nom_parse("10x + 3y <= 60", vars).unwrap().1,
-            ]
-        },
-    }
-    .build();
+    let system = ch7_example8();
     let mut tableau = Tableau::create_initial(&system).unwrap();
 
     assert_eq!(
@@ -339,8 +673,6 @@ fn tableau_iteration_test() -> Result<(), NoFeasibleSolution> {
         "Ch 7 Example 8 after 1 complete iteration"
     );
 
-    // The floating point error is noticeable in this one. Those two values in the bottom row that
-    // look like 0.2 should be exactly 0.2
     tableau.do_iteration()?;
     assert_eq!(
         tableau.to_string(),
@@ -356,6 +688,1128 @@ fn tableau_iteration_test() -> Result<(), NoFeasibleSolution> {
 └───────────┴───┴───┴───────┴───────┴───────┴───┴────────┘"#,
         "Ch 7 Example 8 after 2 complete iterations"
     );
+    assert_tableau_cell(&tableau, "ObjFunc#", "sl#0", Frac::new(1u32, 5u32));
+    assert_tableau_cell(&tableau, "ObjFunc#", "sl#1", Frac::new(1u32, 5u32));
+    assert_tableau_cell(&tableau, "x", "Value", Frac::new(42u32, 11u32));
 
     Ok(())
 }
+
+#[test]
+#[traced_test]
+fn pivot_element_test() -> Result<(), IterationError> {
+    let system = ch7_example8();
+    let mut tableau = Tableau::create_initial(&system).unwrap();
+    assert_eq!(
+        tableau.pivot_element(),
+        Some(Frac::from(10)),
+        "Ch 7 Example 8 initial"
+    );
+
+    tableau.do_iteration()?;
+    assert_eq!(tableau.pivot_element(), Some(Frac::new(11u32, 2u32)));
+
+    // There's no pivot once the tableau is optimal
+    tableau.do_iteration()?;
+    assert!(!tableau.negatives_in_bottom_row());
+    assert_eq!(tableau.pivot_element(), None);
+
+    // Or if there's nothing positive in the pivot column, since the system is unbounded
+    let unbounded = LinProgSystem::build_from_str("x y\nmaximise x + 2y\nx - y <= 2").unwrap();
+    let tableau = Tableau::create_initial(&unbounded).unwrap();
+    assert_eq!(tableau.pivot_element(), None);
+    assert_eq!(
+        tableau.clone().do_iteration(),
+        Err(IterationError::Unbounded)
+    );
+
+    Ok(())
+}
+
+#[test]
+#[traced_test]
+fn tableau_right_align_numbers_test() -> Result<(), IterationError> {
+    let system = ch7_example8_with_config(Config {
+        right_align_numbers: true,
+        ..Config::default()
+    });
+    let mut tableau = Tableau::create_initial(&system).unwrap();
+    tableau.do_iteration()?;
+
+    assert_eq!(
+        tableau.to_string(),
+        r#"
+┌───────────┬───┬────────┬──────┬──────┬───────┬───┬────────┐
+│ Basic var │ x │ y      │ sl#0 │ sl#1 │ Value │ θ │ Row op │
+├───────────┼───┼────────┼──────┼──────┼───────┼───┼────────┤
+│ sl#0      │ 0 │   11/2 │    1 │ -1/2 │    40 │   │        │
+├───────────┼───┼────────┼──────┼──────┼───────┼───┼────────┤
+│ x         │ 1 │   3/10 │    0 │ 1/10 │     6 │   │        │
+├───────────┼───┼────────┼──────┼──────┼───────┼───┼────────┤
+│ ObjFunc#  │ 0 │ -11/10 │    0 │ 3/10 │    18 │   │        │
+└───────────┴───┴────────┴──────┴──────┴───────┴───┴────────┘"#
+    );
+
+    Ok(())
+}
+
+#[test]
+#[traced_test]
+fn tableau_ascii_output_test() -> Result<(), IterationError> {
+    // Show the tableau from the first iteration, with theta values and row operations
+    let working_tableau = |ascii_output| -> Result<String, IterationError> {
+        let system = ch7_example8_with_config(Config {
+            ascii_output,
+            ..Config::default()
+        });
+        let mut tableau = Tableau::create_initial(&system).unwrap();
+        let mut working = String::new();
+        tableau.do_iteration_showing_working(|tableau| working = tableau.to_string())?;
+        Ok(working)
+    };
+
+    assert_eq!(
+        working_tableau(false)?,
+        r#"
+┌───────────┬────┬────┬──────┬──────┬───────┬────┬────────┐
+│ Basic var │ x  │ y  │ sl#0 │ sl#1 │ Value │ θ  │ Row op │
+├───────────┼────┼────┼──────┼──────┼───────┼────┼────────┤
+│ sl#0      │ 5  │ 7  │ 1    │ 0    │ 70    │ 14 │ -5 R2  │
+├───────────┼────┼────┼──────┼──────┼───────┼────┼────────┤
+│ x         │ 10 │ 3  │ 0    │ 1    │ 60    │ 6  │ ×1/10  │
+├───────────┼────┼────┼──────┼──────┼───────┼────┼────────┤
+│ ObjFunc#  │ -3 │ -2 │ 0    │ 0    │ 0     │    │ +3 R2  │
+└───────────┴────┴────┴──────┴──────┴───────┴────┴────────┘"#
+    );
+    assert_eq!(
+        working_tableau(true)?,
+        r#"
++-----------+----+----+------+------+-------+-------+--------+
+| Basic var | x  | y  | sl#0 | sl#1 | Value | theta | Row op |
++-----------+----+----+------+------+-------+-------+--------+
+| sl#0      | 5  | 7  | 1    | 0    | 70    | 14    | -5 R2  |
++-----------+----+----+------+------+-------+-------+--------+
+| x         | 10 | 3  | 0    | 1    | 60    | 6     | *1/10  |
++-----------+----+----+------+------+-------+-------+--------+
+| ObjFunc#  | -3 | -2 | 0    | 0    | 0     |       | +3 R2  |
++-----------+----+----+------+------+-------+-------+--------+"#
+    );
+
+    Ok(())
+}
+
+#[test]
+#[traced_test]
+fn solve_with_trace_test() {
+    let system = ch7_example8();
+    let trace = solve_with_trace(&system).unwrap();
+
+    assert_eq!(
+        trace
+            .snapshots
+            .iter()
+            .map(|snapshot| snapshot.title.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "Initial tableau",
+            "Iteration 1",
+            "Iteration 2",
+            "Final tableau"
+        ]
+    );
+
+    let to_strings = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|&(row, op)| (row.to_string(), op.to_string()))
+            .collect()
+    };
+    assert_eq!(
+        trace.pivots,
+        vec![
+            PivotInfo {
+                iteration: 1,
+                entering: "x".to_string(),
+                leaving: "sl#1".to_string(),
+                row_operations: to_strings(&[
+                    ("sl#0", "-5 R2"),
+                    ("x", "×1/10"),
+                    ("ObjFunc#", "+3 R2")
+                ]),
+            },
+            PivotInfo {
+                iteration: 2,
+                entering: "y".to_string(),
+                leaving: "sl#0".to_string(),
+                row_operations: to_strings(&[
+                    ("y", "×2/11"),
+                    ("x", "-3/10 R1"),
+                    ("ObjFunc#", "+11/10 R1")
+                ]),
+            },
+        ]
+    );
+
+    // The snapshots keep the row operations as they were computed
+    assert_eq!(
+        trace.snapshots[1].tableau["rows"][1]["values"][6],
+        json!("×1/10")
+    );
+    assert_eq!(
+        trace.snapshots[3].tableau["rows"][0]["values"][6],
+        Value::Null
+    );
+    assert_eq!(
+        trace.solution,
+        solve_with_simplex_tableaux(&system).unwrap().to_json()
+    );
+
+    let json = serde_json::to_value(&trace).unwrap();
+    assert_eq!(json["pivots"][0]["entering"], json!("x"));
+    assert_eq!(
+        json["pivots"][0]["row_operations"][0],
+        json!(["sl#0", "-5 R2"])
+    );
+    assert_eq!(json["snapshots"][0]["title"], json!("Initial tableau"));
+
+    let coordinate = |value: &Value| {
+        (
+            value["numerator"].as_i64().unwrap(),
+            value["denominator"].as_u64().unwrap(),
+        )
+    };
+    assert_eq!(
+        trace
+            .vertices
+            .iter()
+            .map(|vertex| (coordinate(&vertex["x"]), coordinate(&vertex["y"])))
+            .collect::<Vec<_>>(),
+        vec![((0, 1), (0, 1)), ((6, 1), (0, 1)), ((42, 11), (80, 11))],
+        "Ch 7 Example 8 visits (0, 0), then (6, 0), then (42/11, 80/11)"
+    );
+}
+
+#[test]
+#[traced_test]
+fn simplex_driver_test() {
+    let system = ch7_example8();
+    let mut driver = SimplexDriver::new(&system).unwrap();
+
+    let pivots: Vec<(String, String)> = (0..2)
+        .map(|_| match driver.step().unwrap() {
+            StepOutcome::Pivoted(pivot) => (pivot.entering, pivot.leaving),
+            outcome => panic!("Expected a pivot, but got {outcome:?}"),
+        })
+        .collect();
+    assert_eq!(
+        pivots,
+        vec![
+            ("x".to_string(), "sl#1".to_string()),
+            ("y".to_string(), "sl#0".to_string())
+        ]
+    );
+    assert_eq!(driver.tableau().vertex()[0], ("x", Frac::new(42u32, 11u32)));
+
+    assert_eq!(driver.step().unwrap(), StepOutcome::Optimal);
+    assert_eq!(driver.step().unwrap(), StepOutcome::Optimal);
+    assert_eq!(driver.iterations(), 2);
+    assert_eq!(
+        driver.solution().unwrap(),
+        solve_with_simplex_tableaux(&system).unwrap()
+    );
+
+    let unbounded = LinProgSystem::build_from_str("x y\nmaximise x + y\nx - y <= 1").unwrap();
+    let mut driver = SimplexDriver::new(&unbounded).unwrap();
+    assert!(matches!(driver.step().unwrap(), StepOutcome::Pivoted(_)));
+    assert_eq!(driver.step().unwrap(), StepOutcome::Unbounded);
+    assert_eq!(driver.step().unwrap(), StepOutcome::Unbounded);
+    assert_eq!(
+        driver
+            .solution()
+            .unwrap_err()
+            .downcast_ref::<IterationError>(),
+        Some(&IterationError::Unbounded)
+    );
+
+    let infeasible =
+        LinProgSystem::build_from_str("x y\nmaximise x + y\nx + y <= 1; x >= 2").unwrap();
+    let mut driver = SimplexDriver::new(&infeasible).unwrap();
+    let outcome = loop {
+        match driver.step().unwrap() {
+            StepOutcome::Pivoted(_) => (),
+            outcome => break outcome,
+        }
+    };
+    assert_eq!(outcome, StepOutcome::Infeasible);
+    assert!(driver
+        .solution()
+        .unwrap_err()
+        .downcast_ref::<NoFeasibleSolution>()
+        .is_some());
+}
+
+#[test]
+#[traced_test]
+fn random_pivot_rule_seed_test() {
+    let input =
+        "x y z\nmaximise 2x + 3y + 4z\n3x + 2y + z <= 10; 2x + 5y + 3z <= 15; x + y + z <= 6";
+    let config = Config {
+        pivot_rule: PivotRule::Random,
+        rng_seed: Some(42),
+        ..Config::default()
+    };
+
+    let system = LinProgSystem::build_from_str_with_config(input, config.clone()).unwrap();
+    let same_system = LinProgSystem::build_from_str_with_config(input, config).unwrap();
+    let trace = solve_with_trace(&system).unwrap();
+    assert_eq!(trace, solve_with_trace(&same_system).unwrap());
+
+    let most_negative = LinProgSystem::build_from_str(input).unwrap();
+    assert_eq!(
+        trace.solution["objective_function_value"],
+        solve_with_trace(&most_negative).unwrap().solution["objective_function_value"]
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_and_progress_test() {
+    let system = ch7_example8();
+
+    let mut calls = vec![];
+    solve_with_simplex_tableaux_and_progress(&system, |iterations, value| {
+        calls.push((iterations, value))
+    })
+    .unwrap();
+
+    assert_eq!(
+        calls,
+        vec![(1, 18.into()), (2, 26.into())],
+        "Ch 7 Example 8 takes 2 iterations"
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_and_progress_branch_and_bound_test() {
+    let system = ch7_example8_with_config(Config {
+        integer_variables: IntegerMode::All,
+        ..Config::default()
+    });
+
+    let mut calls = vec![];
+    let solution =
+        solve_with_simplex_tableaux_and_progress(&system, |step, value| calls.push((step, value)))
+            .unwrap();
+    assert_eq!(solution.objective_function_value, 24.into());
+
+    assert_eq!(
+        calls[..2],
+        [(1, 18.into()), (2, 26.into())],
+        "The relaxation takes 2 iterations, like Ch 7 Example 8"
+    );
+    assert_eq!(
+        calls.len(),
+        15,
+        "Ch 7 Example 12 explores 13 branch and bound nodes"
+    );
+    assert!(calls
+        .iter()
+        .enumerate()
+        .all(|(idx, &(step, _))| step == idx + 1));
+
+    // The best value never gets worse once we have an integer solution
+    assert_eq!(calls[4], (5, 23.into()));
+    assert!(calls[4..].windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert_eq!(calls.last().unwrap().1, 24.into());
+}
+
+#[test]
+#[traced_test]
+fn tableau_to_html_test() -> Result<(), IterationError> {
+    let system = ch7_example8();
+    let mut tableau = Tableau::create_initial(&system).unwrap();
+    tableau.do_iteration()?;
+
+    let html = tableau.to_html();
+    assert!(html.starts_with("<table"));
+    assert!(html.ends_with("</table>"));
+    assert_eq!(
+        html.matches("<th>").count(),
+        8,
+        "Basic var, 4 variables, value, θ, row op"
+    );
+    assert_eq!(
+        html.matches("<tr>").count(),
+        4,
+        "Header, 2 constraints, objective"
+    );
+    assert!(html.contains("<td><sup>11</sup>&frasl;<sub>2</sub></td>"));
+    assert!(html.contains("<td>-<sup>11</sup>&frasl;<sub>10</sub></td>"));
+    assert!(html.contains("<th scope=\"row\">x</th>"));
+
+    Ok(())
+}
+
+#[test]
+#[traced_test]
+fn tableau_to_latex_test() -> Result<(), IterationError> {
+    let system = ch7_example8();
+    let mut tableau = Tableau::create_initial(&system).unwrap();
+
+    let latex = tableau.to_latex();
+    assert!(latex.starts_with("\\begin{tabular}{l|cccc|c|c|l}\n"));
+    assert!(latex.ends_with("\\end{tabular}"));
+    assert!(latex.contains("Basic var & x & y & sl\\#0 & sl\\#1 & Value & $\\theta$ & Row op \\\\"));
+    assert!(latex.contains(
+        "\\hline\nObjFunc\\# & \\underline{$-3$} & \\underline{$-2$} & $0$ & $0$ & $0$ &  &  \\\\"
+    ));
+    assert!(!latex.contains("\\mathbf"));
+
+    // Midway through the iteration, the row ops are populated and the pivot is bold
+    let mut working = String::new();
+    tableau.do_iteration_showing_working(|tableau| working = tableau.to_latex())?;
+    assert!(
+        working.contains("sl\\#0 & $5$ & $7$ & $1$ & $0$ & $70$ & $14$ & $-5 R_{2}$ \\\\"),
+        "{working}"
+    );
+    assert!(
+        working.contains(
+            "x & $\\mathbf{10}$ & $3$ & $0$ & $1$ & $60$ & $6$ & $\\times \\frac{1}{10}$ \\\\"
+        ),
+        "{working}"
+    );
+
+    // Afterwards, the fractions are written with \frac
+    let latex = tableau.to_latex();
+    assert!(latex.contains("$\\frac{11}{2}$"), "{latex}");
+    assert!(latex.contains("\\underline{$-\\frac{11}{10}$}"), "{latex}");
+
+    Ok(())
+}
+
+#[test]
+#[traced_test]
+fn tableau_to_markdown_test() -> Result<(), IterationError> {
+    let system = ch7_example8();
+    let mut tableau = Tableau::create_initial(&system).unwrap();
+
+    let markdown = tableau.to_markdown();
+    let lines: Vec<&str> = markdown.lines().collect();
+    assert_eq!(
+        lines[..2],
+        [
+            "| Basic var | x | y | sl#0 | sl#1 | Value | θ | Row op |",
+            "| --- | --- | --- | --- | --- | --- | --- | --- |"
+        ]
+    );
+    assert_eq!(lines[4], "| ObjFunc# | -3 | -2 | 0 | 0 | 0 |  |  |");
+    assert_eq!(lines.len(), 5);
+
+    // Midway through the iteration, the pivot is bold
+    let mut working = String::new();
+    tableau.do_iteration_showing_working(|tableau| working = tableau.to_markdown())?;
+    assert!(
+        working.contains("| x | **10** | 3 | 0 | 1 | 60 | 6 | ×1/10 |"),
+        "{working}"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[traced_test]
+fn to_json_test() -> Result<(), IterationError> {
+    let system = ch7_example8();
+    let mut tableau = Tableau::create_initial(&system).unwrap();
+    tableau.do_iteration()?;
+
+    let json = tableau.to_json();
+    assert_eq!(
+        json["column_labels"],
+        json!(["x", "y", "sl#0", "sl#1", "Value", "θ", "Row op"])
+    );
+    assert_eq!(json["rows"][1]["label"], "x");
+    assert_eq!(
+        json["rows"][1]["values"][4],
+        json!({"numerator": 6, "denominator": 1, "decimal": 6.0})
+    );
+    assert_eq!(json["rows"][1]["values"][5], Value::Null);
+
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+    assert_eq!(
+        solution.to_json(),
+        json!({
+            "objective_function_value": {"numerator": 26, "denominator": 1, "decimal": 26.0},
+            "variable_values": {
+                "x": {"numerator": 42, "denominator": 11, "decimal": 42.0 / 11.0},
+                "y": {"numerator": 80, "denominator": 11, "decimal": 80.0 / 11.0},
+                "sl#0": {"numerator": 0, "denominator": 1, "decimal": 0.0},
+                "sl#1": {"numerator": 0, "denominator": 1, "decimal": 0.0},
+            },
+            "basic_variables": ["x", "y"],
+            "shadow_prices": {
+                "0": {"numerator": 1, "denominator": 5, "decimal": 0.2},
+                "1": {"numerator": 1, "denominator": 5, "decimal": 0.2},
+            },
+            "has_alternative_optima": false,
+            "objective_name": null,
+            "variable_naming": "Symbolic",
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+#[traced_test]
+fn solve_warm_start_test() {
+    let original = ch7_example8();
+    let mut previous = Tableau::create_initial(&original).unwrap();
+    while previous.negatives_in_bottom_row() {
+        previous.do_iteration().unwrap();
+    }
+
+    // 50 and 66 keep the old basis feasible, but 20 needs the dual simplex
+    for (rhs, expected_warm_iterations) in [(50, 0), (66, 0), (20, 1)] {
+        let modified = original.with_constraint_constant(1, rhs.into());
+
+        let mut cold_iterations = 0;
+        let cold = solve_with_simplex_tableaux_and_progress(&modified, |i, _| cold_iterations = i)
+            .unwrap();
+
+        let mut warm_iterations = 0;
+        let warm = solve_warm_start_and_progress(&modified, &previous, |i, _| warm_iterations = i)
+            .unwrap();
+
+        assert_eq!(warm, cold, "RHS of {rhs}");
+        assert!(
+            warm_iterations < cold_iterations,
+            "RHS of {rhs}: {warm_iterations} warm vs {cold_iterations} cold"
+        );
+        assert_eq!(warm_iterations, expected_warm_iterations, "RHS of {rhs}");
+    }
+}
+
+#[test]
+#[traced_test]
+fn solve_from_basis_test() {
+    let system = ch7_example8();
+    let cold = solve_with_simplex_tableaux(&system).unwrap();
+
+    let optimal_basis = solve_to_final_tableau(&system).unwrap().basis();
+    let mut iterations = 0;
+    let warm =
+        solve_from_basis_and_progress(&system, optimal_basis, |i, _| iterations = i).unwrap();
+    assert_eq!(warm, cold);
+    assert_eq!(iterations, 0);
+
+    // The basis can be given by name, in any order
+    let basis = vec![VariableType::Original("y"), VariableType::Original("x")];
+    assert_eq!(solve_from_basis(&system, basis).unwrap(), cold);
+
+    let error = |basis| solve_from_basis(&system, basis).unwrap_err().to_string();
+    assert_eq!(
+        error(vec![
+            VariableType::Artificial(0),
+            VariableType::Original("x")
+        ]),
+        "Cannot warm start with artificial variable ar#0 in the basis"
+    );
+    assert_eq!(
+        error(vec![
+            VariableType::Original("z"),
+            VariableType::Original("x")
+        ]),
+        "Variable z is not in the tableau"
+    );
+    assert_eq!(
+        error(vec![VariableType::Original("x")]),
+        "The basis has 1 variables, but the tableau has 2 rows"
+    );
+    assert_eq!(
+        error(vec![
+            VariableType::Original("x"),
+            VariableType::Original("x")
+        ]),
+        "Variable x is in the basis more than once"
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_objective_only_variable_test() {
+    let build_system = |objective_function: &'static str| {
+        LinProgSystemBuilder {
+            variables: Variables::from(["x", "y", "z"]),
+            config: Config::default(),
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse(objective_function, vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("5x + 7y <= 70", vars).unwrap().1,
+                    Constraint::nom_parse("10x + 3y <= 60", vars).unwrap().1,
+                ]
+            },
+        }
+        .build()
+    };
+
+    let error = solve_with_simplex_tableaux(&build_system("3x + 2y + z")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Objective function is unbounded, since z is not in any constraints"
+    );
+
+    let system = build_system("3x + 2y - z");
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+    assert_eq!(solution.objective_function_value, Frac::from(26));
+    assert_eq!(
+        solution.variable_values[&VariableType::Original("z")],
+        Frac::zero()
+    );
+}
+
+/// Beale's example, which cycles forever with the textbook pivot rules.
+fn beales_example(config: Config) -> LinProgSystem {
+    LinProgSystemBuilder {
+        variables: Variables::from(["a", "b", "c", "d"]),
+        config,
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("0.75a - 20b + 0.5c - 6d", vars)
+                    .unwrap()
+                    .1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("0.25a - 8b - c + 9d <= 0", vars)
+                    .unwrap()
+                    .1,
+                Constraint::nom_parse("0.5a - 12b - 0.5c + 3d <= 0", vars)
+                    .unwrap()
+                    .1,
+                Constraint::nom_parse("c <= 1", vars).unwrap().1,
+            ]
+        },
+    }
+    .build()
+}
+
+/// Check that the system was solved without cycling and has the optimal solution of Beale's
+/// example.
+#[track_caller]
+fn assert_solves_beales_example(system: &LinProgSystem) {
+    let solution = solve_with_simplex_tableaux_and_progress(system, |iterations, _| {
+        assert!(iterations < 20, "The simplex algorithm should not cycle");
+    })
+    .unwrap();
+    assert_eq!(solution.objective_function_value, Frac::new(5u32, 4u32));
+    assert_eq!(
+        solution.variable_values[&VariableType::Original("a")],
+        Frac::from(1)
+    );
+    assert_eq!(
+        solution.variable_values[&VariableType::Original("c")],
+        Frac::from(1)
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_lexicographic_test() {
+    assert_solves_beales_example(&beales_example(Config {
+        pivot_rule: PivotRule::Lexicographic,
+        ..Config::default()
+    }));
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_anti_cycling_test() {
+    // Without anti-cycling, the same tableaux come up again and again
+    let system = beales_example(Config::default());
+    let mut tableau = Tableau::create_initial(&system).unwrap();
+    for _ in 0..50 {
+        assert!(tableau.negatives_in_bottom_row());
+        tableau.do_iteration().unwrap();
+    }
+    assert!(tableau.negatives_in_bottom_row());
+
+    assert_solves_beales_example(&beales_example(Config {
+        anti_cycling: true,
+        ..Config::default()
+    }));
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_max_iterations_test() {
+    let system = beales_example(Config {
+        max_iterations: 50,
+        ..Config::default()
+    });
+
+    let mut iterations = 0;
+    let error =
+        solve_with_simplex_tableaux_and_progress(&system, |i, _| iterations = i).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Exceeded 50 iterations without converging"
+    );
+    assert_eq!(iterations, 50);
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_unbounded_test() {
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("x + y", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("x - y <= 2", vars).unwrap().1,
+                Constraint::nom_parse("x <= 5", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+
+    let error = solve_with_simplex_tableaux(&system).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<IterationError>(),
+        Some(&IterationError::Unbounded)
+    );
+    assert_eq!(error.to_string(), "Objective function is unbounded");
+}
+
+#[test]
+#[traced_test]
+fn solution_set_basis_summary_test() {
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y", "z"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("10x + 12y + 8z", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("2x + 2y <= 5", vars).unwrap().1,
+                Constraint::nom_parse("5x + 3y + 4z <= 15", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+
+    // y and z are basic, while x and both slack variables are non-basic
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+    assert_eq!(solution.basis_summary(), (2, 3));
+}
+
+#[test]
+#[traced_test]
+fn solve_with_objective_function_constant_test() {
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("3x + 2y", vars).unwrap().1,
+                50.into(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("5x + 7y ≤ 70", vars).unwrap().1,
+                Constraint::nom_parse("10x + 3y ≤ 60", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+
+    // The constant doesn't move the optimum, only its value
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+    assert_eq!(solution.objective_function_value, 76.into());
+    assert_eq!(
+        solution.variable_values[&VariableType::Original("x")],
+        Frac::new(42u32, 11u32)
+    );
+    assert_eq!(
+        solution.variable_values[&VariableType::Original("y")],
+        Frac::new(80u32, 11u32)
+    );
+
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Minimise(
+                Expression::nom_parse("3x - y", vars).unwrap().1,
+                -Frac::from(10),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("2x + y ≤ 12", vars).unwrap().1,
+                Constraint::nom_parse("x + 4y <= 8", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+    assert_eq!(solution.objective_function_value, -Frac::from(12));
+    assert_eq!(
+        solution.variable_values[&VariableType::Original("y")],
+        2.into()
+    );
+}
+
+#[test]
+#[traced_test]
+fn solution_set_shadow_prices_test() {
+    let system = ch7_example8();
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+    assert_eq!(
+        solution.shadow_prices(),
+        &HashMap::from([(0, Frac::new(1u32, 5u32)), (1, Frac::new(1u32, 5u32))]),
+        "Ch 7 Example 8"
+    );
+
+    // Increasing each constant by 1 should increase the optimal value by the shadow price
+    for (idx, constant) in [(0, 71), (1, 61)] {
+        let modified = system.with_constraint_constant(idx, constant.into());
+        assert_eq!(
+            solve_with_simplex_tableaux(&modified)
+                .unwrap()
+                .objective_function_value,
+            solution.objective_function_value + solution.shadow_prices()[&idx]
+        );
+    }
+}
+
+#[test]
+#[traced_test]
+fn solution_set_has_alternative_optima_test() {
+    // The objective function is parallel to the first constraint, so every point on the edge
+    // between (3, 1) and (0, 4) is optimal
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("x + y", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("x + y <= 4", vars).unwrap().1,
+                Constraint::nom_parse("x <= 3", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+
+    assert_eq!(solution.objective_function_value, 4.into());
+    assert!(solution.has_alternative_optima());
+    assert!(solution
+        .to_string()
+        .starts_with("\nObjFunc# = 4 (alternative optima exist)\n"));
+
+    // Ch 7 Example 8 has a unique optimum
+    let unique_system = ch7_example8();
+    let solution = solve_with_simplex_tableaux(&unique_system).unwrap();
+
+    assert!(!solution.has_alternative_optima());
+    assert!(!solution.to_string().contains("alternative optima"));
+}
+
+#[test]
+#[traced_test]
+fn solution_set_as_fixing_constraints_test() {
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y", "z"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("10x + 12y + 8z", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("2x + 2y <= 5", vars).unwrap().1,
+                Constraint::nom_parse("5x + 3y + 4z <= 15", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+    let fixing_constraints = solution.as_fixing_constraints();
+    assert_eq!(
+        fixing_constraints
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![
+            "1x ≤ 0",
+            "1x ≥ 0",
+            "1y ≤ 5/2",
+            "1y ≥ 5/2",
+            "1z ≤ 15/8",
+            "1z ≥ 15/8"
+        ]
+    );
+
+    // Maximising something else with the fixing constraints must still give the same point, since
+    // it's the only feasible one
+    let fixed_system = system.rebuild(
+        system.borrow_config().clone(),
+        |vars, _| ObjectiveFunction::Maximise(Expression::parse("x", vars).unwrap(), Frac::zero()),
+        |vars, mut cons| {
+            cons.extend(fixing_constraints.iter().map(|con| con.rebind(vars)));
+            cons
+        },
+    );
+    let fixed_solution = solve_with_simplex_tableaux(&fixed_system).unwrap();
+
+    for var in ["x", "y", "z"] {
+        assert_eq!(
+            fixed_solution.variable_values[&VariableType::Original(var)],
+            solution.variable_values[&VariableType::Original(var)],
+            "Value of {var}"
+        );
+    }
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_verbose_test() {
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("3x + 2y", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("5x + 7y ≤ 70", vars).unwrap().1,
+                Constraint::nom_parse("10x + 3y ≤ 60", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+
+    let mut output = Vec::new();
+    let solution = solve_with_simplex_tableaux_verbose(&system, &mut output).unwrap();
+    assert_eq!(solution, solve_with_simplex_tableaux(&system).unwrap());
+
+    let output = String::from_utf8(output).unwrap();
+    let titles: Vec<&str> = output.lines().filter(|line| line.ends_with(':')).collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Initial tableau:",
+            "Iteration 1:",
+            "Iteration 2:",
+            "Final tableau:"
+        ]
+    );
+
+    // The first iteration pivots on x in the second row, so the row ops should be shown
+    let iteration_1 = output
+        .split("Iteration 1:")
+        .nth(1)
+        .and_then(|rest| rest.split("Iteration 2:").next())
+        .unwrap();
+    assert!(iteration_1.contains("×1/10"), "{iteration_1}");
+    assert!(iteration_1.contains("-5 R2"), "{iteration_1}");
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_explained_test() {
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("3x + 2y", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("5x + 7y ≤ 70", vars).unwrap().1,
+                Constraint::nom_parse("10x + 3y ≤ 60", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+
+    let mut output = Vec::new();
+    let solution = solve_with_simplex_tableaux_explained(&system, &mut output).unwrap();
+    assert_eq!(solution, solve_with_simplex_tableaux(&system).unwrap());
+
+    let output = String::from_utf8(output).unwrap();
+    let titles: Vec<&str> = output
+        .lines()
+        .filter(|line| line.ends_with(':') || line.starts_with("Iteration"))
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Initial tableau:",
+            "Iteration 1: Most negative reduced cost is -3 under column x, so x enters. The \
+            minimum positive ratio is 6 in row sl#1, so sl#1 leaves.",
+            "Iteration 2: Most negative reduced cost is -11/10 under column y, so y enters. The \
+            minimum positive ratio is 80/11 in row sl#0, so sl#0 leaves.",
+        ]
+    );
+
+    // Each explanation is followed by the tableau that it results in
+    let iteration_2 = output.split("Iteration 2:").nth(1).unwrap();
+    assert!(iteration_2.contains("80/11"), "{iteration_2}");
+    assert!(iteration_2.contains("│ 26 "), "{iteration_2}");
+}
+
+#[test]
+#[traced_test]
+fn explain_next_pivot_test() {
+    let build_system = |config| {
+        LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config,
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("x + y", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("x + y <= 4", vars).unwrap().1,
+                    Constraint::nom_parse("x <= 4", vars).unwrap().1,
+                ]
+            },
+        }
+        .build()
+    };
+
+    for (config, expected) in [
+        (
+            Config::default(),
+            "Most negative reduced cost is -1 under column x, which is the first of the tied \
+            columns, so x enters. The minimum positive ratio is 4 in row sl#0, which is the first \
+            of the tied rows, so sl#0 leaves.",
+        ),
+        (
+            Config {
+                column_tie_break: ColumnTieBreak::HighestIndex,
+                ..Config::default()
+            },
+            "Most negative reduced cost is -1 under column y, which is the last of the tied \
+            columns, so y enters. The minimum positive ratio is 4 in row sl#0, so sl#0 leaves.",
+        ),
+        (
+            Config {
+                pivot_rule: PivotRule::PreferOriginal,
+                ..Config::default()
+            },
+            "Most negative reduced cost is -1 under column x, which is preferred to the other \
+            tied columns by its type of variable, so x enters. The minimum positive ratio is 4 in \
+            row sl#0, which is the first of the tied rows, so sl#0 leaves.",
+        ),
+        (
+            Config {
+                pivot_rule: PivotRule::Lexicographic,
+                ..Config::default()
+            },
+            "Most negative reduced cost is -1 under column x, which is the first of the tied \
+            columns, so x enters. The minimum positive ratio is 4 in row sl#1, breaking the tie \
+            lexicographically, so sl#1 leaves.",
+        ),
+        (
+            Config {
+                anti_cycling: true,
+                ..Config::default()
+            },
+            "The first column with a negative reduced cost is x, with -1, so x enters by Bland's \
+            rule. The minimum positive ratio is 4 in row sl#0, breaking the tie by Bland's rule, \
+            so sl#0 leaves.",
+        ),
+    ] {
+        let system = build_system(config);
+        let tableau = Tableau::create_initial(&system).unwrap();
+        assert_eq!(tableau.explain_next_pivot(), expected);
+    }
+}
+
+#[test]
+#[traced_test]
+fn solve_weighted_combination_test() {
+    let solve_with_weights = |w1: Frac, w2: Frac| {
+        let system = LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config: Config::default(),
+            objective_function_builder: |vars| {
+                ObjectiveFunction::weighted_combination(
+                    ObjectiveSense::Maximise,
+                    (w1, Expression::parse("x", vars).unwrap()),
+                    (w2, Expression::parse("y", vars).unwrap()),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("5x + 7y ≤ 70", vars).unwrap().1,
+                    Constraint::nom_parse("10x + 3y ≤ 60", vars).unwrap().1,
+                ]
+            },
+        }
+        .build();
+
+        let solution = solve_with_simplex_tableaux(&system).unwrap();
+        (
+            solution.objective_function_value,
+            solution.variable_values[&VariableType::Original("x")],
+            solution.variable_values[&VariableType::Original("y")],
+        )
+    };
+
+    // 3x + 2y is optimal where the constraints meet
+    assert_eq!(
+        solve_with_weights(3.into(), 2.into()),
+        (26.into(), Frac::new(42u32, 11u32), Frac::new(80u32, 11u32))
+    );
+
+    // Only caring about x or y puts the optimum at the end of an axis
+    assert_eq!(
+        solve_with_weights(1.into(), Frac::zero()),
+        (6.into(), 6.into(), Frac::zero())
+    );
+    assert_eq!(
+        solve_with_weights(Frac::zero(), 1.into()),
+        (10.into(), Frac::zero(), 10.into())
+    );
+}
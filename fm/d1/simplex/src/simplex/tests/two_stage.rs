@@ -1,17 +1,21 @@
 use crate::{
     lin_prog::{
-        config::Config, constraint::Constraint, expression::Expression,
-        system::LinProgSystemBuilder, ObjectiveFunction, Variables,
+        config::{Config, IntegerMode, VariableNaming},
+        constraint::Constraint,
+        expression::Expression,
+        system::{LinProgSystem, LinProgSystemBuilder},
+        ObjectiveFunction, Variables,
     },
     simplex::{
-        solve_with_simplex_tableaux,
-        tableau::{NoFeasibleSolution, Tableau},
+        branch_and_bound::IntegerInfeasible,
+        solve_to_final_tableau, solve_with_simplex_tableaux,
+        tableau::{IterationError, Tableau},
         SolutionSet, VariableType,
     },
     Frac,
 };
 use fraction::Zero;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing_test::traced_test;
 
 #[test]
@@ -25,6 +29,7 @@ fn solve_with_simplex_tableaux_test() {
                 objective_function_builder: |vars| {
                     ObjectiveFunction::Maximise(
                         Expression::nom_parse("3x - 2y + z", vars).unwrap().1,
+                        Frac::zero(),
                     )
                 },
                 constraints_builder: |vars| {
@@ -47,7 +52,16 @@ fn solve_with_simplex_tableaux_test() {
                 (VariableType::Slack(0), Frac::zero()),
                 (VariableType::Surplus(0), 15.into()),
                 (VariableType::Surplus(1), 2.into()),
-            ])
+            ]),
+            basic_variables: HashSet::from([
+                VariableType::Original("x"),
+                VariableType::Surplus(0),
+                VariableType::Surplus(1),
+            ]),
+            shadow_prices: HashMap::from([(0, 3.into()), (1, Frac::zero()), (2, Frac::zero())]),
+            has_alternative_optima: false,
+            objective_name: None,
+            variable_naming: VariableNaming::Symbolic,
         },
         "Ch 7 Example 15"
     );
@@ -60,6 +74,7 @@ fn solve_with_simplex_tableaux_test() {
                 objective_function_builder: |vars| {
                     ObjectiveFunction::Maximise(
                         Expression::nom_parse("3x - 2y + z", vars).unwrap().1,
+                        Frac::zero(),
                     )
                 },
                 constraints_builder: |vars| {
@@ -79,6 +94,154 @@ fn solve_with_simplex_tableaux_test() {
     );
 }
 
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_integer_infeasible_test() {
+    let err = solve_with_simplex_tableaux(
+        &LinProgSystemBuilder {
+            variables: Variables::from(["x", "y"]),
+            config: Config {
+                integer_variables: IntegerMode::All,
+                ..Config::default()
+            },
+            objective_function_builder: |vars| {
+                ObjectiveFunction::Maximise(
+                    Expression::nom_parse("x + y", vars).unwrap().1,
+                    Frac::zero(),
+                )
+            },
+            constraints_builder: |vars| {
+                vec![
+                    Constraint::nom_parse("2x <= 1", vars).unwrap().1,
+                    Constraint::nom_parse("2x >= 1", vars).unwrap().1,
+                    Constraint::nom_parse("y <= 3", vars).unwrap().1,
+                ]
+            },
+        }
+        .build(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err.downcast_ref::<IntegerInfeasible>(),
+        Some(&IntegerInfeasible)
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_degenerate_artificial_test() {
+    // The first stage ends with ar#1 still basic at 0, which used to be left in the basis after
+    // its column was removed, so that later iterations made it non-zero
+    assert_eq!(
+        solve_with_simplex_tableaux(
+            &LinProgSystem::build_from_str(
+                "x y\nmaximise 5x + 8y\nx + y <= 6; 5x + 9y <= 45; x <= 2; y >= 4; x <= 1; y >= 5"
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .variable_values,
+        HashMap::from([
+            (VariableType::Original("x"), Frac::zero()),
+            (VariableType::Original("y"), 5.into()),
+            (VariableType::Slack(0), 1.into()),
+            (VariableType::Slack(1), Frac::zero()),
+            (VariableType::Slack(2), 2.into()),
+            (VariableType::Slack(3), 1.into()),
+            (VariableType::Surplus(0), 1.into()),
+            (VariableType::Surplus(1), Frac::zero()),
+        ])
+    );
+}
+
+#[test]
+#[traced_test]
+fn solve_with_simplex_tableaux_keep_artificial_columns_test() {
+    for input in [
+        "x y z\nmaximise 3x - 2y + z\nx + y + 2z <= 10; 2x - 3y + z >= 5; x + y >= 8",
+        "x y\nmaximise 5x + 8y\nx + y <= 6; 5x + 9y <= 45; x <= 2; y >= 4; x <= 1; y >= 5",
+        "x y\nminimise 2x + 3y\nx + y >= 4; x + 3y >= 6; x <= 5",
+    ] {
+        let removed = LinProgSystem::build_from_str(input).unwrap();
+        let kept = LinProgSystem::build_from_str_with_config(
+            input,
+            Config {
+                keep_artificial_columns: true,
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            solve_with_simplex_tableaux(&kept).unwrap(),
+            solve_with_simplex_tableaux(&removed).unwrap(),
+            "{input}"
+        );
+
+        let final_tableau = solve_to_final_tableau(&kept).unwrap().to_string();
+        assert!(final_tableau.contains("ar#0"), "{final_tableau}");
+        let final_tableau = solve_to_final_tableau(&removed).unwrap().to_string();
+        assert!(!final_tableau.contains("ar#0"), "{final_tableau}");
+    }
+}
+
+#[test]
+#[traced_test]
+fn variable_naming_test() {
+    let input = "x y\nminimise 2x + 3y\nx + y >= 4; x + 3y >= 6; x <= 5";
+    let build_system = |variable_naming| {
+        LinProgSystem::build_from_str_with_config(
+            input,
+            Config {
+                variable_naming,
+                ..Config::default()
+            },
+        )
+        .unwrap()
+    };
+
+    for (variable_naming, header, solution) in [
+        (
+            VariableNaming::Symbolic,
+            "│ Basic var   │ x  │ y  │ sl#0 │ su#0 │ su#1 │ ar#0 │ ar#1 │ Value │ θ │ Row op │",
+            "\nObjFunc# = 9\nx = 3\ny = 1\nsl#0 = 2\nsu#0 = 0\nsu#1 = 0",
+        ),
+        (
+            VariableNaming::Numbered,
+            "│ Basic var   │ x  │ y  │ s1 │ e1 │ e2 │ a1 │ a2 │ Value │ θ │ Row op │",
+            "\nObjFunc# = 9\nx = 3\ny = 1\ns1 = 2\ne1 = 0\ne2 = 0",
+        ),
+        (
+            VariableNaming::Subscripted,
+            "│ Basic var   │ x  │ y  │ s₁ │ e₁ │ e₂ │ a₁ │ a₂ │ Value │ θ │ Row op │",
+            "\nObjFunc# = 9\nx = 3\ny = 1\ns₁ = 2\ne₁ = 0\ne₂ = 0",
+        ),
+    ] {
+        let system = build_system(variable_naming);
+        let tableau = Tableau::create_initial(&system).unwrap().to_string();
+        assert_eq!(tableau.lines().nth(2), Some(header), "{variable_naming:?}");
+        assert_eq!(
+            solve_with_simplex_tableaux(&system).unwrap().to_string(),
+            solution,
+            "{variable_naming:?}"
+        );
+    }
+
+    // The naming is only for display, so the solutions are otherwise the same
+    let json_without_naming = |variable_naming| {
+        let mut json = solve_with_simplex_tableaux(&build_system(variable_naming))
+            .unwrap()
+            .to_json();
+        json.as_object_mut().unwrap().remove("variable_naming");
+        json
+    };
+    assert_eq!(
+        json_without_naming(VariableNaming::Symbolic),
+        json_without_naming(VariableNaming::Subscripted)
+    );
+}
+
 #[test]
 #[traced_test]
 fn create_initial_tableau_test() {
@@ -90,7 +253,8 @@ fn create_initial_tableau_test() {
                 variables: Variables::from(["x", "y", "z"]),
                 config: Config::default(),
                 objective_function_builder: |vars| ObjectiveFunction::Maximise(
-                    Expression::nom_parse("3x - 2y + z", vars).unwrap().1
+                    Expression::nom_parse("3x - 2y + z", vars).unwrap().1,
+                    Frac::zero()
                 ),
                 constraints_builder: |vars| vec![
                     Constraint::nom_parse("x + y + 2z <= 10", vars).unwrap().1,
@@ -125,7 +289,8 @@ fn create_initial_tableau_test() {
                 variables: Variables::from(["x", "y", "z"]),
                 config: Config::default(),
                 objective_function_builder: |vars| ObjectiveFunction::Maximise(
-                    Expression::nom_parse("3x - 2y + z", vars).unwrap().1
+                    Expression::nom_parse("3x - 2y + z", vars).unwrap().1,
+                    Frac::zero()
                 ),
                 constraints_builder: |vars| vec![
                     Constraint::nom_parse("x + y + 2z <= 8", vars).unwrap().1,
@@ -157,14 +322,47 @@ fn create_initial_tableau_test() {
 
 #[test]
 #[traced_test]
-fn tableau_iteration_test() -> Result<(), NoFeasibleSolution> {
+fn solve_with_simplex_tableaux_negative_constant_test() {
+    // -x <= -1 is really x >= 1, so it needs the two-stage simplex
+    let system = LinProgSystemBuilder {
+        variables: Variables::from(["x", "y"]),
+        config: Config::default(),
+        objective_function_builder: |vars| {
+            ObjectiveFunction::Minimise(
+                Expression::nom_parse("x + 2y", vars).unwrap().1,
+                Frac::zero(),
+            )
+        },
+        constraints_builder: |vars| {
+            vec![
+                Constraint::nom_parse("x + y <= 4", vars).unwrap().1,
+                Constraint::nom_parse("-x <= -1", vars).unwrap().1,
+            ]
+        },
+    }
+    .build();
+
+    let solution = solve_with_simplex_tableaux(&system).unwrap();
+    assert_eq!(solution.objective_function_value, Frac::from(1));
+    assert_eq!(
+        solution.variable_values[&VariableType::Original("x")],
+        Frac::from(1)
+    );
+}
+
+#[test]
+#[traced_test]
+fn tableau_iteration_test() -> Result<(), IterationError> {
     use pretty_assertions::assert_eq;
 
     let system = LinProgSystemBuilder {
         variables: Variables::from(["x", "y", "z"]),
         config: Config::default(),
         objective_function_builder: |vars| {
-            ObjectiveFunction::Maximise(Expression::nom_parse("3x - 2y + z", vars).unwrap().1)
+            ObjectiveFunction::Maximise(
+                Expression::nom_parse("3x - 2y + z", vars).unwrap().1,
+                Frac::zero(),
+            )
         },
         constraints_builder: |vars| {
             vec![
@@ -0,0 +1,135 @@
+//! This module handles recording the whole solve as structured data, so that it can be serialized
+//! and the working can be shown somewhere else, like in a web page. See [`solve_with_trace`].
+
+use super::{
+    diagnostics, frac_to_json, iterate_until_optimal, solution_from_final_tableau, tableau::Tableau,
+};
+use crate::lin_prog::system::LinProgSystem;
+use color_eyre::Result;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::instrument;
+
+/// A tableau at one point in the solve.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TableauSnapshot {
+    /// What this tableau is, like `Initial tableau` or `Iteration 1`.
+    pub title: String,
+
+    /// The tableau, in the format of [`Tableau::to_json`]. The tableau for each iteration has the
+    /// theta values and row operations as they were computed, before the row operations were
+    /// performed.
+    pub tableau: Value,
+}
+
+impl TableauSnapshot {
+    /// Take a snapshot of the given tableau.
+    fn new(title: &str, tableau: &Tableau) -> Self {
+        Self {
+            title: title.to_string(),
+            tableau: tableau.to_json(),
+        }
+    }
+}
+
+/// The pivot from one iteration of the simplex algorithm.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PivotInfo {
+    /// The number of the iteration, counting from 1.
+    pub iteration: usize,
+
+    /// The variable which entered the basis.
+    pub entering: String,
+
+    /// The variable which left the basis.
+    pub leaving: String,
+
+    /// The row operations which were applied, as pairs of the label of each row and its operation.
+    /// See [`Tableau::row_operations`].
+    pub row_operations: Vec<(String, String)>,
+}
+
+/// Everything that happened while solving a system, from the initial tableau to the solution.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SolveTrace {
+    /// The initial tableau, the tableau for each iteration, and the final tableau, in order.
+    pub snapshots: Vec<TableauSnapshot>,
+
+    /// The pivot from each iteration, in order.
+    pub pivots: Vec<PivotInfo>,
+
+    /// The values of the original variables at the vertex of the initial tableau, and then at the
+    /// vertex reached by each iteration, in order. Each one is in the format of the variables in
+    /// [`SolutionSet::to_json`](super::SolutionSet::to_json). See [`Tableau::vertex`].
+    pub vertices: Vec<Value>,
+
+    /// The solution, in the format of [`SolutionSet::to_json`](super::SolutionSet::to_json).
+    pub solution: Value,
+}
+
+/// Solve the given linear programming system using simplex tableaux, recording every tableau and
+/// pivot along the way. Everything in the trace can be serialized, so that the whole solve can be
+/// shown step by step somewhere else.
+#[instrument(skip(system))]
+pub fn solve_with_trace(system: &LinProgSystem) -> Result<SolveTrace> {
+    diagnostics::check_system(system)?;
+
+    let mut tableau: Tableau = Tableau::create_initial(system)?;
+    let mut snapshots = vec![TableauSnapshot::new("Initial tableau", &tableau)];
+    let mut row_operations = Vec::new();
+    let mut pivots = Vec::new();
+    let mut vertices = vec![vertex_to_json(&tableau)];
+
+    iterate_until_optimal(
+        system,
+        &mut tableau,
+        0,
+        &mut |_, _| (),
+        &mut |title, tableau| {
+            snapshots.push(TableauSnapshot::new(title, tableau));
+            row_operations.push(tableau.row_operations());
+        },
+        Some(&mut |_, before, after| {
+            let pivot = before.next_pivot();
+            pivots
+                .push(pivot.map(|(entering, leaving)| (entering.to_string(), leaving.to_string())));
+            vertices.push(vertex_to_json(after));
+        }),
+    )?;
+    snapshots.push(TableauSnapshot::new("Final tableau", &tableau));
+
+    let pivots = pivots
+        .into_iter()
+        .zip(row_operations)
+        .enumerate()
+        .map(|(idx, (pivot, row_operations))| {
+            let (entering, leaving) =
+                pivot.expect("Every successful iteration should have a pivot");
+            PivotInfo {
+                iteration: idx + 1,
+                entering,
+                leaving,
+                row_operations,
+            }
+        })
+        .collect();
+
+    Ok(SolveTrace {
+        snapshots,
+        pivots,
+        vertices,
+        solution: solution_from_final_tableau(system, tableau)?.to_json(),
+    })
+}
+
+/// Convert the vertex of the given tableau to a JSON object mapping each original variable to its
+/// value.
+fn vertex_to_json(tableau: &Tableau) -> Value {
+    Value::Object(
+        tableau
+            .vertex()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), frac_to_json(&value)))
+            .collect(),
+    )
+}